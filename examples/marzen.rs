@@ -1,12 +1,11 @@
 use beermaker::prelude::*;
-use beermaker::{Brewery, Process, Recipe, print_process};
+use beermaker::{Brewery, Process, Recipe, UnitSystem, print_process};
 use beermaker::{MashRest, Packaging, Style};
 
 /// This is a very small 4.25 L batch experiment that I did.
 /// I would not consider this a great recipe, because I made
 /// it up and I am a beginning brewer.  But this shows you
 /// how to use the software.
-
 fn main() {
     // Here I define my water profile
     pub const PAPAIOEA_PARK_BORE: WaterProfile = WaterProfile {
@@ -22,6 +21,10 @@ fn main() {
     // And this is my equipment
     let brewery = Brewery {
         water_profile: PAPAIOEA_PARK_BORE,
+
+        // I don't have an RO system
+        ro_fraction: None,
+
         salts_available: vec![
             Salt::CalciumChloride,
             Salt::TableSalt,
@@ -30,6 +33,8 @@ fn main() {
             Salt::BakingSoda,
         ],
         acids_available: vec![Acid::LacticAcid],
+        yeast_nutrient: YeastNutrient::FermaidO,
+        hulls_contain_gluten: false,
 
         // I mash in my kettle
         mash_tun_volume: Liters(11.0),
@@ -37,6 +42,10 @@ fn main() {
         // Pretty much no mash tun losses
         mash_tun_losses: Liters(0.01),
 
+        // About 2.5 kg of stainless steel, at room temperature
+        mash_tun_thermal_mass: Liters(0.3),
+        preheat_mash_tun: false,
+
         // I leave 1.5 liters of headroom on the 11L kettle so that it
         // doesn't boil over
         max_kettle_volume: Liters(9.5),
@@ -63,6 +72,10 @@ fn main() {
 
         // It is summer, the house runs a bit warmer
         room_temperature: Celsius(22.0),
+        serving_temperature: Celsius(4.0),
+
+        // Palmerston North is nearly at sea level
+        elevation: Meters(30.0),
 
         // I don't have a plate chiller, or a pump, or hoses.
         ice_bath: true,
@@ -138,6 +151,10 @@ fn main() {
             },
         ],
 
+        // No decoction, the infusions and Munich malt carry the maltiness
+        decoction: false,
+        mash_method: MashMethod::Infusion,
+
         // Fairly typical for two-step mash
         // Final mash thickness 3.3 L/kg, but 2.5 L/kg at first rest
         mash_thickness: 3.3,
@@ -149,22 +166,32 @@ fn main() {
         // No sugars. If you add DME or maltodextrin you can
         // put that here.
         sugars: vec![],
+        extracts: vec![],
 
         // Original graivty
         original_gravity: SpecificGravity(1.056),
 
         ibu: Ibu(21.0),
+        ibu_model: IbuModel::Tinseth,
+        abv_model: AbvModel::Hall,
 
         hops: vec![HopsProportion {
             hops: Hops::HallertauMittelfruh, // Alt: Tettnanger
             proportion: 11.0,
             timing: Minutes(60),
+            first_wort: false,
+            whirlpool: None,
+            age: None,
+            measured_alpha_acid: None,
         }],
 
+        dry_hops: vec![],
+
         boil_length: Minutes(80),
 
         // Yes, lagers should clear
         fining_desired: true,
+        fining_agent: FiningAgent::Whirlfloc,
 
         // White Labs German X Lager Yeast WLP835
         yeast: Yeast::WLP835,
@@ -186,7 +213,10 @@ fn main() {
 
     let process = Process::new(brewery, recipe, Liters(7.0));
 
-    println!("{}", print_process(&process, None, Some(70)));
+    println!(
+        "{}",
+        print_process(&process, None, Some(70), UnitSystem::Metric)
+    );
 
     let warnings = process.get_warnings();
     if warnings.is_empty() {
@@ -200,4 +230,8 @@ fn main() {
             }
         }
     }
+
+    for suggestion in process.style_autocorrect() {
+        println!("SUGGESTION: {}", suggestion);
+    }
 }
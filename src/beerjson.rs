@@ -0,0 +1,737 @@
+use crate::beerxml::{EFFICIENCY, crop_alpha_acid, fuzzy_match, trim_brackets};
+use crate::mash::MashRest;
+use crate::prelude::*;
+use serde_json::{Value, json};
+use std::fmt;
+
+/// An error importing a BeerJSON recipe
+#[derive(Debug, Clone, PartialEq)]
+pub enum BeerJsonError {
+    /// The document is not valid JSON
+    Json(String),
+
+    /// A required field is missing or has the wrong type
+    Missing(&'static str),
+
+    /// Ingredients or style that do not match anything we know about
+    Unmatched(Vec<String>),
+
+    /// A quantity in a unit we do not convert from
+    Unit {
+        /// The field holding the quantity
+        field: &'static str,
+
+        /// Its unit
+        unit: String,
+    },
+}
+
+impl fmt::Display for BeerJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "Invalid JSON: {e}"),
+            Self::Missing(field) => write!(f, "Missing {field}"),
+            Self::Unmatched(names) => write!(f, "Unmatched: {}", names.join(", ")),
+            Self::Unit { field, unit } => write!(f, "Unsupported unit {unit} for {field}"),
+        }
+    }
+}
+
+impl std::error::Error for BeerJsonError {}
+
+fn unit(unit: &str, value: impl Into<f64>) -> Value {
+    json!({ "unit": unit, "value": value.into() })
+}
+
+impl Recipe {
+    /// Export as a BeerJSON 1.0 document containing one `RecipeType`.
+    ///
+    /// Proportions are converted to weights for the given batch size,
+    /// assuming 75% mash efficiency.
+    ///
+    /// First-wort hops are added to the mash, and dry hops to the
+    /// fermentation. BeerJSON has no whirlpool temperature, so hop stands
+    /// are written as a `whirlpool` extension to the hop timing.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::too_many_lines)]
+    pub fn to_beerjson(&self, batch_size: Liters) -> String {
+        let (malt_kg, hops_g) = self.weight_scales(batch_size);
+
+        let mut fermentables: Vec<Value> = Vec::new();
+        for mp in &self.malts {
+            let lovibond: Lovabond = mp.malt.ebc().into();
+            fermentables.push(json!({
+                "name": trim_brackets(&mp.malt.to_string()),
+                "type": "grain",
+                "producer": mp.malt.maltster().to_string(),
+                "yield": { "fine_grind": unit("%", mp.malt.extract_percent()) },
+                "color": unit("Lovi", lovibond.0),
+                "amount": unit("kg", mp.proportion * malt_kg),
+            }));
+        }
+        for sp in &self.sugars {
+            let lovibond: Lovabond = sp.sugar.ebc().into();
+            fermentables.push(json!({
+                "name": trim_brackets(&sp.sugar.to_string()),
+                "type": "sugar",
+                "yield": { "fine_grind": unit("%", sp.sugar.extract_percent()) },
+                "color": unit("Lovi", lovibond.0),
+                "amount": unit("kg", sp.proportion * malt_kg),
+            }));
+        }
+        for ep in &self.extracts {
+            let lovibond: Lovabond = ep.extract.ebc().into();
+            fermentables.push(json!({
+                "name": trim_brackets(&ep.extract.to_string()),
+                "type": if ep.extract.is_dry() { "dry extract" } else { "extract" },
+                "yield": { "fine_grind": unit("%", ep.extract.extract_percent()) },
+                "color": unit("Lovi", lovibond.0),
+                "amount": unit("kg", ep.proportion * malt_kg),
+            }));
+        }
+
+        let mut hops: Vec<Value> = Vec::new();
+        for hp in &self.hops {
+            let mut timing = json!({
+                "use": if hp.first_wort { "add_to_mash" } else { "add_to_boil" },
+                "duration": unit("min", hp.timing.0 as f64),
+            });
+            if let Some((temperature, minutes)) = hp.whirlpool {
+                timing["whirlpool"] = json!({
+                    "temperature": unit("C", temperature.0),
+                    "duration": unit("min", minutes.0 as f64),
+                });
+            }
+            hops.push(json!({
+                "name": trim_brackets(&hp.hops.to_string()),
+                "alpha_acid": unit("%", hp.alpha_acid() * 100.0),
+                "timing": timing,
+                "amount": unit("g", hp.proportion * hops_g),
+            }));
+        }
+        for addition in &self.dry_hops {
+            hops.push(json!({
+                "name": trim_brackets(&addition.hops.to_string()),
+                "alpha_acid": unit("%", addition.hops.alpha_acid() * 100.0),
+                "timing": {
+                    "use": "add_to_fermentation",
+                    "time": unit("day", addition.day.0 as f64),
+                    "duration": unit("day", addition.duration.0 as f64),
+                },
+                "amount": unit("g", addition.grams_per_liter * batch_size.0),
+            }));
+        }
+
+        let culture = json!({
+            "name": self.yeast.desc(),
+            "type": if self.yeast.is_lager() { "lager" } else { "ale" },
+            "form": if self.yeast.is_dry() { "dry" } else { "liquid" },
+            "producer": self.yeast.provider().to_string(),
+            "product_id": format!("{:?}", self.yeast),
+            "attenuation": unit("%", self.yeast.attenuation() * 100.0),
+        });
+
+        let step_type = if self.decoction {
+            "decoction"
+        } else {
+            "infusion"
+        };
+        let mash_steps: Vec<Value> = self
+            .mash_rests
+            .iter()
+            .enumerate()
+            .map(|(i, rest)| {
+                json!({
+                    "name": format!("Rest {}", i + 1),
+                    "type": step_type,
+                    "step_temperature": unit("C", rest.target_temperature.0),
+                    "step_time": unit("min", rest.duration.0 as f64),
+                })
+            })
+            .collect();
+
+        // Only BJCP styles claim the BJCP guide; others are their own
+        // category
+        let mut style = json!({
+            "name": self.style.to_string(),
+            "category": self.style.to_string(),
+            "type": "beer",
+        });
+        if let (Some((number, letter)), Some(category)) =
+            (self.style.bjcp_code(), self.style.bjcp_category())
+        {
+            style["category"] = json!(category);
+            style["style_guide"] = json!("BJCP 2021");
+            style["category_number"] = json!(number);
+            style["style_letter"] = json!(letter);
+        }
+
+        let recipe = json!({
+            "name": self.name,
+            "type": self.beerxml_type().to_lowercase(),
+            "author": "beermaker",
+            "batch_size": unit("l", batch_size.0),
+            "efficiency": { "brewhouse": unit("%", EFFICIENCY * 100.0) },
+            "style": style,
+            "original_gravity": unit("sg", self.original_gravity.0),
+            "ibu_estimate": { "method": self.ibu_model.to_string() },
+            "ingredients": {
+                "fermentable_additions": fermentables,
+                "hop_additions": hops,
+                "culture_additions": [culture],
+            },
+            "mash": {
+                "name": "Mash",
+                "grain_temperature": unit("C", 20.0),
+                "mash_steps": mash_steps,
+            },
+            "boil": {
+                "boil_time": unit("min", self.boil_length.0 as f64),
+            },
+            "fermentation": {
+                "name": "Primary",
+                "fermentation_steps": [{
+                    "name": "Primary",
+                    "start_temperature": unit("C", self.ferment_temperature.0),
+                }],
+            },
+        });
+
+        let doc = json!({ "beerjson": { "version": 1.0, "recipes": [recipe] } });
+        serde_json::to_string_pretty(&doc).unwrap_or_default()
+    }
+
+    /// Import the first recipe in a BeerJSON document.
+    ///
+    /// Ingredient names are matched loosely against our own. Weights become
+    /// proportions. Fields that BeerJSON does not carry get defaults, and
+    /// the IBU target is recomputed from the hop weights. Quantities are
+    /// converted from their units, and taken as metric if they have none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed, if a required field is
+    /// missing or in a unit we do not convert from, if grains come without
+    /// mash steps, or if any ingredient or the style cannot be matched.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::too_many_lines)]
+    pub fn from_beerjson(json: &str) -> Result<Recipe, BeerJsonError> {
+        let doc: Value =
+            serde_json::from_str(json).map_err(|e| BeerJsonError::Json(e.to_string()))?;
+        let recipe = &doc["beerjson"]["recipes"][0];
+
+        let name = string(recipe, "name")?;
+        let batch_size = liters(recipe, "batch_size")?;
+
+        let mut unmatched: Vec<String> = Vec::new();
+
+        let style_name = string(&recipe["style"], "name")?;
+        let style = fuzzy_match(&style_name, |s: Style| {
+            vec![s.to_string(), format!("{s:?}")]
+        });
+        if style.is_none() {
+            unmatched.push(style_name);
+        }
+
+        let ingredients = &recipe["ingredients"];
+        let mut malts: Vec<MaltProportion> = Vec::new();
+        let mut sugars: Vec<SugarProportion> = Vec::new();
+        let mut extracts: Vec<ExtractProportion> = Vec::new();
+        for f in array(ingredients, "fermentable_additions") {
+            let name = string(f, "name")?;
+            let proportion = kilograms(f, "amount")?.0;
+            if f["type"] == "extract" || f["type"] == "dry extract" {
+                match fuzzy_match(&name, |e: MaltExtract| {
+                    vec![e.to_string(), format!("{e:?}")]
+                }) {
+                    Some(extract) => extracts.push(ExtractProportion {
+                        extract,
+                        proportion,
+                    }),
+                    None => unmatched.push(name),
+                }
+            } else if f["type"] == "sugar" {
+                match fuzzy_match(&name, |s: Sugar| vec![s.to_string(), format!("{s:?}")]) {
+                    Some(sugar) => sugars.push(SugarProportion { sugar, proportion }),
+                    None => unmatched.push(name),
+                }
+            } else {
+                match fuzzy_match(&name, |m: Malt| vec![m.to_string(), format!("{m:?}")]) {
+                    Some(malt) => malts.push(MaltProportion { malt, proportion }),
+                    None => unmatched.push(name),
+                }
+            }
+        }
+
+        let mut hops: Vec<HopsProportion> = Vec::new();
+        let mut dry_hops: Vec<DryHopAddition> = Vec::new();
+        for h in array(ingredients, "hop_additions") {
+            let name = string(h, "name")?;
+            let proportion = grams(h, "amount")?.0;
+            let timing = &h["timing"];
+            let alpha = optional(percent(h, "alpha_acid"))?;
+            let Some(hops_variety) =
+                fuzzy_match(&name, |h: Hops| vec![h.to_string(), format!("{h:?}")])
+            else {
+                unmatched.push(name);
+                continue;
+            };
+
+            if timing["use"] == "add_to_fermentation" {
+                dry_hops.push(DryHopAddition {
+                    hops: hops_variety,
+                    grams_per_liter: proportion / batch_size.0,
+                    day: optional(days(timing, "time"))?.unwrap_or(Days(0)),
+                    duration: optional(days(timing, "duration"))?.unwrap_or(Days(3)),
+                });
+                continue;
+            }
+            let whirlpool = match (
+                optional(celsius(&timing["whirlpool"], "temperature"))?,
+                optional(minutes(&timing["whirlpool"], "duration"))?,
+            ) {
+                (Some(temperature), Some(duration)) => Some((temperature, duration)),
+                _ => None,
+            };
+            hops.push(HopsProportion {
+                hops: hops_variety,
+                proportion,
+                timing: optional(minutes(timing, "duration"))?.unwrap_or(Minutes(0)),
+                first_wort: timing["use"] == "add_to_mash",
+                whirlpool,
+                age: None,
+                measured_alpha_acid: crop_alpha_acid(hops_variety, alpha),
+            });
+        }
+
+        let culture = array(ingredients, "culture_additions")
+            .first()
+            .copied()
+            .ok_or(BeerJsonError::Missing("culture_additions"))?;
+        let yeast_names: Vec<String> = ["product_id", "name"]
+            .iter()
+            .filter_map(|field| string(culture, field).ok())
+            .collect();
+        let yeast = yeast_names
+            .iter()
+            .find_map(|n| fuzzy_match(n, |y: Yeast| vec![y.desc().to_owned(), format!("{y:?}")]));
+        if yeast.is_none() {
+            unmatched.push(yeast_names.last().cloned().unwrap_or_default());
+        }
+
+        let mut mash_rests: Vec<MashRest> = Vec::new();
+        let mut decoction = false;
+        for step in array(&recipe["mash"], "mash_steps") {
+            decoction |= step["type"] == "decoction";
+            mash_rests.push(MashRest {
+                target_temperature: celsius(step, "step_temperature")?,
+                duration: minutes(step, "step_time")?,
+            });
+        }
+
+        // Grains need at least one rest to be mashed at
+        if mash_rests.is_empty() && malts.iter().any(|m| m.proportion > 0.0) {
+            return Err(BeerJsonError::Missing("mash_steps"));
+        }
+
+        let (Some(style), Some(yeast), true) = (style, yeast, unmatched.is_empty()) else {
+            return Err(BeerJsonError::Unmatched(unmatched));
+        };
+
+        let efficiency = optional(percent(&recipe["efficiency"], "brewhouse"))?
+            .map_or(EFFICIENCY, |e| e / 100.0);
+        let boil_length = optional(minutes(&recipe["boil"], "boil_time"))?.unwrap_or(Minutes(60));
+        let ferment_temperature =
+            match array(&recipe["fermentation"], "fermentation_steps").first() {
+                Some(step) => optional(celsius(step, "start_temperature"))?,
+                None => None,
+            }
+            .unwrap_or(yeast.temp());
+
+        let mut out = Recipe {
+            name,
+            style,
+            sulfate_chloride_target: 1.0,
+            malts,
+            mash_rests,
+            decoction,
+            mash_method: MashMethod::Infusion,
+            mash_thickness: 2.6,
+            mash_ph_target: Ph(5.4),
+            ph_method: PhMethod::None,
+            sugars,
+            extracts,
+            original_gravity: SpecificGravity(1.0),
+            ibu: Ibu(0.0),
+            ibu_model: match recipe["ibu_estimate"]["method"].as_str() {
+                Some("Rager") => IbuModel::Rager,
+                Some("Garetz") => IbuModel::Garetz,
+                _ => IbuModel::Tinseth,
+            },
+            abv_model: AbvModel::default(),
+            hops,
+            dry_hops,
+            boil_length,
+            fining_desired: false,
+            fining_agent: FiningAgent::Whirlfloc,
+            yeast,
+            max_partial_boil_dilution: 1.0,
+            ferment_temperature,
+            target_abv: None,
+            max_post_ferment_dilution: 1.0,
+            custom_steps: None,
+        };
+        out.original_gravity = match optional(gravity(recipe, "original_gravity"))? {
+            Some(og) => og,
+            None => SpecificGravity(1.0 + out.extract_points(efficiency) / batch_size.0 / 1000.0),
+        };
+        out.ibu = Ibu(out.tinseth_per_gram(batch_size));
+
+        Ok(out)
+    }
+}
+
+fn string(v: &Value, field: &'static str) -> Result<String, BeerJsonError> {
+    v[field]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or(BeerJsonError::Missing(field))
+}
+
+/// An optional field: None if it is missing, but still an error if it is
+/// in a unit we do not convert from
+fn optional<T>(result: Result<T, BeerJsonError>) -> Result<Option<T>, BeerJsonError> {
+    match result {
+        Ok(t) => Ok(Some(t)),
+        Err(BeerJsonError::Missing(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The value of a quantity, converted to our unit by `convert`, which
+/// gives None for units it does not know. A missing unit is ours.
+#[allow(clippy::cast_possible_truncation)]
+fn quantity(
+    v: &Value,
+    field: &'static str,
+    convert: impl Fn(&str, f32) -> Option<f32>,
+) -> Result<f32, BeerJsonError> {
+    let amount = v[field]["value"]
+        .as_f64()
+        .ok_or(BeerJsonError::Missing(field))? as f32;
+    match v[field]["unit"].as_str() {
+        None => Ok(amount),
+        Some(unit) => convert(unit, amount).ok_or_else(|| BeerJsonError::Unit {
+            field,
+            unit: unit.to_owned(),
+        }),
+    }
+}
+
+fn kilograms(v: &Value, field: &'static str) -> Result<Kilograms, BeerJsonError> {
+    quantity(v, field, |unit, amount| match unit {
+        "kg" => Some(amount),
+        "g" => Some(Kilograms::from(Grams(amount)).0),
+        "mg" => Some(Kilograms::from(Milligrams(amount)).0),
+        "lb" => Some(Kilograms::from(Pounds(amount)).0),
+        "oz" => Some(Kilograms::from(Ounces(amount)).0),
+        _ => None,
+    })
+    .map(Kilograms)
+}
+
+fn grams(v: &Value, field: &'static str) -> Result<Grams, BeerJsonError> {
+    kilograms(v, field).map(Grams::from)
+}
+
+fn liters(v: &Value, field: &'static str) -> Result<Liters, BeerJsonError> {
+    quantity(v, field, |unit, amount| match unit {
+        "l" => Some(amount),
+        "ml" => Some(Liters::from(Milliliters(amount)).0),
+        "gal" => Some(Liters::from(Gallons(amount)).0),
+        "qt" => Some(Liters::from(Quarts(amount)).0),
+        "floz" => Some(Liters::from(FluidOunces(amount)).0),
+        _ => None,
+    })
+    .map(Liters)
+}
+
+fn celsius(v: &Value, field: &'static str) -> Result<Celsius, BeerJsonError> {
+    quantity(v, field, |unit, amount| match unit {
+        "C" => Some(amount),
+        "F" => Some(Celsius::from(Fahrenheit(amount)).0),
+        _ => None,
+    })
+    .map(Celsius)
+}
+
+fn percent(v: &Value, field: &'static str) -> Result<f32, BeerJsonError> {
+    quantity(v, field, |unit, amount| (unit == "%").then_some(amount))
+}
+
+fn gravity(v: &Value, field: &'static str) -> Result<SpecificGravity, BeerJsonError> {
+    quantity(v, field, |unit, amount| match unit {
+        "sg" => Some(amount),
+        "plato" => Some(SpecificGravity::from(Plato(amount)).0),
+        "brix" => Some(SpecificGravity::from(Brix(amount)).0),
+        _ => None,
+    })
+    .map(SpecificGravity)
+}
+
+/// A time in minutes, from one in seconds, minutes, hours, days or weeks
+fn time_in_minutes(unit: &str, amount: f32) -> Option<f32> {
+    match unit {
+        "sec" => Some(amount / 60.0),
+        "min" => Some(amount),
+        "hr" => Some(amount * 60.0),
+        "day" => Some(amount * 24.0 * 60.0),
+        "week" => Some(amount * 7.0 * 24.0 * 60.0),
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn minutes(v: &Value, field: &'static str) -> Result<Minutes, BeerJsonError> {
+    quantity(v, field, time_in_minutes).map(|m| Minutes(m.round() as usize))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn days(v: &Value, field: &'static str) -> Result<Days, BeerJsonError> {
+    quantity(v, field, |unit, amount| {
+        time_in_minutes(unit, amount).map(|m| m / (24.0 * 60.0))
+    })
+    .map(|d| Days(d.round() as usize))
+}
+
+fn array<'a>(v: &'a Value, field: &str) -> Vec<&'a Value> {
+    v[field]
+        .as_array()
+        .map(|a| a.iter().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_recipe;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_beerjson_units() {
+        let recipe = test_recipe();
+        let json = recipe.to_beerjson(Liters(20.0));
+        let doc: Value = serde_json::from_str(&json).unwrap();
+        let r = &doc["beerjson"]["recipes"][0];
+
+        assert_eq!(r["batch_size"], json!({"unit": "l", "value": 20.0}));
+        assert_eq!(r["original_gravity"]["unit"], "sg");
+        assert_eq!(r["style"]["category_number"], 6);
+        assert_eq!(r["style"]["style_letter"], "A");
+        assert_eq!(r["style"]["style_guide"], "BJCP 2021");
+        let fermentable = &r["ingredients"]["fermentable_additions"][0];
+        assert_eq!(fermentable["amount"]["unit"], "kg");
+        assert_eq!(fermentable["color"]["unit"], "Lovi");
+        let hop = &r["ingredients"]["hop_additions"][0];
+        assert_eq!(hop["amount"]["unit"], "g");
+        assert_eq!(hop["alpha_acid"]["unit"], "%");
+        assert_eq!(hop["timing"]["duration"]["unit"], "min");
+        assert_eq!(r["ingredients"]["culture_additions"][0]["form"], "liquid");
+        assert_eq!(r["mash"]["mash_steps"][0]["step_temperature"]["unit"], "C");
+
+        let imported = Recipe::from_beerjson(&json).unwrap();
+        assert_eq!(imported.style, recipe.style);
+        assert_eq!(imported.yeast, recipe.yeast);
+        assert_eq!(imported.malts.len(), recipe.malts.len());
+        assert!(approx_eq!(
+            f32,
+            imported.ibu.0,
+            recipe.ibu.0,
+            epsilon = 0.01
+        ));
+
+        // A style outside the BJCP guidelines claims no BJCP category
+        let mut recipe = test_recipe();
+        recipe.style = Style::LeichtesWeizen;
+        let doc: Value = serde_json::from_str(&recipe.to_beerjson(Liters(20.0))).unwrap();
+        let style = &doc["beerjson"]["recipes"][0]["style"];
+        assert_eq!(style["category"], "Leichtes Weizen");
+        assert!(style.get("style_guide").is_none());
+        assert!(style.get("category_number").is_none());
+    }
+
+    #[test]
+    fn test_beerjson_hop_uses() {
+        let mut recipe = test_recipe();
+        recipe.ibu_model = IbuModel::Rager;
+        recipe.hops.push(HopsProportion {
+            hops: Hops::Saaz,
+            proportion: 5.0,
+            timing: Minutes(60),
+            first_wort: true,
+            whirlpool: None,
+            age: None,
+            measured_alpha_acid: None,
+        });
+        recipe.hops.push(HopsProportion {
+            hops: Hops::Saaz,
+            proportion: 5.0,
+            timing: Minutes(0),
+            first_wort: false,
+            whirlpool: Some((Celsius(80.0), Minutes(20))),
+            age: None,
+            measured_alpha_acid: None,
+        });
+        recipe.dry_hops.push(DryHopAddition {
+            hops: Hops::Saaz,
+            grams_per_liter: 2.0,
+            day: Days(7),
+            duration: Days(3),
+        });
+
+        let json = recipe.to_beerjson(Liters(20.0));
+        let doc: Value = serde_json::from_str(&json).unwrap();
+        let r = &doc["beerjson"]["recipes"][0];
+        assert_eq!(r["ibu_estimate"]["method"], "Rager");
+        let uses: Vec<&Value> = r["ingredients"]["hop_additions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| &h["timing"]["use"])
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                "add_to_boil",
+                "add_to_mash",
+                "add_to_boil",
+                "add_to_fermentation"
+            ]
+        );
+
+        let imported = Recipe::from_beerjson(&json).unwrap();
+        assert_eq!(imported.ibu_model, IbuModel::Rager);
+        assert_eq!(imported.hops.len(), 3);
+        assert!(!imported.hops[0].first_wort);
+        assert!(imported.hops[1].first_wort);
+        assert_eq!(imported.hops[1].timing, Minutes(60));
+        assert_eq!(
+            imported.hops[2].whirlpool,
+            Some((Celsius(80.0), Minutes(20)))
+        );
+        assert_eq!(imported.dry_hops.len(), 1);
+        assert_eq!(imported.dry_hops[0].hops, Hops::Saaz);
+        assert_eq!(imported.dry_hops[0].day, Days(7));
+        assert_eq!(imported.dry_hops[0].duration, Days(3));
+        assert!(approx_eq!(
+            f32,
+            imported.dry_hops[0].grams_per_liter,
+            2.0,
+            epsilon = 0.001
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_beerjson_imperial_units() {
+        let recipe = test_recipe();
+        let json = recipe.to_beerjson(Liters(20.0));
+        let metric = Recipe::from_beerjson(&json).unwrap();
+
+        let mut doc: Value = serde_json::from_str(&json).unwrap();
+        let r = &mut doc["beerjson"]["recipes"][0];
+        assert_eq!(r["type"], "all grain");
+        r["batch_size"] = unit("gal", Gallons::from(Liters(20.0)).0);
+        for f in r["ingredients"]["fermentable_additions"]
+            .as_array_mut()
+            .unwrap()
+        {
+            let kg = f["amount"]["value"].as_f64().unwrap() as f32;
+            f["amount"] = unit("lb", Pounds::from(Kilograms(kg)).0);
+        }
+        for h in r["ingredients"]["hop_additions"].as_array_mut().unwrap() {
+            let g = h["amount"]["value"].as_f64().unwrap() as f32;
+            h["amount"] = unit("oz", Ounces::from(Grams(g)).0);
+        }
+        for step in r["mash"]["mash_steps"].as_array_mut().unwrap() {
+            let c = step["step_temperature"]["value"].as_f64().unwrap() as f32;
+            step["step_temperature"] = unit("F", Fahrenheit::from(Celsius(c)).0);
+        }
+        let c = recipe.ferment_temperature.0;
+        r["fermentation"]["fermentation_steps"][0]["start_temperature"] =
+            unit("F", Fahrenheit::from(Celsius(c)).0);
+        r["boil"]["boil_time"] = unit("hr", recipe.boil_length.0 as f32 / 60.0);
+        let imperial = serde_json::to_string(&doc).unwrap();
+
+        let imported = Recipe::from_beerjson(&imperial).unwrap();
+        assert_eq!(imported.boil_length, metric.boil_length);
+        assert!(approx_eq!(
+            f32,
+            imported.ferment_temperature.0,
+            metric.ferment_temperature.0,
+            epsilon = 0.01
+        ));
+        for (a, b) in imported.mash_rests.iter().zip(metric.mash_rests.iter()) {
+            assert!(approx_eq!(
+                f32,
+                a.target_temperature.0,
+                b.target_temperature.0,
+                epsilon = 0.01
+            ));
+        }
+        for (a, b) in imported.malts.iter().zip(metric.malts.iter()) {
+            assert!(approx_eq!(f32, a.proportion, b.proportion, epsilon = 0.001));
+        }
+        assert!(approx_eq!(
+            f32,
+            imported.hops[0].proportion,
+            metric.hops[0].proportion,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f32,
+            imported.ibu.0,
+            metric.ibu.0,
+            epsilon = 0.01
+        ));
+
+        // Units we do not know are an error, not a guess
+        let stone = imperial.replacen("\"lb\"", "\"stone\"", 1);
+        assert!(matches!(
+            Recipe::from_beerjson(&stone),
+            Err(BeerJsonError::Unit {
+                field: "amount",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_beerjson_missing_mash_steps() {
+        let json = test_recipe().to_beerjson(Liters(20.0));
+        let mut doc: Value = serde_json::from_str(&json).unwrap();
+        doc["beerjson"]["recipes"][0]["mash"]["mash_steps"] = json!([]);
+        assert!(matches!(
+            Recipe::from_beerjson(&doc.to_string()),
+            Err(BeerJsonError::Missing("mash_steps"))
+        ));
+
+        // Extract recipes do not need a mash
+        let mut extract = test_recipe();
+        extract.malts = vec![];
+        extract.extracts = vec![ExtractProportion {
+            extract: MaltExtract::LightDme,
+            proportion: 100.0,
+        }];
+        extract.mash_rests = vec![];
+        let json = extract.to_beerjson(Liters(20.0));
+        let doc: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(doc["beerjson"]["recipes"][0]["type"], "extract");
+        assert!(Recipe::from_beerjson(&json).unwrap().mash_rests.is_empty());
+    }
+}
@@ -0,0 +1,776 @@
+use crate::mash::MashRest;
+use crate::prelude::*;
+use std::fmt;
+use std::fmt::Write;
+use strum::IntoEnumIterator;
+
+/// Mash efficiency assumed when converting proportions to and from weights
+pub(crate) const EFFICIENCY: f32 = 0.75;
+
+/// Temperature assumed for a hop stand, which BeerXML does not carry
+pub(crate) const HOP_STAND_TEMPERATURE: Celsius = Celsius(85.0);
+
+/// An error importing a BeerXML recipe
+#[derive(Debug, Clone, PartialEq)]
+pub enum BeerXmlError {
+    /// The document is not well-formed XML
+    Xml(String),
+
+    /// A required element is missing
+    Missing(&'static str),
+
+    /// An element could not be parsed as a number
+    Invalid {
+        /// The element
+        element: &'static str,
+
+        /// Its text
+        value: String,
+    },
+
+    /// Ingredients or style that do not match anything we know about
+    Unmatched(Vec<String>),
+}
+
+impl fmt::Display for BeerXmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Xml(e) => write!(f, "Invalid XML: {e}"),
+            Self::Missing(element) => write!(f, "Missing <{element}>"),
+            Self::Invalid { element, value } => write!(f, "Invalid <{element}>: {value}"),
+            Self::Unmatched(names) => write!(f, "Unmatched: {}", names.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for BeerXmlError {}
+
+impl Recipe {
+    /// Export as a BeerXML 1.0 document.
+    ///
+    /// Proportions are converted to weights for the given batch size,
+    /// assuming 75% mash efficiency.
+    #[must_use]
+    #[allow(clippy::too_many_lines)]
+    pub fn to_beerxml(&self, batch_size: Liters) -> String {
+        let (malt_kg, hops_g) = self.weight_scales(batch_size);
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<RECIPES>\n<RECIPE>\n");
+        element(&mut xml, "NAME", &self.name);
+        element(&mut xml, "VERSION", 1);
+        element(&mut xml, "TYPE", self.beerxml_type());
+        element(&mut xml, "BREWER", "beermaker");
+        element(&mut xml, "BATCH_SIZE", batch_size.0);
+        element(&mut xml, "BOIL_SIZE", batch_size.0);
+        element(&mut xml, "BOIL_TIME", self.boil_length.0);
+        element(&mut xml, "EFFICIENCY", EFFICIENCY * 100.0);
+        element(&mut xml, "OG", self.original_gravity.0);
+        element(&mut xml, "IBU", self.ibu.0);
+        element(&mut xml, "PRIMARY_TEMP", self.ferment_temperature.0);
+
+        xml.push_str("<STYLE>\n");
+        element(&mut xml, "NAME", self.style);
+        element(&mut xml, "VERSION", 1);
+        element(&mut xml, "STYLE_GUIDE", "BJCP");
+        let style_type = if self.style.lager_style() {
+            "Lager"
+        } else {
+            "Ale"
+        };
+        element(&mut xml, "TYPE", style_type);
+        if let Some(og) = self.style.original_gravity_range() {
+            element(&mut xml, "OG_MIN", og.start.0);
+            element(&mut xml, "OG_MAX", og.end.0);
+        }
+        if let Some(fg) = self.style.final_gravity_range() {
+            element(&mut xml, "FG_MIN", fg.start.0);
+            element(&mut xml, "FG_MAX", fg.end.0);
+        }
+        if let Some(ibu) = self.style.bitterness_range() {
+            element(&mut xml, "IBU_MIN", ibu.start.0);
+            element(&mut xml, "IBU_MAX", ibu.end.0);
+        }
+        if let Some(color) = self.style.color_range() {
+            element(&mut xml, "COLOR_MIN", color.start.0);
+            element(&mut xml, "COLOR_MAX", color.end.0);
+        }
+        xml.push_str("</STYLE>\n");
+
+        xml.push_str("<FERMENTABLES>\n");
+        for mp in &self.malts {
+            xml.push_str("<FERMENTABLE>\n");
+            element(&mut xml, "NAME", trim_brackets(&mp.malt.to_string()));
+            element(&mut xml, "VERSION", 1);
+            element(&mut xml, "TYPE", "Grain");
+            element(&mut xml, "AMOUNT", mp.proportion * malt_kg);
+            element(&mut xml, "YIELD", mp.malt.extract_percent());
+            let lovibond: Lovabond = mp.malt.ebc().into();
+            element(&mut xml, "COLOR", lovibond.0);
+            xml.push_str("</FERMENTABLE>\n");
+        }
+        for sp in &self.sugars {
+            xml.push_str("<FERMENTABLE>\n");
+            element(&mut xml, "NAME", trim_brackets(&sp.sugar.to_string()));
+            element(&mut xml, "VERSION", 1);
+            element(&mut xml, "TYPE", "Sugar");
+            element(&mut xml, "AMOUNT", sp.proportion * malt_kg);
+            element(&mut xml, "YIELD", sp.sugar.extract_percent());
+            let lovibond: Lovabond = sp.sugar.ebc().into();
+            element(&mut xml, "COLOR", lovibond.0);
+            xml.push_str("</FERMENTABLE>\n");
+        }
+        for ep in &self.extracts {
+            xml.push_str("<FERMENTABLE>\n");
+            element(&mut xml, "NAME", trim_brackets(&ep.extract.to_string()));
+            element(&mut xml, "VERSION", 1);
+            let extract_type = if ep.extract.is_dry() {
+                "Dry Extract"
+            } else {
+                "Extract"
+            };
+            element(&mut xml, "TYPE", extract_type);
+            element(&mut xml, "AMOUNT", ep.proportion * malt_kg);
+            element(&mut xml, "YIELD", ep.extract.extract_percent());
+            let lovibond: Lovabond = ep.extract.ebc().into();
+            element(&mut xml, "COLOR", lovibond.0);
+            xml.push_str("</FERMENTABLE>\n");
+        }
+        xml.push_str("</FERMENTABLES>\n");
+
+        xml.push_str("<HOPS>\n");
+        for hp in &self.hops {
+            xml.push_str("<HOP>\n");
+            element(&mut xml, "NAME", trim_brackets(&hp.hops.to_string()));
+            element(&mut xml, "VERSION", 1);
+            element(&mut xml, "ALPHA", hp.alpha_acid() * 100.0);
+            element(&mut xml, "AMOUNT", hp.proportion * hops_g / 1000.0);
+            // TIME is the length of the hop stand for aroma hops
+            let (hop_use, time) = match hp.whirlpool {
+                Some((_, minutes)) => ("Aroma", minutes),
+                None if hp.first_wort => ("First Wort", hp.timing),
+                None => ("Boil", hp.timing),
+            };
+            element(&mut xml, "USE", hop_use);
+            element(&mut xml, "TIME", time.0);
+            xml.push_str("</HOP>\n");
+        }
+        for addition in &self.dry_hops {
+            xml.push_str("<HOP>\n");
+            element(&mut xml, "NAME", trim_brackets(&addition.hops.to_string()));
+            element(&mut xml, "VERSION", 1);
+            element(&mut xml, "ALPHA", addition.hops.alpha_acid() * 100.0);
+            element(
+                &mut xml,
+                "AMOUNT",
+                addition.grams_per_liter * batch_size.0 / 1000.0,
+            );
+            element(&mut xml, "USE", "Dry Hop");
+            element(&mut xml, "TIME", addition.duration.0 * 24 * 60);
+            xml.push_str("</HOP>\n");
+        }
+        xml.push_str("</HOPS>\n");
+
+        xml.push_str("<YEASTS>\n<YEAST>\n");
+        element(&mut xml, "NAME", self.yeast.desc());
+        element(&mut xml, "VERSION", 1);
+        let yeast_type = if self.yeast.is_lager() {
+            "Lager"
+        } else {
+            "Ale"
+        };
+        element(&mut xml, "TYPE", yeast_type);
+        let form = if self.yeast.is_dry() { "Dry" } else { "Liquid" };
+        element(&mut xml, "FORM", form);
+        element(&mut xml, "LABORATORY", self.yeast.provider());
+        element(&mut xml, "PRODUCT_ID", format!("{:?}", self.yeast));
+        element(&mut xml, "ATTENUATION", self.yeast.attenuation() * 100.0);
+        xml.push_str("</YEAST>\n</YEASTS>\n");
+
+        xml.push_str("<MASH>\n");
+        element(&mut xml, "NAME", "Mash");
+        element(&mut xml, "VERSION", 1);
+        element(&mut xml, "GRAIN_TEMP", 20.0);
+        element(&mut xml, "PH", self.mash_ph_target.0);
+        xml.push_str("<MASH_STEPS>\n");
+        let step_type = if self.decoction {
+            "Decoction"
+        } else {
+            "Infusion"
+        };
+        for (i, rest) in self.mash_rests.iter().enumerate() {
+            xml.push_str("<MASH_STEP>\n");
+            element(&mut xml, "NAME", format!("Rest {}", i + 1));
+            element(&mut xml, "VERSION", 1);
+            element(&mut xml, "TYPE", step_type);
+            element(&mut xml, "STEP_TEMP", rest.target_temperature.0);
+            element(&mut xml, "STEP_TIME", rest.duration.0);
+            xml.push_str("</MASH_STEP>\n");
+        }
+        xml.push_str("</MASH_STEPS>\n</MASH>\n");
+
+        xml.push_str("</RECIPE>\n</RECIPES>\n");
+        xml
+    }
+
+    /// Import the first recipe in a BeerXML 1.0 document.
+    ///
+    /// Ingredient names are matched loosely against our own. Weights become
+    /// proportions. Fields that BeerXML does not carry get defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed, if a required element is
+    /// missing, or if any ingredient or the style cannot be matched.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::too_many_lines)]
+    pub fn from_beerxml(xml: &str) -> Result<Recipe, BeerXmlError> {
+        let doc = roxmltree::Document::parse(xml).map_err(|e| BeerXmlError::Xml(e.to_string()))?;
+        let recipe = doc
+            .descendants()
+            .find(|n| n.has_tag_name("RECIPE"))
+            .ok_or(BeerXmlError::Missing("RECIPE"))?;
+
+        let mut unmatched: Vec<String> = Vec::new();
+
+        let name = text(recipe, "NAME").ok_or(BeerXmlError::Missing("NAME"))?;
+        let batch_size = Liters(required(recipe, "BATCH_SIZE")?);
+
+        let style_name = child(recipe, "STYLE")
+            .and_then(|s| text(s, "NAME"))
+            .ok_or(BeerXmlError::Missing("STYLE"))?;
+        let style = fuzzy_match(&style_name, |s: Style| {
+            vec![s.to_string(), format!("{s:?}")]
+        });
+        if style.is_none() {
+            unmatched.push(style_name);
+        }
+
+        let mut malts: Vec<MaltProportion> = Vec::new();
+        let mut sugars: Vec<SugarProportion> = Vec::new();
+        let mut extracts: Vec<ExtractProportion> = Vec::new();
+        for f in children(recipe, "FERMENTABLES", "FERMENTABLE") {
+            let name = text(f, "NAME").ok_or(BeerXmlError::Missing("NAME"))?;
+            let proportion = required(f, "AMOUNT")?;
+            let is_sugar = text(f, "TYPE").is_some_and(|t| t.eq_ignore_ascii_case("sugar"));
+            let is_extract = text(f, "TYPE").is_some_and(|t| {
+                t.eq_ignore_ascii_case("extract") || t.eq_ignore_ascii_case("dry extract")
+            });
+            if is_extract {
+                match fuzzy_match(&name, |e: MaltExtract| {
+                    vec![e.to_string(), format!("{e:?}")]
+                }) {
+                    Some(extract) => extracts.push(ExtractProportion {
+                        extract,
+                        proportion,
+                    }),
+                    None => unmatched.push(name),
+                }
+            } else if is_sugar {
+                match fuzzy_match(&name, |s: Sugar| vec![s.to_string(), format!("{s:?}")]) {
+                    Some(sugar) => sugars.push(SugarProportion { sugar, proportion }),
+                    None => unmatched.push(name),
+                }
+            } else {
+                match fuzzy_match(&name, |m: Malt| vec![m.to_string(), format!("{m:?}")]) {
+                    Some(malt) => malts.push(MaltProportion { malt, proportion }),
+                    None => unmatched.push(name),
+                }
+            }
+        }
+
+        let boil_length = Minutes(optional(Some(recipe), "BOIL_TIME")?.unwrap_or(60.0) as usize);
+
+        let mut hops: Vec<HopsProportion> = Vec::new();
+        let mut dry_hops: Vec<DryHopAddition> = Vec::new();
+        for h in children(recipe, "HOPS", "HOP") {
+            let name = text(h, "NAME").ok_or(BeerXmlError::Missing("NAME"))?;
+            let amount = required(h, "AMOUNT")?;
+            let time = Minutes(required(h, "TIME")?.round() as usize);
+            let hop_use = text(h, "USE").unwrap_or_default().to_lowercase();
+            let alpha = optional(Some(h), "ALPHA")?;
+            let Some(hops_variety) =
+                fuzzy_match(&name, |h: Hops| vec![h.to_string(), format!("{h:?}")])
+            else {
+                unmatched.push(name);
+                continue;
+            };
+
+            // TIME is how long the hops are in the fermenter for dry hops,
+            // and how long they steep after the boil for aroma hops
+            if hop_use == "dry hop" {
+                let days = (time.0 + 12 * 60) / (24 * 60);
+                dry_hops.push(DryHopAddition {
+                    hops: hops_variety,
+                    grams_per_liter: amount * 1000.0 / batch_size.0,
+                    day: Days(0),
+                    duration: Days(days.max(1)),
+                });
+                continue;
+            }
+            let (timing, whirlpool) = if hop_use == "aroma" {
+                let stand = (time > Minutes(0)).then_some((HOP_STAND_TEMPERATURE, time));
+                (Minutes(0), stand)
+            } else {
+                (time.min(boil_length), None)
+            };
+            hops.push(HopsProportion {
+                hops: hops_variety,
+                proportion: amount * 1000.0,
+                timing,
+                first_wort: hop_use == "first wort",
+                whirlpool,
+                age: None,
+                measured_alpha_acid: crop_alpha_acid(hops_variety, alpha),
+            });
+        }
+
+        let yeast_node = children(recipe, "YEASTS", "YEAST")
+            .into_iter()
+            .next()
+            .ok_or(BeerXmlError::Missing("YEAST"))?;
+        let yeast_names: Vec<String> = ["PRODUCT_ID", "NAME"]
+            .iter()
+            .filter_map(|tag| text(yeast_node, tag))
+            .collect();
+        let yeast = yeast_names
+            .iter()
+            .find_map(|n| fuzzy_match(n, |y: Yeast| vec![y.desc().to_owned(), format!("{y:?}")]));
+        if yeast.is_none() {
+            unmatched.push(yeast_names.last().cloned().unwrap_or_default());
+        }
+
+        let mash = child(recipe, "MASH");
+        let steps = mash
+            .map(|m| children(m, "MASH_STEPS", "MASH_STEP"))
+            .unwrap_or_default();
+        let mut mash_rests: Vec<MashRest> = Vec::new();
+        let mut decoction = false;
+        for step in steps {
+            decoction |= text(step, "TYPE").is_some_and(|t| t.eq_ignore_ascii_case("decoction"));
+            mash_rests.push(MashRest {
+                target_temperature: Celsius(required(step, "STEP_TEMP")?),
+                duration: Minutes(required(step, "STEP_TIME")?.round() as usize),
+            });
+        }
+
+        // Grains need at least one rest to be mashed at
+        if mash_rests.is_empty() && malts.iter().any(|m| m.proportion > 0.0) {
+            return Err(BeerXmlError::Missing(if mash.is_some() {
+                "MASH_STEP"
+            } else {
+                "MASH"
+            }));
+        }
+
+        let (Some(style), Some(yeast), true) = (style, yeast, unmatched.is_empty()) else {
+            return Err(BeerXmlError::Unmatched(unmatched));
+        };
+
+        let mut recipe_out = Recipe {
+            name,
+            style,
+            sulfate_chloride_target: 1.0,
+            malts,
+            mash_rests,
+            decoction,
+            mash_method: MashMethod::Infusion,
+            mash_thickness: 2.6,
+            mash_ph_target: Ph(optional(mash, "PH")?.unwrap_or(5.4)),
+            ph_method: PhMethod::None,
+            sugars,
+            extracts,
+            original_gravity: SpecificGravity(1.0),
+            ibu: Ibu(0.0),
+            ibu_model: IbuModel::Tinseth,
+            abv_model: AbvModel::default(),
+            hops,
+            dry_hops,
+            boil_length,
+            fining_desired: false,
+            fining_agent: FiningAgent::Whirlfloc,
+            yeast,
+            max_partial_boil_dilution: 1.0,
+            ferment_temperature: yeast.temp(),
+            target_abv: None,
+            max_post_ferment_dilution: 1.0,
+            custom_steps: None,
+        };
+
+        if let Some(temp) = optional(Some(recipe), "PRIMARY_TEMP")? {
+            recipe_out.ferment_temperature = Celsius(temp);
+        }
+
+        // BeerXML does not say when to dry hop, so dry hop for the last
+        // days of fermentation
+        let fermentation_time = recipe_out.fermentation_time();
+        for addition in &mut recipe_out.dry_hops {
+            addition.day = Days(fermentation_time.0.saturating_sub(addition.duration.0));
+        }
+
+        // Recompute OG and IBU from the weights if they are not given
+        let efficiency = optional(Some(recipe), "EFFICIENCY")?.map_or(EFFICIENCY, |e| e / 100.0);
+        recipe_out.original_gravity = match optional(Some(recipe), "OG")? {
+            Some(og) => SpecificGravity(og),
+            None => {
+                SpecificGravity(1.0 + recipe_out.extract_points(efficiency) / batch_size.0 / 1000.0)
+            }
+        };
+        recipe_out.ibu = match optional(Some(recipe), "IBU")? {
+            Some(ibu) => Ibu(ibu),
+            None => Ibu(recipe_out.tinseth_per_gram(batch_size)),
+        };
+
+        Ok(recipe_out)
+    }
+
+    /// Extract points (liters × gravity points) per unit of proportion
+    pub(crate) fn extract_points(&self, efficiency: f32) -> f32 {
+        self.malts
+            .iter()
+            .map(|mp| mp.proportion * mp.malt.pkl() * efficiency)
+            .sum::<f32>()
+            + self
+                .sugars
+                .iter()
+                .map(|sp| sp.proportion * sp.sugar.pkl())
+                .sum::<f32>()
+            + self
+                .extracts
+                .iter()
+                .map(|ep| ep.proportion * ep.extract.pkl())
+                .sum::<f32>()
+    }
+
+    /// IBUs per unit of hops proportion (in grams), by Tinseth
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn tinseth_per_gram(&self, batch_size: Liters) -> f32 {
+        let bigness_factor = 1.65 * (0.000_125_f32).powf(self.original_gravity.0 - 1.0);
+        self.hops
+            .iter()
+            .map(|hp| {
+                let boil_time_factor = (1.0 - (-0.04 * hp.timing.0 as f32).exp()) / 4.15;
+                bigness_factor * boil_time_factor * hp.alpha_acid() * hp.proportion * 1000.0
+                    / batch_size.0
+            })
+            .sum()
+    }
+
+    /// The BeerXML recipe type: "All Grain", "Partial Mash" or "Extract"
+    pub(crate) fn beerxml_type(&self) -> &'static str {
+        let mashed = self.malts.iter().any(|m| m.proportion > 0.0);
+        match (mashed, self.extracts.is_empty()) {
+            (true, true) => "All Grain",
+            (true, false) => "Partial Mash",
+            (false, _) => "Extract",
+        }
+    }
+
+    /// Kilograms per unit of fermentable proportion, and grams per unit of
+    /// hops proportion, to hit the OG and IBU targets in the batch
+    pub(crate) fn weight_scales(&self, batch_size: Liters) -> (f32, f32) {
+        let points = (self.original_gravity.0 - 1.0) * 1000.0 * batch_size.0;
+        let extract = self.extract_points(EFFICIENCY);
+        let malt_kg = if extract > 0.0 { points / extract } else { 0.0 };
+        let ibu = self.tinseth_per_gram(batch_size);
+        let hops_g = if ibu > 0.0 { self.ibu.0 / ibu } else { 0.0 };
+        (malt_kg, hops_g)
+    }
+}
+
+fn element<T: fmt::Display>(xml: &mut String, tag: &str, value: T) {
+    let value = value.to_string();
+    let _ = writeln!(xml, "<{tag}>{}</{tag}>", escape(&value));
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// An imported alpha acid (in percent), kept as the measured alpha acid
+/// only if it differs from the variety's
+pub(crate) fn crop_alpha_acid(hops: Hops, percent: Option<f32>) -> Option<f32> {
+    let fraction = percent? / 100.0;
+    if (fraction - hops.alpha_acid()).abs() < 0.0005 {
+        None
+    } else {
+        Some(fraction)
+    }
+}
+
+pub(crate) fn trim_brackets(s: &str) -> &str {
+    s.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn child<'a, 'i>(node: roxmltree::Node<'a, 'i>, tag: &str) -> Option<roxmltree::Node<'a, 'i>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn children<'a, 'i>(
+    node: roxmltree::Node<'a, 'i>,
+    list: &str,
+    tag: &str,
+) -> Vec<roxmltree::Node<'a, 'i>> {
+    child(node, list)
+        .map(|l| l.children().filter(|n| n.has_tag_name(tag)).collect())
+        .unwrap_or_default()
+}
+
+fn text(node: roxmltree::Node<'_, '_>, tag: &str) -> Option<String> {
+    child(node, tag)
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_owned())
+}
+
+fn optional(
+    node: Option<roxmltree::Node<'_, '_>>,
+    tag: &'static str,
+) -> Result<Option<f32>, BeerXmlError> {
+    match node.and_then(|n| text(n, tag)) {
+        None => Ok(None),
+        Some(value) => value
+            .parse::<f32>()
+            .map(Some)
+            .map_err(|_| BeerXmlError::Invalid {
+                element: tag,
+                value,
+            }),
+    }
+}
+
+fn required(node: roxmltree::Node<'_, '_>, tag: &'static str) -> Result<f32, BeerXmlError> {
+    optional(Some(node), tag)?.ok_or(BeerXmlError::Missing(tag))
+}
+
+/// Lowercase alphanumerics only
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Find the variant whose name matches best. An exact match (ignoring case
+/// and punctuation) wins; otherwise the longest name that contains, or is
+/// contained in, the given name.
+pub(crate) fn fuzzy_match<T: IntoEnumIterator + Copy>(
+    name: &str,
+    names: impl Fn(T) -> Vec<String>,
+) -> Option<T> {
+    let wanted = normalize(name);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(usize, T)> = None;
+    for variant in T::iter() {
+        for candidate in names(variant).iter().map(|n| normalize(n)) {
+            if candidate == wanted {
+                return Some(variant);
+            }
+            if (candidate.contains(&wanted) || wanted.contains(&candidate))
+                && best.is_none_or(|(len, _)| candidate.len() > len)
+            {
+                best = Some((candidate.len(), variant));
+            }
+        }
+    }
+    best.map(|(_, variant)| variant)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::{test_brewery, test_recipe};
+    use crate::{Process, UnitSystem, print_process};
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_beerxml_round_trip() {
+        let recipe = test_recipe();
+        let xml = recipe.to_beerxml(Liters(20.0));
+        let imported = Recipe::from_beerxml(&xml).unwrap();
+
+        assert_eq!(imported.name, recipe.name);
+        assert_eq!(imported.style, recipe.style);
+        assert_eq!(imported.yeast, recipe.yeast);
+        assert_eq!(imported.boil_length, recipe.boil_length);
+        assert!(approx_eq!(
+            f32,
+            imported.original_gravity.0,
+            recipe.original_gravity.0
+        ));
+        assert!(approx_eq!(f32, imported.ibu.0, recipe.ibu.0));
+
+        // Proportions survive up to a common scale
+        assert_eq!(imported.malts.len(), recipe.malts.len());
+        let scale = imported.malts[0].proportion / recipe.malts[0].proportion;
+        for (a, b) in imported.malts.iter().zip(recipe.malts.iter()) {
+            assert_eq!(a.malt, b.malt);
+            assert!(approx_eq!(
+                f32,
+                a.proportion,
+                b.proportion * scale,
+                epsilon = 0.001
+            ));
+        }
+        assert_eq!(imported.hops.len(), recipe.hops.len());
+        assert_eq!(imported.hops[0].hops, recipe.hops[0].hops);
+        assert_eq!(imported.hops[0].timing, recipe.hops[0].timing);
+
+        assert_eq!(imported.mash_rests.len(), recipe.mash_rests.len());
+        for (a, b) in imported.mash_rests.iter().zip(recipe.mash_rests.iter()) {
+            assert_eq!(a.target_temperature, b.target_temperature);
+            assert_eq!(a.duration, b.duration);
+        }
+    }
+
+    #[test]
+    fn test_beerxml_unmatched() {
+        let xml = test_recipe()
+            .to_beerxml(Liters(20.0))
+            .replace("Hallertau", "Zzyzx");
+        match Recipe::from_beerxml(&xml) {
+            Err(BeerXmlError::Unmatched(names)) => assert_eq!(names.len(), 1),
+            other => panic!("expected unmatched, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_beerxml_hop_uses() {
+        let hop = |hop_use: &str, time: usize| {
+            format!(
+                "<HOP><NAME>Saaz</NAME><VERSION>1</VERSION><ALPHA>3.5</ALPHA>\
+                 <AMOUNT>0.02</AMOUNT><USE>{hop_use}</USE><TIME>{time}</TIME></HOP>\n"
+            )
+        };
+        let rows = format!(
+            "{}{}{}</HOPS>",
+            hop("Dry Hop", 4320),
+            hop("Aroma", 20),
+            hop("Boil", 500)
+        );
+        let xml = test_recipe()
+            .to_beerxml(Liters(20.0))
+            .replace("</HOPS>", &rows);
+        let recipe = Recipe::from_beerxml(&xml).unwrap();
+
+        // The dry hop row is a dry hop addition, not a 3 day boil
+        assert_eq!(recipe.dry_hops.len(), 1);
+        assert_eq!(recipe.dry_hops[0].hops, Hops::Saaz);
+        assert_eq!(recipe.dry_hops[0].duration, Days(3));
+        assert!(approx_eq!(
+            f32,
+            recipe.dry_hops[0].grams_per_liter,
+            1.0,
+            epsilon = 0.001
+        ));
+
+        let aroma = &recipe.hops[recipe.hops.len() - 2];
+        assert_eq!(aroma.timing, Minutes(0));
+        assert_eq!(aroma.whirlpool, Some((HOP_STAND_TEMPERATURE, Minutes(20))));
+
+        let boil = recipe.hops.last().unwrap();
+        assert_eq!(boil.timing, recipe.boil_length);
+        assert!(recipe.hops.iter().all(|hp| hp.timing <= recipe.boil_length));
+
+        // And it prints
+        let process = Process::new(test_brewery(), recipe, Liters(20.0));
+        let _ = print_process(&process, None, None, UnitSystem::Metric);
+
+        // Dry hops and hop stands survive a round trip
+        let again = Recipe::from_beerxml(&process.recipe.to_beerxml(Liters(20.0))).unwrap();
+        assert_eq!(again.dry_hops.len(), 1);
+        assert_eq!(again.dry_hops[0].duration, Days(3));
+        let aroma = &again.hops[again.hops.len() - 2];
+        assert_eq!(aroma.whirlpool, Some((HOP_STAND_TEMPERATURE, Minutes(20))));
+    }
+
+    #[test]
+    fn test_beerxml_aged_hops() {
+        let fresh = test_recipe();
+        let mut aged = test_recipe();
+        aged.hops[0].age = Some((Days(365), HopStorage::RoomTempOpen));
+        let retained = aged.hops[0].alpha_acid() / aged.hops[0].fresh_alpha_acid();
+        assert!(retained < 0.5);
+
+        // Aged hops are weighed out heavier to reach the same IBU
+        let (_, fresh_g) = fresh.weight_scales(Liters(20.0));
+        let (_, aged_g) = aged.weight_scales(Liters(20.0));
+        assert!(approx_eq!(f32, fresh_g / aged_g, retained, epsilon = 0.001));
+
+        // and are exported at their aged alpha acid
+        let imported = Recipe::from_beerxml(&aged.to_beerxml(Liters(20.0))).unwrap();
+        assert!(approx_eq!(
+            f32,
+            imported.hops[0].alpha_acid(),
+            aged.hops[0].alpha_acid(),
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let malt = |name: &str| fuzzy_match(name, |m: Malt| vec![m.to_string(), format!("{m:?}")]);
+
+        // An exact match wins over a longer name containing it
+        assert_eq!(
+            malt("Weyermann Carafa Special II"),
+            Some(Malt::WeyermannCarafaSpecial2)
+        );
+        assert_eq!(
+            malt("weyermann carafa-special III"),
+            Some(Malt::WeyermannCarafaSpecial3)
+        );
+
+        // Otherwise the longest contained name wins
+        assert_eq!(
+            malt("Weyermann Munich Malt II (25 kg)"),
+            Some(Malt::WeyermannMunich2)
+        );
+
+        assert_eq!(malt("Unobtainium"), None);
+        assert_eq!(malt(" - "), None);
+    }
+
+    #[test]
+    fn test_beerxml_default_abv_model() {
+        let mut recipe = test_recipe();
+        recipe.abv_model = AbvModel::Simple;
+        let imported = Recipe::from_beerxml(&recipe.to_beerxml(Liters(20.0))).unwrap();
+        assert_eq!(imported.abv_model, AbvModel::default());
+    }
+
+    #[test]
+    fn test_beerxml_missing_mash() {
+        let xml = test_recipe().to_beerxml(Liters(20.0));
+        assert!(xml.contains("<TYPE>All Grain</TYPE>"));
+        let (before, rest) = xml.split_once("<MASH>").unwrap();
+        let (_, after) = rest.split_once("</MASH>\n").unwrap();
+        let no_mash = format!("{before}{after}");
+        assert!(matches!(
+            Recipe::from_beerxml(&no_mash),
+            Err(BeerXmlError::Missing("MASH"))
+        ));
+
+        // Extract recipes do not need a mash
+        let mut extract = test_recipe();
+        extract.malts = vec![];
+        extract.extracts = vec![ExtractProportion {
+            extract: MaltExtract::LightDme,
+            proportion: 100.0,
+        }];
+        extract.mash_rests = vec![];
+        let xml = extract.to_beerxml(Liters(20.0));
+        assert!(xml.contains("<TYPE>Extract</TYPE>"));
+        let imported = Recipe::from_beerxml(&xml).unwrap();
+        assert!(imported.mash_rests.is_empty());
+        let process = Process::new(test_brewery(), imported, Liters(20.0));
+        let _ = print_process(&process, None, None, UnitSystem::Metric);
+    }
+}
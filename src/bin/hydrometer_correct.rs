@@ -17,7 +17,8 @@ pub fn main() {
         .expect("failed to read input line.");
     let temp: f32 = s.trim().parse().expect("reading not an f32.");
 
-    let specific_gravity = hydrometer_temp_correction(reading, Celsius(temp), Celsius(20.0));
+    let specific_gravity =
+        hydrometer_temp_correction(SpecificGravity(reading), Celsius(temp), Celsius(20.0));
 
-    println!("S.G. = {:.3}", specific_gravity);
+    println!("S.G. = {:.3}", specific_gravity.0);
 }
@@ -0,0 +1,42 @@
+use beermaker::prelude::*;
+use beermaker::{DEFAULT_WORT_CORRECTION_FACTOR, refractometer_fg};
+use std::io;
+
+pub fn main() {
+    println!("Please enter the ORIGINAL gravity (from a hydrometer): ");
+    let mut s = String::new();
+    io::stdin()
+        .read_line(&mut s)
+        .expect("failed to read input line.");
+    let f: f32 = s.trim().parse().expect("reading not an f32.");
+    let original_sg = SpecificGravity(f);
+
+    println!("Please enter the CURRENT refractometer reading in Brix: ");
+    let mut s = String::new();
+    io::stdin()
+        .read_line(&mut s)
+        .expect("failed to read input line.");
+    let f: f32 = s.trim().parse().expect("reading not an f32.");
+    let current_brix = Brix(f);
+
+    println!(
+        "Please enter your wort correction factor (blank for {DEFAULT_WORT_CORRECTION_FACTOR}): "
+    );
+    let mut s = String::new();
+    io::stdin()
+        .read_line(&mut s)
+        .expect("failed to read input line.");
+    let wort_correction_factor: f32 = if s.trim().is_empty() {
+        DEFAULT_WORT_CORRECTION_FACTOR
+    } else {
+        s.trim().parse().expect("factor not an f32.")
+    };
+
+    let corrected_sg = refractometer_fg(original_sg, current_brix, wort_correction_factor);
+    let abv = Abv::from_gravity(original_sg, corrected_sg, 1.0);
+
+    println!("Current:");
+    println!("  Original Gravity = {original_sg:.3}");
+    println!("  Current Gravity = {corrected_sg:.3} (measured was {current_brix})");
+    println!("  {abv}");
+}
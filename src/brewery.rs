@@ -1,5 +1,5 @@
 use crate::Packaging;
-use crate::ingredients::{Salt, WaterProfile};
+use crate::ingredients::{Salt, WaterProfile, YeastNutrient};
 use crate::prelude::*;
 use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -11,18 +11,44 @@ pub struct Brewery {
     /// Water profile
     pub water_profile: WaterProfile,
 
+    /// Fraction of the brewing water that is reverse osmosis (RO) water,
+    /// blended with the water profile above.  None means no RO water.
+    /// Values outside 0.0 to 1.0 are clamped to that range.
+    pub ro_fraction: Option<f32>,
+
     /// Water salts available
     pub salts_available: Vec<Salt>,
 
     /// Water acids/bases available
     pub acids_available: Vec<Acid>,
 
+    /// The yeast nutrient on hand
+    #[serde(default)]
+    pub yeast_nutrient: YeastNutrient,
+
+    /// Whether the rice and oat hulls on hand may carry gluten, e.g.
+    /// because they are not certified gluten free.
+    #[serde(default)]
+    pub hulls_contain_gluten: bool,
+
     /// Max usable volume of your mash tun (or kettle if BIAB)
     pub mash_tun_volume: Liters,
 
     /// Mash tun losses
     pub mash_tun_losses: Liters,
 
+    /// Heat capacity of the mash tun, as the volume of water that would
+    /// absorb the same heat. This is its weight times its specific heat:
+    /// about 0.12 for steel, 0.2 for aluminium and ceramic, and near zero
+    /// for an insulated cooler.
+    #[serde(default)]
+    pub mash_tun_thermal_mass: Liters,
+
+    /// Whether the mash tun is preheated to the mash temperature (e.g.
+    /// with hot water) before the strike water goes in.
+    #[serde(default)]
+    pub preheat_mash_tun: bool,
+
     /// Max usable volume of your boil kettle
     pub max_kettle_volume: Liters,
 
@@ -73,6 +99,15 @@ pub struct Brewery {
     /// for strike and mash infusions.
     pub room_temperature: Celsius,
 
+    /// Temperature of the keg fridge, where kegged beer is carbonated
+    /// and served.
+    #[serde(default = "default_serving_temperature")]
+    pub serving_temperature: Celsius,
+
+    /// Elevation above sea level. Wort boils cooler at elevation.
+    #[serde(default)]
+    pub elevation: Meters,
+
     /// If chilling is done with an ice bath
     pub ice_bath: bool,
 
@@ -86,7 +121,23 @@ pub struct Brewery {
     pub packaging: Packaging,
 }
 
+/// A typical keg fridge
+fn default_serving_temperature() -> Celsius {
+    Celsius(4.0)
+}
+
 impl Brewery {
+    /// The starting water profile, blending in any RO water
+    #[must_use]
+    pub fn source_water_profile(&self) -> WaterProfile {
+        match self.ro_fraction {
+            Some(ro) => self
+                .water_profile
+                .blend(&WaterProfile::REVERSE_OSMOSIS, 1.0 - ro.clamp(0.0, 1.0)),
+            None => self.water_profile,
+        }
+    }
+
     /// Ice bath ice weight
     #[must_use]
     pub fn ice_weight(&self) -> Kilograms {
@@ -99,3 +150,39 @@ impl Brewery {
         self.max_kettle_volume
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_brewery;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_brewery_without_new_fields() {
+        // A brewery saved before elevation and the serving temperature
+        // were added
+        let mut value = serde_json::to_value(test_brewery()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        let _ = object.remove("elevation");
+        let _ = object.remove("serving_temperature");
+        let brewery: Brewery = serde_json::from_value(value).unwrap();
+        assert_eq!(brewery.elevation, Meters(0.0));
+        assert_eq!(brewery.serving_temperature, Celsius(4.0));
+    }
+
+    #[test]
+    fn test_ro_fraction_clamped() {
+        let mut brewery = test_brewery();
+        brewery.ro_fraction = Some(1.5);
+        let all_ro = brewery.source_water_profile();
+        assert!(approx_eq!(
+            f32,
+            all_ro.ca.0,
+            WaterProfile::REVERSE_OSMOSIS.ca.0
+        ));
+
+        brewery.ro_fraction = Some(-0.5);
+        let no_ro = brewery.source_water_profile();
+        assert!(approx_eq!(f32, no_ro.ca.0, brewery.water_profile.ca.0));
+    }
+}
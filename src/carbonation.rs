@@ -0,0 +1,34 @@
+use crate::units::prelude::*;
+
+/// Regulator (gauge) pressure which carbonates beer held at `temp` to
+/// `volumes_co2`.
+///
+/// This is the Henry's law regression used in the common brewers'
+/// carbonation charts.
+#[must_use]
+pub fn keg_pressure(volumes_co2: CarbonationVolume, temp: Celsius) -> Psi {
+    let t: Fahrenheit = temp.into();
+    let (t, v) = (t.0, volumes_co2.0);
+    Psi(
+        -16.6999 - 0.010_105_9 * t + 0.001_165_12 * t * t + 0.173_354 * t * v + 4.242_67 * v
+            - 0.068_422_6 * v * v,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_keg_pressure() {
+        let psi = keg_pressure(CarbonationVolume(2.5), Celsius(4.0));
+        assert!(psi.0 > 11.0 && psi.0 < 12.0);
+
+        // Warmer beer needs more pressure for the same carbonation
+        assert!(keg_pressure(CarbonationVolume(2.5), Celsius(10.0)).0 > psi.0);
+
+        let bar: Bar = psi.into();
+        assert!(approx_eq!(f32, bar.0, psi.0 / 14.5, epsilon = 0.01));
+    }
+}
@@ -17,6 +17,7 @@ pub enum Element {
     Sulfur,
     Chlorine,
     Calcium,
+    Phosphorus,
 }
 
 impl fmt::Display for Element {
@@ -30,6 +31,7 @@ impl fmt::Display for Element {
             Self::Sulfur => write!(f, "S"),
             Self::Chlorine => write!(f, "Cl"),
             Self::Calcium => write!(f, "Ca"),
+            Self::Phosphorus => write!(f, "P"),
         }
     }
 }
@@ -47,6 +49,7 @@ impl Element {
             Self::Sulfur => 32.06,
             Self::Chlorine => 35.45,
             Self::Calcium => 40.078,
+            Self::Phosphorus => 30.974,
         }
     }
 }
@@ -81,6 +84,12 @@ pub enum Ion {
 
     /// Ca+2
     Calcium,
+
+    /// C3H5O3-, from lactic acid
+    Lactate,
+
+    /// H2PO4-, from phosphoric acid
+    DihydrogenPhosphate,
 }
 
 impl fmt::Display for Ion {
@@ -95,6 +104,8 @@ impl fmt::Display for Ion {
             Self::Sulfate => write!(f, "SO₄⁻²"),
             Self::Chloride => write!(f, "Cl⁻²"),
             Self::Calcium => write!(f, "Ca⁺²"),
+            Self::Lactate => write!(f, "C₃H₅O₃⁻"),
+            Self::DihydrogenPhosphate => write!(f, "H₂PO₄⁻"),
         }
     }
 }
@@ -113,6 +124,8 @@ impl Ion {
             Self::Sulfate => -2,
             Self::Chloride => -2,
             Self::Calcium => 2,
+            Self::Lactate => -1,
+            Self::DihydrogenPhosphate => -1,
         }
     }
 
@@ -159,6 +172,28 @@ impl Ion {
             Self::Calcium => &[
                 Element::Calcium
             ],
+            Self::Lactate => &[
+                Element::Carbon,
+                Element::Carbon,
+                Element::Carbon,
+                Element::Hydrogen,
+                Element::Hydrogen,
+                Element::Hydrogen,
+                Element::Hydrogen,
+                Element::Hydrogen,
+                Element::Oxygen,
+                Element::Oxygen,
+                Element::Oxygen
+            ],
+            Self::DihydrogenPhosphate => &[
+                Element::Hydrogen,
+                Element::Hydrogen,
+                Element::Phosphorus,
+                Element::Oxygen,
+                Element::Oxygen,
+                Element::Oxygen,
+                Element::Oxygen
+            ],
         }
     }
 
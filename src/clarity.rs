@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use crate::{Conditioning, Process};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How clear the finished beer is expected to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Clarity {
+    /// Visibly cloudy, from yeast and protein in suspension
+    Hazy,
+
+    /// Clear, perhaps with a slight chill haze
+    Clear,
+
+    /// Bright and brilliant
+    Brilliant,
+}
+
+impl fmt::Display for Clarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Hazy => write!(f, "hazy"),
+            Self::Clear => write!(f, "clear"),
+            Self::Brilliant => write!(f, "brilliant"),
+        }
+    }
+}
+
+impl Process {
+    /// How clear the finished beer is expected to be
+    ///
+    /// Yeast that flocculates well drops out on its own; fining and a
+    /// long cold lagering drop out more, a step for each three weeks of
+    /// lagering up to two. Wheat beers keep a haze from the protein in the
+    /// wheat (and their yeasts tend to stay in suspension too).
+    #[must_use]
+    pub fn expected_clarity(&self) -> Clarity {
+        let mut score: i32 = match self.recipe.yeast.flocculation() {
+            Flocculation::Low => 0,
+            Flocculation::LowMedium => 1,
+            Flocculation::Medium => 2,
+            Flocculation::MediumHigh => 3,
+            Flocculation::High => 4,
+            Flocculation::VeryHigh => 5,
+        };
+        if self.recipe.fining_desired {
+            score += 2;
+        }
+        if self.recipe.style.conditioning() == Conditioning::Lagered {
+            let weeks = self.conditioning_time().0 / 7;
+            score += i32::try_from(weeks / 3).unwrap_or(i32::MAX).min(2);
+        }
+        if self.recipe.style.is_a_wheat_beer() {
+            score -= 3;
+        }
+
+        match score {
+            6.. => Clarity::Brilliant,
+            2.. => Clarity::Clear,
+            _ => Clarity::Hazy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_process;
+
+    #[test]
+    fn test_expected_clarity() {
+        // A fined Märzen
+        let mut process = test_process();
+        process.recipe.style = Style::Marzen;
+        process.recipe.fining_desired = true;
+        // Medium flocculation (2), fining (2) and seven weeks of lagering (2)
+        assert_eq!(process.conditioning_time(), Days(49));
+        assert_eq!(process.expected_clarity(), Clarity::Brilliant);
+
+        // Unfined, it is only clear
+        process.recipe.fining_desired = false;
+        assert_eq!(process.expected_clarity(), Clarity::Clear);
+        process.recipe.fining_desired = true;
+
+        // An unfined Hefeweizen
+        process.recipe.style = Style::Weissbier;
+        process.recipe.yeast = Yeast::WLP300;
+        process.recipe.fining_desired = false;
+        assert_eq!(process.expected_clarity(), Clarity::Hazy);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::Process;
+use crate::beerxml::trim_brackets;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Ingredient prices, all in the same (any) currency
+///
+/// Malt, sugar, extract, hops, salts and acids are priced per kilogram,
+/// and yeast per package.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceBook {
+    /// Malt prices, per kg
+    #[serde(default)]
+    pub malts: HashMap<Malt, f32>,
+
+    /// Sugar prices, per kg
+    #[serde(default)]
+    pub sugars: HashMap<Sugar, f32>,
+
+    /// Malt extract prices, per kg
+    #[serde(default)]
+    pub extracts: HashMap<MaltExtract, f32>,
+
+    /// Hops prices, per kg
+    #[serde(default)]
+    pub hops: HashMap<Hops, f32>,
+
+    /// Yeast prices, per package
+    #[serde(default)]
+    pub yeasts: HashMap<Yeast, f32>,
+
+    /// Salt prices, per kg
+    #[serde(default)]
+    pub salts: HashMap<Salt, f32>,
+
+    /// Acid prices, per kg
+    #[serde(default)]
+    pub acids: HashMap<Acid, f32>,
+}
+
+impl Process {
+    /// Estimated cost of the ingredients, in the currency of the price
+    /// book.
+    ///
+    /// Ingredients without a price are skipped; see
+    /// `unpriced_ingredients()` for which ones.
+    #[must_use]
+    pub fn estimate_cost(&self, prices: &PriceBook) -> f32 {
+        self.ingredient_costs(prices)
+            .iter()
+            .filter_map(|(_, cost)| *cost)
+            .sum()
+    }
+
+    /// Ingredients which the price book has no price for, and which are
+    /// therefore left out of `estimate_cost()`
+    #[must_use]
+    pub fn unpriced_ingredients(&self, prices: &PriceBook) -> Vec<String> {
+        let mut unpriced: Vec<String> = Vec::new();
+        for (name, cost) in self.ingredient_costs(prices) {
+            if cost.is_none() && !unpriced.contains(&name) {
+                unpriced.push(name);
+            }
+        }
+        unpriced
+    }
+
+    /// The cost of each ingredient, or None if it has no price
+    fn ingredient_costs(&self, prices: &PriceBook) -> Vec<(String, Option<f32>)> {
+        fn cost<T: std::fmt::Display + Eq + std::hash::Hash>(
+            map: &HashMap<T, f32>,
+            item: &T,
+            amount: f32,
+        ) -> (String, Option<f32>) {
+            (
+                trim_brackets(&item.to_string()).to_owned(),
+                map.get(item).map(|price| price * amount),
+            )
+        }
+
+        let mut costs: Vec<(String, Option<f32>)> = Vec::new();
+
+        for dose in &self.malt_doses() {
+            costs.push(cost(&prices.malts, &dose.malt, dose.weight.0));
+        }
+        for dose in &self.sugar_doses() {
+            costs.push(cost(&prices.sugars, &dose.sugar, dose.weight.0));
+        }
+        for dose in &self.extract_doses() {
+            costs.push(cost(&prices.extracts, &dose.extract, dose.weight.0));
+        }
+        for dose in &self.hops_doses() {
+            let kg: Kilograms = dose.weight.into();
+            costs.push(cost(&prices.hops, &dose.hops, kg.0));
+        }
+        for addition in &self.recipe.dry_hops {
+            let kg: Kilograms = self.dry_hop_weight(addition).into();
+            costs.push(cost(&prices.hops, &addition.hops, kg.0));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let packages = self.yeast_packages() as f32;
+        costs.push(cost(&prices.yeasts, &self.recipe.yeast, packages));
+
+        for dose in &self.salt_doses(None) {
+            let kg: Kilograms = dose.mg.into();
+            costs.push(cost(&prices.salts, &dose.salt, kg.0));
+        }
+        for dose in self
+            .mash_acid_doses()
+            .iter()
+            .chain(self.sparge_acid_doses().iter())
+        {
+            let kg: Kilograms = dose.mg.into();
+            costs.push(cost(&prices.acids, &dose.acid, kg.0));
+        }
+
+        costs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_process;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_estimate_cost() {
+        let process = test_process();
+
+        let mut prices = PriceBook::default();
+        for prop in &process.recipe.malts {
+            let _ = prices.malts.insert(prop.malt, 4.0);
+        }
+        for prop in &process.recipe.hops {
+            let _ = prices.hops.insert(prop.hops, 60.0);
+        }
+        let _ = prices.yeasts.insert(process.recipe.yeast, 10.0);
+
+        // 5.214 kg of malt at 4.0, 51.24 g of hops at 60.0 per kg, and 5
+        // packages of yeast at 10.0
+        assert!(approx_eq!(
+            f32,
+            process.estimate_cost(&prices),
+            20.856 + 3.074 + 50.0,
+            epsilon = 0.01
+        ));
+
+        // Acid was not priced, so it is reported
+        assert!(!process.mash_acid_doses().is_empty());
+        let unpriced = process.unpriced_ingredients(&prices);
+        assert!(
+            unpriced
+                .iter()
+                .any(|name| name.as_str() == trim_brackets(&Acid::LacticAcid.to_string()))
+        );
+        assert!(!unpriced.iter().any(|name| name.contains("Munich")));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::Process;
+use crate::beerxml::trim_brackets;
+use serde::{Deserialize, Serialize};
+
+/// Whether the finished beer suits some common dietary needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DietaryReport {
+    /// Whether any ingredient contains gluten
+    pub contains_gluten: bool,
+
+    /// Whether the beer is free of animal products
+    pub vegan: bool,
+
+    /// The ingredients responsible
+    pub notes: Vec<String>,
+}
+
+impl Process {
+    /// Report whether the beer contains gluten, and whether it is vegan
+    #[must_use]
+    pub fn dietary_report(&self) -> DietaryReport {
+        let mut notes: Vec<String> = Vec::new();
+
+        let mut gluten: Vec<String> = Vec::new();
+        for prop in &self.recipe.malts {
+            let hulls = prop.malt.is_hulls() && self.brewery.hulls_contain_gluten;
+            if prop.malt.contains_gluten() || hulls {
+                gluten.push(trim_brackets(&prop.malt.to_string()).to_owned());
+            }
+        }
+        for prop in &self.recipe.sugars {
+            if prop.sugar.contains_gluten() {
+                gluten.push(trim_brackets(&prop.sugar.to_string()).to_owned());
+            }
+        }
+        for prop in &self.recipe.extracts {
+            gluten.push(trim_brackets(&prop.extract.to_string()).to_owned());
+        }
+        if !gluten.is_empty() {
+            notes.push(format!("Contains gluten from {}", gluten.join(", ")));
+        }
+
+        let mut animal: Vec<String> = Vec::new();
+        for prop in &self.recipe.sugars {
+            if !prop.sugar.is_vegan() {
+                animal.push(trim_brackets(&prop.sugar.to_string()).to_owned());
+            }
+        }
+        if self.recipe.fining_desired && !self.recipe.fining_agent.is_vegan() {
+            animal.push(trim_brackets(&self.recipe.fining_agent.to_string()).to_owned());
+        }
+        if !animal.is_empty() {
+            notes.push(format!("Not vegan due to {}", animal.join(", ")));
+        }
+
+        DietaryReport {
+            contains_gluten: !gluten.is_empty(),
+            vegan: animal.is_empty(),
+            notes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::process::test::test_process;
+
+    #[test]
+    fn test_dietary_report() {
+        let mut process = test_process();
+        assert_eq!(process.recipe.fining_agent, FiningAgent::Whirlfloc);
+        let report = process.dietary_report();
+        assert!(report.contains_gluten);
+        assert!(report.vegan);
+
+        process.recipe.fining_agent = FiningAgent::Isinglass;
+        let report = process.dietary_report();
+        assert!(!report.vegan);
+        assert!(report.notes.iter().any(|n| n.contains("Isinglass")));
+
+        // Not used, so it does not matter
+        process.recipe.fining_desired = false;
+        assert!(process.dietary_report().vegan);
+
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::FlakedRice,
+            proportion: 1.0,
+        }];
+        assert!(!process.dietary_report().contains_gluten);
+
+        process.recipe.malts.push(MaltProportion {
+            malt: Malt::RiceHulls,
+            proportion: 0.1,
+        });
+        assert!(!process.dietary_report().contains_gluten);
+        process.brewery.hulls_contain_gluten = true;
+        assert!(process.dietary_report().contains_gluten);
+    }
+}
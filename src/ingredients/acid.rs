@@ -1,18 +1,141 @@
+use crate::chemistry::Ion;
+use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A type of Acid or Base
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[allow(clippy::doc_markdown)]
 pub enum Acid {
-    /// Lactic Acid
+    /// Lactic Acid, 88%
     LacticAcid,
+
+    /// Phosphoric Acid, 10%
+    PhosphoricAcid,
+
+    /// Phosphoric Acid, 75%
+    PhosphoricAcid75,
 }
 
 impl fmt::Display for Acid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::LacticAcid => write!(f, "[Lactic Acid, 88%]"),
+            Self::PhosphoricAcid => write!(f, "[Phosphoric Acid, 10%]"),
+            Self::PhosphoricAcid75 => write!(f, "[Phosphoric Acid, 75%]"),
+        }
+    }
+}
+
+impl Acid {
+    /// What ions does the pure acid have?
+    ///
+    /// Only the proton that dissociates at mash pH is separated, so
+    /// phosphoric acid is H⁺ and H₂PO₄⁻.
+    #[must_use]
+    pub fn ions(&self) -> &[Ion] {
+        match *self {
+            Self::LacticAcid => &[Ion::Hydrogen, Ion::Lactate],
+            Self::PhosphoricAcid | Self::PhosphoricAcid75 => {
+                &[Ion::Hydrogen, Ion::DihydrogenPhosphate]
+            }
+        }
+    }
+
+    /// Molecular weight of the pure acid
+    #[must_use]
+    pub fn molecular_weight(&self) -> f32 {
+        self.ions().iter().map(Ion::molecular_weight).sum()
+    }
+
+    /// What fraction (by weight) of the pure acid is the ion
+    #[must_use]
+    pub fn ion_fraction(&self, target_ion: Ion) -> f32 {
+        let numerator: f32 = self
+            .ions()
+            .iter()
+            .filter(|ion| **ion == target_ion)
+            .map(Ion::molecular_weight)
+            .sum();
+        numerator / self.molecular_weight()
+    }
+
+    /// Concentration of the acid in the solution, by weight
+    #[must_use]
+    pub fn concentration(&self) -> f32 {
+        match *self {
+            Self::LacticAcid => 0.88,
+            Self::PhosphoricAcid => 0.10,
+            Self::PhosphoricAcid75 => 0.75,
+        }
+    }
+
+    /// Density of the acid solution, g/mL
+    #[must_use]
+    pub fn density(&self) -> f32 {
+        match *self {
+            Self::LacticAcid => 1.21,
+            Self::PhosphoricAcid => 1.05,
+            Self::PhosphoricAcid75 => 1.57,
+        }
+    }
+
+    /// Milliequivalents of acidity per gram of the acid solution, at
+    /// mash pH.
+    ///
+    /// Both acids give up only one proton at mash pH.
+    #[must_use]
+    pub fn meq_per_gram(&self) -> f32 {
+        self.concentration() * 1000.0 / self.molecular_weight()
+    }
+
+    /// Normality of the acid solution at mash pH, which is also the
+    /// milliequivalents per mL
+    #[must_use]
+    pub fn normality(&self) -> f32 {
+        self.meq_per_gram() * self.density()
+    }
+
+    /// How much of the acid solution reduces the alkalinity of this
+    /// volume of water by this much
+    #[must_use]
+    pub fn ml_for_alkalinity_reduction(&self, reduction: CaCO3, volume: Liters) -> Milliliters {
+        let meql: AlkMEqL = reduction.into();
+        Milliliters(meql.0 * volume.0 / self.normality())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_acid_strength() {
+        // 88% lactic acid is about 11.8 N
+        assert!(approx_eq!(
+            f32,
+            Acid::LacticAcid.normality(),
+            11.8,
+            epsilon = 0.1
+        ));
+
+        // 1 mL in 5 gallons takes out about 31 ppm of alkalinity as CaCO3
+        let ml = Acid::LacticAcid.ml_for_alkalinity_reduction(CaCO3(31.2), Gallons(5.0).into());
+        assert!(approx_eq!(f32, ml.0, 1.0, epsilon = 0.02));
+
+        for acid in [
+            Acid::LacticAcid,
+            Acid::PhosphoricAcid,
+            Acid::PhosphoricAcid75,
+        ] {
+            assert!(approx_eq!(
+                f32,
+                acid.ion_fraction(Ion::Hydrogen) * acid.molecular_weight(),
+                Ion::Hydrogen.molecular_weight(),
+                epsilon = 0.001
+            ));
         }
+        assert!(Acid::PhosphoricAcid75.normality() > 11.0);
     }
 }
@@ -0,0 +1,114 @@
+use crate::units::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::EnumIter;
+
+/// A type of malt extract
+///
+/// Unlike sugar, extract is mostly wort and brings body and FAN along
+/// with its points. It has already been mashed, so it is added to the
+/// kettle and does not depend on mash efficiency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum MaltExtract {
+    /// Light Dry Malt Extract
+    LightDme,
+
+    /// Amber Dry Malt Extract
+    AmberDme,
+
+    /// Dark Dry Malt Extract
+    DarkDme,
+
+    /// Wheat Dry Malt Extract
+    WheatDme,
+
+    /// Pilsen Liquid Malt Extract
+    PilsenLme,
+
+    /// Light Liquid Malt Extract
+    LightLme,
+
+    /// Amber Liquid Malt Extract
+    AmberLme,
+}
+
+impl MaltExtract {
+    /// Whether this is dry (rather than liquid) extract
+    #[must_use]
+    pub fn is_dry(&self) -> bool {
+        matches!(
+            *self,
+            MaltExtract::LightDme
+                | MaltExtract::AmberDme
+                | MaltExtract::DarkDme
+                | MaltExtract::WheatDme
+        )
+    }
+
+    /// Points per pound per gallon
+    ///
+    /// Liquid extract is about 20% water.
+    #[must_use]
+    pub fn ppg(&self) -> f32 {
+        if self.is_dry() { 44.0 } else { 36.0 }
+    }
+
+    /// Points per kilogram per liter (the metric form of `ppg()`)
+    #[must_use]
+    pub fn pkl(&self) -> f32 {
+        self.ppg() * PKL_PER_PPG
+    }
+
+    /// Extract potential as a percentage of the weight (sucrose is 100%)
+    #[must_use]
+    pub fn extract_percent(&self) -> f32 {
+        self.ppg() / SUCROSE_PPG * 100.0
+    }
+
+    /// Color, EBC
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn ebc(&self) -> Ebc {
+        match *self {
+            MaltExtract::LightDme => Ebc(8.0),
+            MaltExtract::AmberDme => Ebc(20.0),
+            MaltExtract::DarkDme => Ebc(60.0),
+            MaltExtract::WheatDme => Ebc(6.0),
+            MaltExtract::PilsenLme => Ebc(5.0),
+            MaltExtract::LightLme => Ebc(8.0),
+            MaltExtract::AmberLme => Ebc(20.0),
+        }
+    }
+
+    /// Free Amino Nitrogen (FAN) contribution in mg/L at 1.040 SG.
+    ///
+    /// Extract carries the FAN of the wort it was made from. Darker
+    /// extracts include specialty malts which contribute little.
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn fan(&self) -> Ppm {
+        match *self {
+            MaltExtract::LightDme => Ppm(130.0),
+            MaltExtract::AmberDme => Ppm(110.0),
+            MaltExtract::DarkDme => Ppm(90.0),
+            MaltExtract::WheatDme => Ppm(100.0),
+            MaltExtract::PilsenLme => Ppm(130.0),
+            MaltExtract::LightLme => Ppm(130.0),
+            MaltExtract::AmberLme => Ppm(110.0),
+        }
+    }
+}
+
+impl fmt::Display for MaltExtract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MaltExtract::LightDme => write!(f, "[Light DME]"),
+            MaltExtract::AmberDme => write!(f, "[Amber DME]"),
+            MaltExtract::DarkDme => write!(f, "[Dark DME]"),
+            MaltExtract::WheatDme => write!(f, "[Wheat DME]"),
+            MaltExtract::PilsenLme => write!(f, "[Pilsen LME]"),
+            MaltExtract::LightLme => write!(f, "[Light LME]"),
+            MaltExtract::AmberLme => write!(f, "[Amber LME]"),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::EnumIter;
+
+/// A fining agent, used to clear the beer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum FiningAgent {
+    /// Whirlfloc tablets (Irish moss and carrageenan), added to the boil
+    #[default]
+    Whirlfloc,
+
+    /// Irish moss, a seaweed added to the boil
+    IrishMoss,
+
+    /// Carrageenan, refined from seaweed, added to the boil
+    Carrageenan,
+
+    /// Isinglass, from fish swim bladders, added after fermentation
+    Isinglass,
+
+    /// Gelatin, from animal collagen, added after fermentation
+    Gelatin,
+}
+
+impl FiningAgent {
+    /// Whether it is free of animal products
+    #[must_use]
+    pub fn is_vegan(&self) -> bool {
+        !matches!(*self, FiningAgent::Isinglass | FiningAgent::Gelatin)
+    }
+
+    /// Whether it is added to the boil (rather than to the beer after
+    /// fermentation)
+    #[must_use]
+    pub fn is_added_to_boil(&self) -> bool {
+        matches!(
+            *self,
+            FiningAgent::Whirlfloc | FiningAgent::IrishMoss | FiningAgent::Carrageenan
+        )
+    }
+
+    /// How much to use per liter, in `unit()`s
+    #[must_use]
+    pub fn amount_per_liter(&self) -> f32 {
+        match *self {
+            FiningAgent::Whirlfloc => 1.0 / 19.0, // one tablet per 5 gallons
+            FiningAgent::IrishMoss => 0.13,       // a teaspoon per 5 gallons
+            FiningAgent::Carrageenan => 0.03,
+            FiningAgent::Isinglass => 1.5, // liquid, 25-40 ml per 23 L
+            FiningAgent::Gelatin => 0.15,  // a teaspoon per 5 gallons
+        }
+    }
+
+    /// The unit it is measured in
+    #[must_use]
+    pub fn unit(&self) -> &'static str {
+        match *self {
+            FiningAgent::Whirlfloc => "tablets",
+            FiningAgent::Isinglass => "ml",
+            _ => "g",
+        }
+    }
+}
+
+impl fmt::Display for FiningAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            FiningAgent::Whirlfloc => write!(f, "[Whirlfloc]"),
+            FiningAgent::IrishMoss => write!(f, "[Irish Moss]"),
+            FiningAgent::Carrageenan => write!(f, "[Carrageenan]"),
+            FiningAgent::Isinglass => write!(f, "[Isinglass]"),
+            FiningAgent::Gelatin => write!(f, "[Gelatin]"),
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use strum::EnumIter;
 
 /// Typical usage of a hops variety
 #[allow(missing_docs)]
@@ -10,8 +12,21 @@ pub enum HopsUsage {
     DualPurpose,
 }
 
+/// A broad character of hop aroma
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumIter)]
+pub enum HopDescriptor {
+    Floral,
+    Spicy,
+    Citrus,
+    Pine,
+    Tropical,
+    Herbal,
+    Earthy,
+}
+
 /// A variety of Hops
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, EnumIter)]
 pub enum Hops {
     /// Cascade
     Cascade,
@@ -148,4 +163,308 @@ impl Hops {
             Hops::Williamette => 0.054,
         }
     }
+
+    /// Hop Storage Index (HSI): the fraction of alpha acid lost after
+    /// 6 months stored at 20°C.  Lower keeps better.
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn storage_index(&self) -> f32 {
+        match *self {
+            Hops::Cascade => 0.50,
+            Hops::Challenger => 0.25,
+            Hops::Chinook => 0.32,
+            Hops::Citra => 0.25,
+            Hops::EastKentGoldings => 0.35,
+            Hops::Fuggles => 0.35,
+            Hops::Galaxy => 0.35,
+            Hops::HallertauMittelfruh => 0.50,
+            Hops::Magnum => 0.15,
+            Hops::Mosaic => 0.25,
+            Hops::NelsonSauvin => 0.30,
+            Hops::Northdown => 0.30,
+            Hops::Saaz => 0.50,
+            Hops::Simcoe => 0.25,
+            Hops::Target => 0.45,
+            Hops::Tettnang => 0.55,
+            Hops::Williamette => 0.40,
+        }
+    }
+
+    /// The fraction of the alpha acid that remains after storage
+    ///
+    /// Alpha acid decays exponentially, at a rate fixed by the storage
+    /// index (which is measured over six months at room temperature) and
+    /// slowed by colder, sealed storage.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn alpha_acid_retained(&self, age: Days, storage: HopStorage) -> f32 {
+        let k = -(1.0 - self.storage_index()).ln() / 182.5;
+        (-k * storage.factor() * age.0 as f32).exp()
+    }
+
+    /// Whether this hop keeps poorly, and so should be bought fresh
+    /// when used for aroma.
+    #[must_use]
+    pub fn keeps_poorly(&self) -> bool {
+        self.storage_index() >= 0.4
+    }
+
+    /// Whether this is one of the traditional noble hops
+    #[must_use]
+    pub fn is_noble(&self) -> bool {
+        matches!(
+            *self,
+            Hops::HallertauMittelfruh | Hops::Saaz | Hops::Tettnang
+        )
+    }
+
+    /// The main characters of this hop's aroma, most prominent first
+    #[must_use]
+    pub fn aroma_profile(&self) -> &'static [HopDescriptor] {
+        use HopDescriptor::{Citrus, Earthy, Floral, Herbal, Pine, Spicy, Tropical};
+        match *self {
+            Hops::Cascade => &[Floral, Citrus, Spicy],
+            Hops::Challenger => &[Spicy, Herbal],
+            Hops::Chinook => &[Pine, Spicy, Citrus],
+            Hops::Citra => &[Citrus, Tropical],
+            Hops::EastKentGoldings => &[Floral, Spicy, Earthy],
+            Hops::Fuggles => &[Earthy, Herbal],
+            Hops::Galaxy => &[Tropical, Citrus],
+            Hops::HallertauMittelfruh => &[Floral, Spicy, Herbal],
+            Hops::Magnum => &[Herbal],
+            Hops::Mosaic => &[Tropical, Citrus, Pine],
+            Hops::NelsonSauvin => &[Tropical, Citrus],
+            Hops::Northdown => &[Pine, Floral, Spicy],
+            Hops::Saaz => &[Spicy, Earthy, Herbal],
+            Hops::Simcoe => &[Pine, Citrus, Tropical],
+            Hops::Target => &[Herbal, Spicy],
+            Hops::Tettnang => &[Floral, Spicy, Herbal],
+            Hops::Williamette => &[Floral, Spicy, Earthy],
+        }
+    }
+
+    /// Varieties that can stand in for this one, with similar alpha
+    /// acids and aroma, best first.
+    ///
+    /// Only varieties we know about are listed (e.g. Liberty and Mt. Hood
+    /// are also good stand-ins for the noble hops).
+    #[must_use]
+    pub fn substitutes(&self) -> Vec<Hops> {
+        match *self {
+            Hops::Cascade => vec![Hops::Citra, Hops::Mosaic],
+            Hops::Challenger => vec![Hops::Northdown, Hops::Target, Hops::EastKentGoldings],
+            Hops::Chinook => vec![Hops::Simcoe, Hops::Northdown],
+            Hops::Citra => vec![Hops::Mosaic, Hops::Galaxy, Hops::Simcoe],
+            Hops::EastKentGoldings => vec![Hops::Fuggles, Hops::Williamette, Hops::Challenger],
+            Hops::Fuggles => vec![Hops::Williamette, Hops::EastKentGoldings],
+            Hops::Galaxy => vec![Hops::Citra, Hops::NelsonSauvin, Hops::Mosaic],
+            Hops::HallertauMittelfruh => vec![Hops::Tettnang, Hops::Saaz],
+            Hops::Magnum => vec![Hops::Target, Hops::Challenger],
+            Hops::Mosaic => vec![Hops::Citra, Hops::Simcoe, Hops::Galaxy],
+            Hops::NelsonSauvin => vec![Hops::Galaxy, Hops::Citra],
+            Hops::Northdown => vec![Hops::Challenger, Hops::Target],
+            Hops::Saaz => vec![Hops::Tettnang, Hops::HallertauMittelfruh],
+            Hops::Simcoe => vec![Hops::Chinook, Hops::Mosaic, Hops::Citra],
+            Hops::Target => vec![Hops::Challenger, Hops::Northdown, Hops::Magnum],
+            Hops::Tettnang => vec![Hops::Saaz, Hops::HallertauMittelfruh],
+            Hops::Williamette => vec![Hops::Fuggles, Hops::EastKentGoldings],
+        }
+    }
+}
+
+/// How hops were stored while they aged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HopStorage {
+    /// Vacuum or nitrogen sealed, in a freezer
+    FreezerSealed,
+
+    /// Vacuum or nitrogen sealed, in a refrigerator
+    FridgeSealed,
+
+    /// Unsealed, at room temperature
+    RoomTempOpen,
+}
+
+impl HopStorage {
+    /// Relative rate of alpha acid loss, compared to room temperature
+    /// storage open to the air (Garetz temperature and sealing factors)
+    #[must_use]
+    pub fn factor(&self) -> f32 {
+        match *self {
+            HopStorage::FreezerSealed => 0.087,
+            HopStorage::FridgeSealed => 0.24,
+            HopStorage::RoomTempOpen => 1.0,
+        }
+    }
+}
+
+/// A model of hops utilization, for computing IBU
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IbuModel {
+    /// Glenn Tinseth
+    #[default]
+    Tinseth,
+
+    /// Jackie Rager
+    Rager,
+
+    /// Mark Garetz
+    Garetz,
+}
+
+impl fmt::Display for IbuModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IbuModel::Tinseth => write!(f, "Tinseth"),
+            IbuModel::Rager => write!(f, "Rager"),
+            IbuModel::Garetz => write!(f, "Garetz"),
+        }
+    }
+}
+
+/// Fraction of the alpha acid of a hops addition that ends up in the beer
+/// as iso-alpha acid.
+///
+/// `gravity` is the boil gravity. `ibu` is the expected bitterness of the
+/// beer and `elevation` is that of the brewery; only Garetz uses these.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn utilization(
+    model: IbuModel,
+    gravity: SpecificGravity,
+    time: Minutes,
+    ibu: Ibu,
+    elevation: Meters,
+) -> f32 {
+    let minutes = time.0 as f32;
+    let gravity_excess = ((gravity.0 - 1.050) / 0.2).max(0.0);
+    match model {
+        IbuModel::Tinseth => {
+            let bigness_factor = 1.65 * (0.000_125_f32).powf(gravity.0 - 1.0);
+            let boil_time_factor = (1.0 - (-0.04 * minutes).exp()) / 4.15;
+            bigness_factor * boil_time_factor
+        }
+        IbuModel::Rager => {
+            let percent = 18.11 + 13.86 * ((minutes - 31.32) / 18.27).tanh();
+            percent / 100.0 / (1.0 + gravity_excess)
+        }
+        IbuModel::Garetz => {
+            let percent = match time.0 {
+                0..=10 => 0.0,
+                11..=15 => 2.0,
+                16..=20 => 5.0,
+                21..=25 => 8.0,
+                26..=30 => 11.0,
+                31..=35 => 14.0,
+                36..=40 => 16.0,
+                41..=45 => 18.0,
+                46..=50 => 19.0,
+                51..=60 => 20.0,
+                61..=70 => 21.0,
+                71..=80 => 22.0,
+                _ => 23.0,
+            };
+            let gravity_factor = 1.0 + gravity_excess;
+            let hopping_rate_factor = 1.0 + ibu.0 / 260.0;
+            let feet: Feet = elevation.into();
+            let temperature_factor = 1.0 + feet.0 / 550.0 * 0.02;
+            percent / 100.0 / (gravity_factor * hopping_rate_factor * temperature_factor)
+        }
+    }
+}
+
+/// Rate of isomerization at `temperature`, relative to that at a boil.
+///
+/// Isomerization slows sharply below 100 °C but does not stop, so hops
+/// held in a whirlpool or hop stand still contribute some bitterness.
+/// This follows the Arrhenius fit from Malowicki and Shellhammer.
+#[must_use]
+pub fn whirlpool_factor(temperature: Celsius) -> f32 {
+    let kelvin = temperature.0.min(100.0) + 273.15;
+    (-11858.0 * (1.0 / kelvin - 1.0 / 373.15)).exp()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_hops_storage_index() {
+        let noble = Hops::Saaz;
+        let stable = Hops::Magnum;
+        assert!(noble.storage_index() > stable.storage_index());
+        assert!(noble.keeps_poorly());
+        assert!(!stable.keeps_poorly());
+
+        let age = Days(365);
+        let noble_kept = noble.alpha_acid_retained(age, HopStorage::RoomTempOpen);
+        let stable_kept = stable.alpha_acid_retained(age, HopStorage::RoomTempOpen);
+        assert!(noble_kept < stable_kept);
+        assert!(stable_kept < 1.0);
+    }
+
+    #[test]
+    fn test_utilization_models() {
+        let sg = SpecificGravity(1.056);
+        let ibu = Ibu(21.0);
+        let sea = Meters(0.0);
+        let tinseth = utilization(IbuModel::Tinseth, sg, Minutes(60), ibu, sea);
+        let rager = utilization(IbuModel::Rager, sg, Minutes(60), ibu, sea);
+        let garetz = utilization(IbuModel::Garetz, sg, Minutes(60), ibu, sea);
+        for u in [tinseth, rager, garetz] {
+            assert!(u > 0.15 && u < 0.35, "{u}");
+        }
+        assert!(rager > tinseth);
+        assert!(garetz < tinseth);
+
+        // Garetz ignores very late additions
+        assert!(utilization(IbuModel::Garetz, sg, Minutes(5), ibu, sea) < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_hops_aged_alpha_acid() {
+        // Cascade is high in cohumulone and keeps poorly
+        let hops = Hops::Cascade;
+        let year = Days(365);
+        let year_open = hops.alpha_acid_retained(year, HopStorage::RoomTempOpen);
+        assert!(approx_eq!(f32, year_open, 0.25, epsilon = 0.001));
+
+        // Each six months open loses the storage index
+        assert!(approx_eq!(
+            f32,
+            year_open,
+            (1.0 - hops.storage_index()).powi(2),
+            epsilon = 0.001
+        ));
+
+        // Sealed in the freezer it barely changes
+        let year_frozen = hops.alpha_acid_retained(year, HopStorage::FreezerSealed);
+        let year_fridge = hops.alpha_acid_retained(year, HopStorage::FridgeSealed);
+        assert!(year_frozen > 0.85);
+        assert!(year_frozen > year_fridge);
+        assert!(year_fridge > year_open);
+    }
+
+    #[test]
+    fn test_hops_substitutes() {
+        use strum::IntoEnumIterator;
+
+        let hallertau = Hops::HallertauMittelfruh;
+        assert!(hallertau.substitutes().iter().any(Hops::is_noble));
+
+        for hops in Hops::iter() {
+            assert!(!hops.aroma_profile().is_empty());
+            assert!(!hops.substitutes().contains(&hops));
+            // Substitutes share at least some of the aroma
+            for sub in hops.substitutes() {
+                assert!(
+                    sub.aroma_profile()
+                        .iter()
+                        .any(|d| hops.aroma_profile().contains(d)),
+                    "{hops} {sub}"
+                );
+            }
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 
 /*
 Malt companies:
@@ -44,8 +44,51 @@ pub enum MaltCategory {
     Special,
 }
 
+/// A maltster (malt producer)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Maltster {
+    /// BestMalz (Germany)
+    BestMalz,
+
+    /// Briess (USA)
+    Briess,
+
+    /// Dingemans (Belgium)
+    Dingemans,
+
+    /// Thomas Fawcett (UK)
+    Fawcett,
+
+    /// Gladfield (New Zealand)
+    Gladfield,
+
+    /// Simpsons (UK)
+    Simpsons,
+
+    /// Weyermann (Germany)
+    Weyermann,
+
+    /// Not specific to any maltster (adjuncts, hulls)
+    Generic,
+}
+
+impl fmt::Display for Maltster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Maltster::BestMalz => write!(f, "BestMalz"),
+            Maltster::Briess => write!(f, "Briess"),
+            Maltster::Dingemans => write!(f, "Dingemans"),
+            Maltster::Fawcett => write!(f, "Thomas Fawcett"),
+            Maltster::Gladfield => write!(f, "Gladfield"),
+            Maltster::Simpsons => write!(f, "Simpsons"),
+            Maltster::Weyermann => write!(f, "Weyermann"),
+            Maltster::Generic => write!(f, "Generic"),
+        }
+    }
+}
+
 /// A type of Malt
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter, PartialEq, Eq, Hash)]
 pub enum Malt {
     /// BestMalz Vienna
     BestMalzVienna,
@@ -62,6 +105,9 @@ pub enum Malt {
     /// Thomas Fawcett Pale Chocolate
     FawcettPaleChocolate,
 
+    /// Flaked Rice (unmalted adjunct)
+    FlakedRice,
+
     /// Gladfield Ale Malt
     GladfieldAle,
 
@@ -163,6 +209,59 @@ pub enum Malt {
 }
 
 impl Malt {
+    /// The maltster that produces this malt
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn maltster(&self) -> Maltster {
+        match *self {
+            Malt::BestMalzVienna => Maltster::BestMalz,
+            Malt::BriessVictory => Maltster::Briess,
+            Malt::DingemansSpecialB => Maltster::Dingemans,
+            Malt::FawcettHalcyonPaleAle => Maltster::Fawcett,
+            Malt::FawcettPaleChocolate => Maltster::Fawcett,
+            Malt::FlakedRice => Maltster::Generic,
+            Malt::GladfieldAle => Maltster::Gladfield,
+            Malt::GladfieldAmericanAle => Maltster::Gladfield,
+            Malt::GladfieldAurora => Maltster::Gladfield,
+            Malt::GladfieldBigOMaltedOats => Maltster::Gladfield,
+            Malt::GladfieldBiscuit => Maltster::Gladfield,
+            Malt::GladfieldBrown => Maltster::Gladfield,
+            Malt::GladfieldCrystalDark => Maltster::Gladfield,
+            Malt::GladfieldCrystalLight => Maltster::Gladfield,
+            Malt::GladfieldCrystalMedium => Maltster::Gladfield,
+            Malt::GladfieldDarkChocolate => Maltster::Gladfield,
+            Malt::GladfieldGermanPilsner => Maltster::Gladfield,
+            Malt::GladfieldLagerLight => Maltster::Gladfield,
+            Malt::GladfieldMunich => Maltster::Gladfield,
+            Malt::GladfieldPilsner => Maltster::Gladfield,
+            Malt::GladfieldShepherdsDelight => Maltster::Gladfield,
+            Malt::GladfieldVienna => Maltster::Gladfield,
+            Malt::GladfieldWheat => Maltster::Gladfield,
+            Malt::OatHulls => Maltster::Generic,
+            Malt::RiceHulls => Maltster::Generic,
+            Malt::SimpsonsMarisOtterPale => Maltster::Simpsons,
+            Malt::WeyermannAcidulated => Maltster::Weyermann,
+            Malt::WeyermannBohemianPilsner => Maltster::Weyermann,
+            Malt::WeyermannCarafaSpecial2 => Maltster::Weyermann,
+            Malt::WeyermannCarafaSpecial3 => Maltster::Weyermann,
+            Malt::WeyermannCarahell => Maltster::Weyermann,
+            Malt::WeyermannCaramunich2 => Maltster::Weyermann,
+            Malt::WeyermannCarapils => Maltster::Weyermann,
+            Malt::WeyermannMelanoidin => Maltster::Weyermann,
+            Malt::WeyermannMunich1 => Maltster::Weyermann,
+            Malt::WeyermannMunich2 => Maltster::Weyermann,
+            Malt::WeyermannPilsner => Maltster::Weyermann,
+            Malt::WeyermannVienna => Maltster::Weyermann,
+            Malt::WeyermannWheatPale => Maltster::Weyermann,
+        }
+    }
+
+    /// All malts from a given maltster
+    #[must_use]
+    pub fn all_by_maltster(maltster: Maltster) -> Vec<Malt> {
+        Malt::iter().filter(|m| m.maltster() == maltster).collect()
+    }
+
     /// Category of malt
     #[must_use]
     #[allow(clippy::match_same_arms)]
@@ -173,6 +272,7 @@ impl Malt {
             Malt::DingemansSpecialB => MaltCategory::Crystal,
             Malt::FawcettHalcyonPaleAle => MaltCategory::Base,
             Malt::FawcettPaleChocolate => MaltCategory::Roasted,
+            Malt::FlakedRice => MaltCategory::Special,
             Malt::GladfieldAle => MaltCategory::Base,
             Malt::GladfieldAmericanAle => MaltCategory::Base,
             Malt::GladfieldAurora => MaltCategory::Base,
@@ -209,6 +309,26 @@ impl Malt {
         }
     }
 
+    /// Whether it contains gluten.
+    ///
+    /// Barley and wheat do, rice and oats do not. Note that oats are
+    /// often contaminated with gluten unless they are certified free of
+    /// it.
+    #[must_use]
+    pub fn contains_gluten(&self) -> bool {
+        !matches!(
+            *self,
+            Malt::FlakedRice | Malt::RiceHulls | Malt::OatHulls | Malt::GladfieldBigOMaltedOats
+        )
+    }
+
+    /// Whether it is rice or oat hulls, which are gluten free unless
+    /// contaminated (see `Brewery::hulls_contain_gluten`)
+    #[must_use]
+    pub fn is_hulls(&self) -> bool {
+        matches!(*self, Malt::RiceHulls | Malt::OatHulls)
+    }
+
     /// Distilled water mash pH
     #[must_use]
     pub fn distilled_water_mash_ph(&self) -> Option<Ph> {
@@ -218,6 +338,7 @@ impl Malt {
             Malt::DingemansSpecialB => None,
             Malt::FawcettHalcyonPaleAle => None,
             Malt::FawcettPaleChocolate => None,
+            Malt::FlakedRice => None,
             Malt::GladfieldAle => Some(Ph(f32::midpoint(5.7, 6.0))), // [2]
             Malt::GladfieldAmericanAle => Some(Ph(f32::midpoint(5.7, 6.0))), // [2]
             Malt::GladfieldAurora => Some(Ph(f32::midpoint(5.1, 5.8))), // [2]
@@ -305,6 +426,7 @@ impl Malt {
             Malt::DingemansSpecialB => (Ebc(300.0), Ebc(300.0)),
             Malt::FawcettHalcyonPaleAle => (Ebc(5.0), Ebc(7.5)),
             Malt::FawcettPaleChocolate => (Ebc(560.0), Ebc(690.0)),
+            Malt::FlakedRice => (Ebc(1.0), Ebc(2.0)),
             Malt::GladfieldAle => (Ebc(5.0), Ebc(6.0)),
             Malt::GladfieldAmericanAle => (Ebc(4.5), Ebc(5.5)),
             Malt::GladfieldAurora => (Ebc(35.0), Ebc(60.0)),
@@ -357,6 +479,7 @@ impl Malt {
             Malt::DingemansSpecialB => 15.0,
             Malt::FawcettHalcyonPaleAle => 100.0,
             Malt::FawcettPaleChocolate => 5.0,
+            Malt::FlakedRice => 40.0,
             Malt::GladfieldAle => 100.0,
             Malt::GladfieldAmericanAle => 100.0,
             Malt::GladfieldAurora => 100.0,
@@ -393,6 +516,18 @@ impl Malt {
         }
     }
 
+    /// Points per kilogram per liter (the metric form of `ppg()`)
+    #[must_use]
+    pub fn pkl(&self) -> f32 {
+        self.ppg() * PKL_PER_PPG
+    }
+
+    /// Extract potential as a percentage of the weight (sucrose is 100%)
+    #[must_use]
+    pub fn extract_percent(&self) -> f32 {
+        self.ppg() / SUCROSE_PPG * 100.0
+    }
+
     /// Points per pound per gallon
     // taken from brewersfriend.com
     #[must_use]
@@ -404,6 +539,7 @@ impl Malt {
             Malt::DingemansSpecialB => 33.1,
             Malt::FawcettHalcyonPaleAle => 36.3,
             Malt::FawcettPaleChocolate => 32.2,
+            Malt::FlakedRice => 38.0,
             Malt::GladfieldAle => 37.4,
             Malt::GladfieldAmericanAle => 37.3,
             Malt::GladfieldAurora => 37.2,
@@ -440,6 +576,107 @@ impl Malt {
         }
     }
 
+    /// Diastatic power, the enzymes available to convert starch
+    /// (including that of any adjuncts) to sugar
+    ///
+    /// Crystal, roasted and unmalted grains have essentially none.
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn diastatic_power(&self) -> Lintner {
+        match *self {
+            Malt::BestMalzVienna => Lintner(76.0), // 250 WK
+            Malt::BriessVictory => Lintner(0.0),
+            Malt::DingemansSpecialB => Lintner(0.0),
+            Malt::FawcettHalcyonPaleAle => Lintner(60.0),
+            Malt::FawcettPaleChocolate => Lintner(0.0),
+            Malt::FlakedRice => Lintner(0.0),
+            Malt::GladfieldAle => Lintner(76.0), // min spec 250 WK
+            Malt::GladfieldAmericanAle => Lintner(90.0), // min spec 300 WK
+            Malt::GladfieldAurora => Lintner(62.0), // min spec 200 WK
+            Malt::GladfieldBigOMaltedOats => Lintner(10.0), // presume little
+            Malt::GladfieldBiscuit => Lintner(0.0),
+            Malt::GladfieldBrown => Lintner(0.0),
+            Malt::GladfieldCrystalDark => Lintner(0.0),
+            Malt::GladfieldCrystalLight => Lintner(0.0),
+            Malt::GladfieldCrystalMedium => Lintner(0.0),
+            Malt::GladfieldDarkChocolate => Lintner(0.0),
+            Malt::GladfieldGermanPilsner => Lintner(100.0), // min spec 330 WK
+            Malt::GladfieldLagerLight => Lintner(90.0),     // min spec 300 WK
+            Malt::GladfieldMunich => Lintner(47.0),         // min spec 150 WK
+            Malt::GladfieldPilsner => Lintner(90.0),        // min spec 300 WK
+            Malt::GladfieldShepherdsDelight => Lintner(0.0),
+            Malt::GladfieldVienna => Lintner(62.0), // min spec 200 WK
+            Malt::GladfieldWheat => Lintner(65.0),
+            Malt::OatHulls => Lintner(0.0),
+            Malt::RiceHulls => Lintner(0.0),
+            Malt::SimpsonsMarisOtterPale => Lintner(65.0),
+            Malt::WeyermannAcidulated => Lintner(0.0),
+            Malt::WeyermannBohemianPilsner => Lintner(110.0),
+            Malt::WeyermannCarafaSpecial2 => Lintner(0.0),
+            Malt::WeyermannCarafaSpecial3 => Lintner(0.0),
+            Malt::WeyermannCarahell => Lintner(0.0),
+            Malt::WeyermannCaramunich2 => Lintner(0.0),
+            Malt::WeyermannCarapils => Lintner(0.0),
+            Malt::WeyermannMelanoidin => Lintner(0.0),
+            Malt::WeyermannMunich1 => Lintner(40.0),
+            Malt::WeyermannMunich2 => Lintner(25.0),
+            Malt::WeyermannPilsner => Lintner(110.0),
+            Malt::WeyermannVienna => Lintner(50.0),
+            Malt::WeyermannWheatPale => Lintner(60.0),
+        }
+    }
+
+    /// How fermentable the extract of this malt is, relative to that of
+    /// a pale base malt mashed at about 66°C.
+    ///
+    /// Crystal malts carry unfermentable dextrins, more so the darker they
+    /// are, and the extract of roasted malts barely ferments at all.
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn fermentability(&self) -> f32 {
+        match *self {
+            Malt::BestMalzVienna => 1.0,
+            Malt::BriessVictory => 0.85,
+            Malt::DingemansSpecialB => 0.55,
+            Malt::FawcettHalcyonPaleAle => 1.0,
+            Malt::FawcettPaleChocolate => 0.1,
+            Malt::FlakedRice => 1.0, // converted by the base malt
+            Malt::GladfieldAle => 1.0,
+            Malt::GladfieldAmericanAle => 1.0,
+            Malt::GladfieldAurora => 1.0,
+            Malt::GladfieldBigOMaltedOats => 0.9, // beta-glucans
+            Malt::GladfieldBiscuit => 0.85,
+            Malt::GladfieldBrown => 0.6,
+            Malt::GladfieldCrystalDark => 0.6,
+            Malt::GladfieldCrystalLight => 0.75,
+            Malt::GladfieldCrystalMedium => 0.7,
+            Malt::GladfieldDarkChocolate => 0.05,
+            Malt::GladfieldGermanPilsner => 1.0,
+            Malt::GladfieldLagerLight => 1.0,
+            Malt::GladfieldMunich => 0.97,
+            Malt::GladfieldPilsner => 1.0,
+            Malt::GladfieldShepherdsDelight => 0.65,
+            Malt::GladfieldVienna => 1.0,
+            Malt::GladfieldWheat => 1.0,
+            Malt::OatHulls => 0.0,
+            Malt::RiceHulls => 0.0,
+            Malt::SimpsonsMarisOtterPale => 1.0,
+            Malt::WeyermannAcidulated => 1.0,
+            Malt::WeyermannBohemianPilsner => 1.0,
+            Malt::WeyermannCarafaSpecial2 => 0.05,
+            Malt::WeyermannCarafaSpecial3 => 0.05,
+            Malt::WeyermannCarahell => 0.75,
+            Malt::WeyermannCaramunich2 => 0.7,
+            Malt::WeyermannCarapils => 0.5, // dextrin malt
+            Malt::WeyermannMelanoidin => 0.9,
+            Malt::WeyermannMunich1 => 0.98,
+            Malt::WeyermannMunich2 => 0.95,
+            Malt::WeyermannPilsner => 1.0,
+            Malt::WeyermannVienna => 1.0,
+            Malt::WeyermannWheatPale => 1.0,
+        }
+    }
+
     /// Percent protein from malt spec
     #[must_use]
     #[allow(clippy::match_same_arms)]
@@ -450,6 +687,7 @@ impl Malt {
             Malt::DingemansSpecialB => None,
             Malt::FawcettHalcyonPaleAle => None,
             Malt::FawcettPaleChocolate => Some(11.6), // less than
+            Malt::FlakedRice => None,                 // unmalted, no foam-positive protein
             Malt::GladfieldAle => None,
             Malt::GladfieldAmericanAle => None,
             Malt::GladfieldAurora => None,
@@ -497,6 +735,7 @@ impl Malt {
             Malt::DingemansSpecialB => None,
             Malt::FawcettHalcyonPaleAle => None,
             Malt::FawcettPaleChocolate => None,
+            Malt::FlakedRice => None,
             Malt::GladfieldAle => Some(38.0), // spec 35 - 41
             Malt::GladfieldAmericanAle => Some(38.0), // spec 35 - 41
             Malt::GladfieldAurora => Some(41.0), // spec 37 - 45
@@ -543,6 +782,7 @@ impl Malt {
             Malt::DingemansSpecialB => Some(Ppm(0.0)), // assume 0
             Malt::FawcettHalcyonPaleAle => None,
             Malt::FawcettPaleChocolate => None,
+            Malt::FlakedRice => Some(Ppm(0.0)),     // assume 0
             Malt::GladfieldAle => Some(Ppm(130.0)), // min spec 120.0
             Malt::GladfieldAmericanAle => Some(Ppm(135.0)), // min spec 120.0
             Malt::GladfieldAurora => Some(Ppm(120.0)), // min spec 120.0
@@ -610,6 +850,7 @@ impl fmt::Display for Malt {
             Malt::DingemansSpecialB => write!(f, "[Dingemans Special B]"),
             Malt::FawcettHalcyonPaleAle => write!(f, "[Thomas Fawcett Halcyon Pale Ale Malt]"),
             Malt::FawcettPaleChocolate => write!(f, "[Thomas Fawcett Pale Chocolate Malt]"),
+            Malt::FlakedRice => write!(f, "[Flaked Rice]"),
             Malt::GladfieldAle => write!(f, "[Gladfield Ale Malt]"),
             Malt::GladfieldAmericanAle => write!(f, "[Gladfield American Ale Malt]"),
             Malt::GladfieldAurora => write!(f, "[Gladfield Aurora Malt]"),
@@ -714,10 +955,45 @@ mod test {
 
     #[test]
     fn test_malt_fan() {
-        use strum::IntoEnumIterator;
-
         for malt in Malt::iter() {
             println!("{}: FAN={}", malt, malt.fan());
         }
     }
+
+    #[test]
+    fn test_maltster() {
+        assert_eq!(Malt::GladfieldPilsner.maltster(), Maltster::Gladfield);
+        assert!(
+            Malt::all_by_maltster(Maltster::Gladfield)
+                .iter()
+                .any(|m| matches!(m, Malt::GladfieldPilsner))
+        );
+        assert!(
+            Malt::all_by_maltster(Maltster::Gladfield)
+                .iter()
+                .all(|m| m.maltster() == Maltster::Gladfield)
+        );
+    }
+
+    #[test]
+    fn test_malt_metric_potential() {
+        let malt = Malt::GladfieldPilsner;
+
+        // 3 kg in 20 L
+        let kg = Kilograms(3.0);
+        let liters = Liters(20.0);
+        let pounds: Pounds = kg.into();
+        let gallons: Gallons = liters.into();
+
+        let metric_points = malt.pkl() * kg.0 / liters.0;
+        let imperial_points = malt.ppg() * pounds.0 / gallons.0;
+        assert!(approx_eq!(
+            f32,
+            metric_points,
+            imperial_points,
+            epsilon = 0.01
+        ));
+
+        assert!(malt.extract_percent() > 75.0 && malt.extract_percent() < 85.0);
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 mod water;
-pub use water::{WaterAdjustment, WaterProfile, WaterVolume};
+pub use water::{WaterAdjustment, WaterBuildError, WaterProfile, WaterReportError, WaterVolume};
 
 mod salt;
 pub use salt::Salt;
@@ -10,13 +10,24 @@ mod acid;
 pub use acid::Acid;
 
 mod malt;
-pub use malt::{Malt, MaltCategory};
+pub use malt::{Malt, MaltCategory, Maltster};
 
 mod sugar;
 pub use sugar::Sugar;
 
+mod extract;
+pub use extract::MaltExtract;
+
+mod fining;
+pub use fining::FiningAgent;
+
+mod nutrient;
+pub use nutrient::YeastNutrient;
+
 mod hops;
-pub use hops::{Hops, HopsUsage};
+pub use hops::{
+    HopDescriptor, HopStorage, Hops, HopsUsage, IbuModel, utilization, whirlpool_factor,
+};
 
 mod yeast;
 pub use yeast::{Flocculation, Gallone, STA1, Strain, Yeast, YeastProvider};
@@ -43,6 +54,22 @@ pub struct SaltDose {
     pub mg: Milligrams,
 }
 
+impl SaltDose {
+    /// Smallest dose a home brewing scale can reasonably weigh
+    pub const MIN_WEIGHABLE: Milligrams = Milligrams(100.0);
+
+    /// Strength of stock solution we suggest for doses too small to weigh
+    pub const STOCK_G_PER_L: f32 = 10.0;
+
+    /// How much of a stock solution, of this salt dissolved in distilled
+    /// water at `stock_g_per_l` grams per liter, gives this dose
+    #[must_use]
+    pub fn as_stock_solution(&self, stock_g_per_l: f32) -> Milliliters {
+        // 1 g/L is 1 mg/mL
+        Milliliters(self.mg.0 / stock_g_per_l)
+    }
+}
+
 /// A concentration of Ions
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct IonConcentration {
@@ -113,6 +140,26 @@ pub struct SugarDose {
     pub weight: Kilograms,
 }
 
+/// A proportion of Malt Extract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExtractProportion {
+    /// Which extract to use
+    pub extract: MaltExtract,
+
+    /// Proportion to use in relation to all other malts and sugars
+    pub proportion: f32,
+}
+
+/// A dose of Malt Extract
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExtractDose {
+    /// Which extract to use
+    pub extract: MaltExtract,
+
+    /// How much
+    pub weight: Kilograms,
+}
+
 /// A proportion of Hops
 ///
 /// where the sum of proportions provides the recipe specified IBUs
@@ -126,6 +173,61 @@ pub struct HopsProportion {
 
     /// How long before the end of the boil to add them
     pub timing: Minutes,
+
+    /// Whether they are first-wort hops, added to the kettle during
+    /// lautering (in which case `timing` is ignored)
+    #[serde(default)]
+    pub first_wort: bool,
+
+    /// A whirlpool or hop stand after the boil, at what temperature and
+    /// for how long, during which these hops keep isomerizing
+    #[serde(default)]
+    pub whirlpool: Option<(Celsius, Minutes)>,
+
+    /// How long the hops have been stored, and how
+    #[serde(default)]
+    pub age: Option<(Days, HopStorage)>,
+
+    /// The AA% printed on the packet for this crop, as a fraction. This is
+    /// used in place of the variety's typical alpha acid.
+    #[serde(default)]
+    pub measured_alpha_acid: Option<f32>,
+}
+
+impl HopsProportion {
+    /// Specify hops by Alpha Acid Units (AAU, also called HBU).
+    ///
+    /// One AAU is one ounce of hops at 1% alpha acid. The proportion is set
+    /// to the implied weight (in grams), so that additions specified this
+    /// way keep their relative bitterness when scaled to the recipe IBU.
+    #[must_use]
+    pub fn from_aau(hops: Hops, aau: f32, timing: Minutes) -> HopsProportion {
+        let ounces = Ounces(aau / (hops.alpha_acid() * 100.0));
+        let grams: Grams = ounces.into();
+        HopsProportion {
+            hops,
+            proportion: grams.0,
+            timing,
+            first_wort: false,
+            whirlpool: None,
+            age: None,
+            measured_alpha_acid: None,
+        }
+    }
+
+    /// AA% of these hops before storage, as a fraction: the crop's if it
+    /// was measured, otherwise the variety's
+    #[must_use]
+    pub fn fresh_alpha_acid(&self) -> f32 {
+        self.measured_alpha_acid
+            .unwrap_or_else(|| self.hops.alpha_acid())
+    }
+
+    /// AA% of these hops after storage, as a fraction
+    #[must_use]
+    pub fn alpha_acid(&self) -> f32 {
+        aged_alpha_acid(self.hops, self.fresh_alpha_acid(), self.age)
+    }
 }
 
 /// A dose of Hops
@@ -139,4 +241,74 @@ pub struct HopsDose {
 
     /// How long before the end of the boil to add them
     pub timing: Minutes,
+
+    /// Whether they are first-wort hops, added to the kettle during
+    /// lautering (in which case `timing` is ignored)
+    #[serde(default)]
+    pub first_wort: bool,
+
+    /// A whirlpool or hop stand after the boil, at what temperature and
+    /// for how long, during which these hops keep isomerizing
+    #[serde(default)]
+    pub whirlpool: Option<(Celsius, Minutes)>,
+
+    /// How long the hops have been stored, and how
+    #[serde(default)]
+    pub age: Option<(Days, HopStorage)>,
+
+    /// The AA% printed on the packet for this crop, as a fraction. This is
+    /// used in place of the variety's typical alpha acid.
+    #[serde(default)]
+    pub measured_alpha_acid: Option<f32>,
+}
+
+impl HopsDose {
+    /// AA% of these hops before storage, as a fraction: the crop's if it
+    /// was measured, otherwise the variety's
+    #[must_use]
+    pub fn fresh_alpha_acid(&self) -> f32 {
+        self.measured_alpha_acid
+            .unwrap_or_else(|| self.hops.alpha_acid())
+    }
+
+    /// AA% of these hops after storage, as a fraction
+    #[must_use]
+    pub fn alpha_acid(&self) -> f32 {
+        aged_alpha_acid(self.hops, self.fresh_alpha_acid(), self.age)
+    }
+}
+
+/// A dry hop addition, made to the fermenter
+///
+/// Dry hops add aroma but no meaningful bitterness.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DryHopAddition {
+    /// Which hops to use
+    pub hops: Hops,
+
+    /// How much, in grams per liter of the batch
+    pub grams_per_liter: f32,
+
+    /// The day of fermentation to add them
+    pub day: Days,
+
+    /// How long to leave them in
+    pub duration: Days,
+}
+
+/// How the hops divide between bittering and aroma
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HopMassSplit {
+    /// Hops boiled for more than 20 minutes, including first wort hops
+    pub bittering: Grams,
+
+    /// Late, whirlpool and dry hops
+    pub aroma: Grams,
+}
+
+fn aged_alpha_acid(hops: Hops, fresh: f32, age: Option<(Days, HopStorage)>) -> f32 {
+    match age {
+        Some((age, storage)) => fresh * hops.alpha_acid_retained(age, storage),
+        None => fresh,
+    }
 }
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum::EnumIter;
+
+/// A yeast nutrient
+///
+/// Nutrients differ a lot in how much FAN they supply per gram.
+/// Organic nutrients (yeast hulls and autolysates) supply much less than
+/// those based on DAP, so the same FAN shortfall needs a bigger dose.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum YeastNutrient {
+    /// Servomyces, a yeast enriched in zinc. It is dosed for its zinc
+    /// and provides very little FAN.
+    Servomyces,
+
+    /// Fermaid O, an organic nutrient (autolyzed yeast)
+    #[default]
+    FermaidO,
+
+    /// Fermaid K, a blend of DAP, yeast hulls and micronutrients
+    FermaidK,
+
+    /// A DAP based blend with added zinc (e.g. Wyeast Beer Nutrient Blend)
+    DapPlusZinc,
+}
+
+impl YeastNutrient {
+    /// FAN provided per gram, in mg. This is the ppm that one gram
+    /// gives in one liter.
+    #[must_use]
+    pub fn fan_per_gram(&self) -> f32 {
+        match *self {
+            YeastNutrient::Servomyces => 10.0,
+            YeastNutrient::FermaidO => 40.0,
+            YeastNutrient::FermaidK => 100.0,
+            YeastNutrient::DapPlusZinc => 150.0,
+        }
+    }
+
+    /// Whether it is dosed for its zinc rather than for FAN
+    #[must_use]
+    pub fn is_dosed_for_zinc(&self) -> bool {
+        *self == YeastNutrient::Servomyces
+    }
+
+    /// Zinc (Zn+2) provided per gram, in mg
+    #[must_use]
+    pub fn zinc_per_gram(&self) -> f32 {
+        match *self {
+            YeastNutrient::Servomyces => 20.0,
+            YeastNutrient::FermaidO | YeastNutrient::FermaidK => 0.0,
+            YeastNutrient::DapPlusZinc => 1.0,
+        }
+    }
+}
+
+impl fmt::Display for YeastNutrient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            YeastNutrient::Servomyces => write!(f, "[Servomyces]"),
+            YeastNutrient::FermaidO => write!(f, "[Fermaid O]"),
+            YeastNutrient::FermaidK => write!(f, "[Fermaid K]"),
+            YeastNutrient::DapPlusZinc => write!(f, "[DAP + Zinc]"),
+        }
+    }
+}
@@ -1,9 +1,13 @@
+// Sugar still has to handle its own deprecated malt extract variants
+#![allow(deprecated)]
+
 use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use strum::{EnumIter, IntoEnumIterator};
 
 /// A type of sugar
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
 pub enum Sugar {
     /// Sucrose, also known as Cane Sugar or Table Sugar
     Sucrose,
@@ -20,7 +24,11 @@ pub enum Sugar {
     /// Maltodextrin
     Maltodextrin,
 
+    /// Lactose, or milk sugar, which brewer's yeast cannot ferment
+    Lactose,
+
     /// Dry Malt Extract
+    #[deprecated(note = "use MaltExtract::LightDme in the recipe's extracts")]
     DME,
 
     /// Brown Sugar
@@ -35,18 +43,80 @@ pub enum Sugar {
     /// Invert Sugar
     InvertSugar,
 
+    /// Brewer's Invert No. 1, a lightly caramelized invert sugar used in
+    /// British ales
+    InvertNo1,
+
+    /// Brewer's Invert No. 2, a golden-brown invert sugar
+    InvertNo2,
+
+    /// Brewer's Invert No. 3, a dark invert sugar used in milds and porters
+    InvertNo3,
+
+    /// Clear Belgian Candi Sugar (rock candi), beet sucrose
+    CandiSugarClear,
+
+    /// Belgian Candi Syrup D-45 (45 SRM, amber)
+    CandiSyrupD45,
+
+    /// Belgian Candi Syrup D-90 (90 SRM)
+    CandiSyrupD90,
+
+    /// Belgian Candi Syrup D-180 (180 SRM, dark)
+    CandiSyrupD180,
+
+    /// Belgian Candi Syrup D-240 (240 SRM, very dark)
+    CandiSyrupD240,
+
     /// Maple Syrup
     MapleSyrup,
 
     /// Light Liquid Malt Extract
+    #[deprecated(note = "use MaltExtract::LightLme in the recipe's extracts")]
     LightLME,
 }
 
 impl Sugar {
+    /// Whether it contains gluten (malt extracts do)
+    #[must_use]
+    pub fn contains_gluten(&self) -> bool {
+        matches!(*self, Sugar::DME | Sugar::LightLME)
+    }
+
+    /// Whether it is free of animal products (honey and lactose are not)
+    #[must_use]
+    pub fn is_vegan(&self) -> bool {
+        !matches!(*self, Sugar::Honey | Sugar::Lactose)
+    }
+
+    /// Whether it is mostly unfermentable, so that it stays in the beer
+    /// as body and sweetness
+    #[must_use]
+    pub fn is_unfermentable(&self) -> bool {
+        self.unfermentability() > self.fermentability()
+    }
+
     /// Points per pound per gallon
+    ///
+    /// Both the fermentable and unfermentable parts add gravity. Earlier
+    /// versions counted only the fermentable part, so sugars with an
+    /// unfermentable part (DME, LME, honey, syrups and maltodextrin) now
+    /// give more gravity, and less of them is needed for the same gravity.
     #[must_use]
     pub fn ppg(&self) -> f32 {
-        self.fermentability() * 46.0
+        (self.fermentability() + self.unfermentability()) * 46.0
+    }
+
+    /// Points per kilogram per liter (the metric form of `ppg()`)
+    #[must_use]
+    pub fn pkl(&self) -> f32 {
+        self.ppg() * PKL_PER_PPG
+    }
+
+    /// Extract potential as a percentage of the weight (sucrose is 100%)
+    #[must_use]
+    pub fn extract_percent(&self) -> f32 {
+        self.ppg() / SUCROSE_PPG * 100.0
     }
 
     /// Color, EBC
@@ -59,6 +129,15 @@ impl Sugar {
             Sugar::Honey => Ebc(5.0),       // guess
             Sugar::MapleSyrup => Ebc(10.0), // guess
             Sugar::LightLME => Ebc(8.0),    // Estimate
+            Sugar::InvertNo1 => Ebc(30.0),  // approx 15 SRM
+            Sugar::InvertNo2 => Ebc(65.0),  // approx 33 SRM
+            Sugar::InvertNo3 => Ebc(120.0), // approx 60 SRM
+            Sugar::CandiSugarClear => Ebc(2.0),
+            // Candi syrups are named for their SRM
+            Sugar::CandiSyrupD45 => Ebc(45.0 * 1.97),
+            Sugar::CandiSyrupD90 => Ebc(90.0 * 1.97),
+            Sugar::CandiSyrupD180 => Ebc(180.0 * 1.97),
+            Sugar::CandiSyrupD240 => Ebc(240.0 * 1.97),
             _ => Ebc(0.0),
         }
     }
@@ -77,8 +156,17 @@ impl Sugar {
             Sugar::Turbinado => 1.00,
             Sugar::Dextrose => 0.91, // brewersfriend and omnicalculator agree 0.91
             Sugar::InvertSugar => 0.91, // as a hydrate
+            Sugar::InvertNo1 => 0.88, // blocks are about 15% water
+            Sugar::InvertNo2 => 0.87,
+            Sugar::InvertNo3 => 0.86,
+            Sugar::CandiSugarClear => 1.00, // it is sucrose
+            Sugar::CandiSyrupD45 => 0.70,   // 32 ppg, about 25% water
+            Sugar::CandiSyrupD90 => 0.70,
+            Sugar::CandiSyrupD180 => 0.70,
+            Sugar::CandiSyrupD240 => 0.70,
             Sugar::BrownSugar => 0.89,
             Sugar::Maltodextrin => 0.03, // 0.86 from somewhere
+            Sugar::Lactose => 0.0,
             Sugar::MapleSyrup => 0.77,
             Sugar::Honey => 0.74, // omnicalculator says 0.78
             Sugar::CornSyrup => 0.69,
@@ -100,25 +188,50 @@ impl Sugar {
             Sugar::Turbinado => 0.0,
             Sugar::Dextrose => 0.0,
             Sugar::InvertSugar => 0.0,
+            Sugar::InvertNo1 => 0.01, // caramelized, a little unfermentable
+            Sugar::InvertNo2 => 0.02,
+            Sugar::InvertNo3 => 0.03,
+            Sugar::CandiSugarClear => 0.0,
+            Sugar::CandiSyrupD45 => 0.01, // mostly fermentable, rest is water
+            Sugar::CandiSyrupD90 => 0.01,
+            Sugar::CandiSyrupD180 => 0.02,
+            Sugar::CandiSyrupD240 => 0.03,
             Sugar::BrownSugar => 0.0,
-            Sugar::Maltodextrin => 0.97,
-            Sugar::MapleSyrup => 0.02, // wild guess, sugars mostly ferm.
-            Sugar::Honey => 0.075,     // honey is 17-20% water, 5-10% unferm
-            Sugar::CornSyrup => 0.05,  // wild guess
-            Sugar::DME => 0.12,        // 20% water
-            Sugar::LightLME => 0.12,   // presume it is like DME
+            Sugar::Maltodextrin => 0.85, // about 40 ppg in all
+            Sugar::Lactose => 0.90,      // about 41 ppg
+            Sugar::MapleSyrup => 0.02,   // wild guess, sugars mostly ferm.
+            Sugar::Honey => 0.075,       // honey is 17-20% water, 5-10% unferm
+            Sugar::CornSyrup => 0.05,    // wild guess
+            Sugar::DME => 0.12,          // 20% water
+            Sugar::LightLME => 0.12,     // presume it is like DME
         }
     }
 
+    /// CO2 produced when primed with this sugar, as a fraction of that
+    /// produced by the same weight of sucrose
+    #[must_use]
+    pub fn co2_yield_fraction(&self) -> f32 {
+        self.fermentability()
+    }
+
     /// Amount for priming
+    ///
+    /// `beer_temp` is the warmest temperature the beer has been at since
+    /// fermentation finished, which sets the CO2 still dissolved in it.
+    ///
+    /// Returns None for unfermentable sugars, which cannot prime.
     // See also "Brew By the Numbers, Zymurgy, Summer 1995
     #[must_use]
     pub fn priming_amount(
         &self,
-        co2_volume: f32,
+        co2_volume: CarbonationVolume,
         beer_volume: Liters,
         beer_temp: Celsius,
-    ) -> Grams {
+    ) -> Option<Grams> {
+        if self.is_unfermentable() {
+            return None;
+        }
+
         let beer_volume: Gallons = beer_volume.into();
         let beer_temp: Fahrenheit = beer_temp.into();
 
@@ -126,9 +239,28 @@ impl Sugar {
         let residual_co2_volume =
             3.0378 - 0.050_062 * beer_temp.0 + 0.000_265_55 * beer_temp.0.powi(2);
 
-        let factor = self.fermentability();
+        let factor = self.co2_yield_fraction();
+
+        Some(Grams(
+            (15.195 * beer_volume.0 * (co2_volume.0 - residual_co2_volume)) / factor,
+        ))
+    }
 
-        Grams((15.195 * beer_volume.0 * (co2_volume - residual_co2_volume)) / factor)
+    /// Amount of every sugar that primes the beer to the same carbonation,
+    /// for comparison. Unfermentable sugars cannot prime, and are left out.
+    #[must_use]
+    pub fn priming_comparison(
+        co2_volume: CarbonationVolume,
+        beer_volume: Liters,
+        peak_temp: Celsius,
+    ) -> Vec<(Sugar, Grams)> {
+        Sugar::iter()
+            .filter_map(|sugar| {
+                sugar
+                    .priming_amount(co2_volume, beer_volume, peak_temp)
+                    .map(|amount| (sugar, amount))
+            })
+            .collect()
     }
 }
 
@@ -140,8 +272,17 @@ impl fmt::Display for Sugar {
             Sugar::Turbinado => write!(f, "[Turbinado]"),
             Sugar::Dextrose => write!(f, "[Dextrose]"),
             Sugar::InvertSugar => write!(f, "[Invert Sugar]"),
+            Sugar::InvertNo1 => write!(f, "[Invert No. 1]"),
+            Sugar::InvertNo2 => write!(f, "[Invert No. 2]"),
+            Sugar::InvertNo3 => write!(f, "[Invert No. 3]"),
+            Sugar::CandiSugarClear => write!(f, "[Clear Candi Sugar]"),
+            Sugar::CandiSyrupD45 => write!(f, "[Candi Syrup D-45]"),
+            Sugar::CandiSyrupD90 => write!(f, "[Candi Syrup D-90]"),
+            Sugar::CandiSyrupD180 => write!(f, "[Candi Syrup D-180]"),
+            Sugar::CandiSyrupD240 => write!(f, "[Candi Syrup D-240]"),
             Sugar::BrownSugar => write!(f, "[Brown Sugar]"),
             Sugar::Maltodextrin => write!(f, "[Maltodextrin]"),
+            Sugar::Lactose => write!(f, "[Lactose]"),
             Sugar::MapleSyrup => write!(f, "[Maple Syrup]"),
             Sugar::Honey => write!(f, "[Honey]"),
             Sugar::CornSyrup => write!(f, "[Corn Syrup]"),
@@ -150,3 +291,62 @@ impl fmt::Display for Sugar {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_priming_comparison() {
+        let table = Sugar::priming_comparison(CarbonationVolume(2.5), Liters(20.0), Celsius(20.0));
+        assert_eq!(
+            table.len(),
+            Sugar::iter().filter(|s| !s.is_unfermentable()).count()
+        );
+        assert!(!table.iter().any(|(sugar, _)| *sugar == Sugar::Lactose));
+
+        let grams = |s: Sugar| table.iter().find(|(sugar, _)| *sugar == s).unwrap().1;
+        assert!(grams(Sugar::DME).0 > grams(Sugar::Dextrose).0);
+        assert!(grams(Sugar::Dextrose).0 > grams(Sugar::Sucrose).0);
+
+        // Beer that got warmer holds less CO2, so needs more sugar
+        let warm =
+            Sugar::Dextrose.priming_amount(CarbonationVolume(2.5), Liters(20.0), Celsius(22.0));
+        let cool =
+            Sugar::Dextrose.priming_amount(CarbonationVolume(2.5), Liters(20.0), Celsius(12.0));
+        assert!(warm.unwrap().0 > cool.unwrap().0);
+
+        // Lactose cannot prime
+        assert!(
+            Sugar::Lactose
+                .priming_amount(CarbonationVolume(2.5), Liters(20.0), Celsius(20.0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_ppg() {
+        use float_cmp::approx_eq;
+
+        // Pure sugars are unchanged, the rest gain their unfermentable part
+        for (sugar, ppg) in [
+            (Sugar::Sucrose, 46.0),
+            (Sugar::Dextrose, 41.86),
+            (Sugar::DME, 36.8),
+            (Sugar::LightLME, 36.8),
+            (Sugar::Honey, 37.49),
+            (Sugar::Maltodextrin, 40.48),
+            (Sugar::Lactose, 41.4),
+        ] {
+            assert!(
+                approx_eq!(f32, sugar.ppg(), ppg, epsilon = 0.001),
+                "{sugar}: {}",
+                sugar.ppg()
+            );
+        }
+
+        // A kilogram of DME in 20 liters is about 15 points
+        let points = Sugar::DME.pkl() * 1.0 / 20.0;
+        assert!(approx_eq!(f32, points, 15.355, epsilon = 0.01), "{points}");
+    }
+}
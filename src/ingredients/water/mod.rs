@@ -2,7 +2,7 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 mod profile;
-pub use profile::WaterProfile;
+pub use profile::{WaterBuildError, WaterProfile, WaterReportError};
 
 /// Tool for adjusting water with salt
 mod water_adjustment;
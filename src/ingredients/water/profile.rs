@@ -3,6 +3,148 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::Add;
 
+/// An error building a water profile from salts
+#[derive(Debug, Clone)]
+pub enum WaterBuildError {
+    /// None of the available salts can be used
+    NoSalts,
+
+    /// Some ions cannot be brought near the target with the available
+    /// salts. Each gives the concentration that was reached instead.
+    Unreachable(Vec<IonConcentration>),
+}
+
+impl fmt::Display for WaterBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSalts => write!(f, "No usable salts are available"),
+            Self::Unreachable(ions) => {
+                write!(f, "Cannot reach the target for")?;
+                for ion in ions {
+                    write!(f, " {} (only {})", ion.ion, ion.ppm)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for WaterBuildError {}
+
+/// An error reading a water report
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaterReportError {
+    /// Required values that the report did not give
+    Missing(Vec<&'static str>),
+}
+
+impl fmt::Display for WaterReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(fields) => {
+                write!(f, "Water report is missing {}", fields.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for WaterReportError {}
+
+/// What a line of a water report gives
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReportField {
+    Calcium,
+    Magnesium,
+    Sodium,
+    Sulfate,
+    SulfateSulfur,
+    Chloride,
+    AlkalinityCaCO3,
+    BicarbonateHCO3,
+    Ph,
+    Ignored,
+}
+
+impl ReportField {
+    /// Recognize the label of a line, case-insensitively
+    fn from_label(label: &str) -> Option<ReportField> {
+        // Units are not part of the name (and "mg/L" is not magnesium)
+        let label = label.to_lowercase().replace("mg/l", "");
+        let words: Vec<&str> = label
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let has = |w: &str| words.contains(&w);
+
+        // Check the more specific labels first, e.g. "Total Hardness,
+        // ppm CaCO3" is not alkalinity and "Calcium Hardness" is not
+        // calcium. Names win over symbols.
+        let field = if has("hardness")
+            || has("tds")
+            || has("dissolved")
+            || has("conductivity")
+            || has("cations")
+            || has("anions")
+            || has("potassium")
+            || has("iron")
+            || has("nitrate")
+        {
+            ReportField::Ignored
+        } else if has("alkalinity") {
+            if has("hco3") {
+                ReportField::BicarbonateHCO3
+            } else {
+                ReportField::AlkalinityCaCO3
+            }
+        } else if has("bicarbonate") {
+            ReportField::BicarbonateHCO3
+        } else if has("carbonate") {
+            ReportField::Ignored
+        } else if has("calcium") {
+            ReportField::Calcium
+        } else if has("magnesium") {
+            ReportField::Magnesium
+        } else if has("sodium") {
+            ReportField::Sodium
+        } else if label.contains("so4-s") || has("sulfur") {
+            ReportField::SulfateSulfur
+        } else if has("sulfate") || has("sulphate") {
+            ReportField::Sulfate
+        } else if has("chloride") {
+            ReportField::Chloride
+        } else if has("ca") {
+            ReportField::Calcium
+        } else if has("mg") {
+            ReportField::Magnesium
+        } else if has("na") {
+            ReportField::Sodium
+        } else if has("so4") {
+            ReportField::Sulfate
+        } else if has("cl") {
+            ReportField::Chloride
+        } else if has("hco3") {
+            ReportField::BicarbonateHCO3
+        } else if has("k") || has("fe") || has("no3") || has("co3") {
+            ReportField::Ignored
+        } else if has("ph") {
+            ReportField::Ph
+        } else {
+            return None;
+        };
+        Some(field)
+    }
+}
+
+/// The ions we build water profiles from
+const BUILD_IONS: [Ion; 6] = [
+    Ion::Calcium,
+    Ion::Magnesium,
+    Ion::Sodium,
+    Ion::Sulfate,
+    Ion::Chloride,
+    Ion::Bicarbonate,
+];
+
 /// Water profile
 #[allow(clippy::doc_markdown)]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -55,6 +197,243 @@ impl WaterProfile {
         ph: Ph(7.5), // guess
     };
 
+    /// Reverse osmosis (RO) water, nearly free of ions
+    pub const REVERSE_OSMOSIS: WaterProfile = WaterProfile {
+        ca: Ppm(0.),
+        mg: Ppm(0.),
+        na: Ppm(0.),
+        so4: Ppm(0.),
+        cl: Ppm(0.),
+        alkalinity_caco3: CaCO3(0.),
+        ph: Ph(7.0),
+    };
+
+    /// Pilsen, very soft
+    pub const PILSEN: WaterProfile = WaterProfile {
+        ca: Ppm(7.),
+        mg: Ppm(2.),
+        na: Ppm(2.),
+        so4: Ppm(5.),
+        cl: Ppm(5.),
+        alkalinity_caco3: CaCO3(12.), // as HCO3 15
+        ph: Ph(7.0),
+    };
+
+    /// Burton-on-Trent, very high in sulfate
+    pub const BURTON: WaterProfile = WaterProfile {
+        ca: Ppm(295.),
+        mg: Ppm(45.),
+        na: Ppm(55.),
+        so4: Ppm(725.),
+        cl: Ppm(25.),
+        alkalinity_caco3: CaCO3(246.), // as HCO3 300
+        ph: Ph(7.8),
+    };
+
+    /// Dublin, high in alkalinity
+    pub const DUBLIN: WaterProfile = WaterProfile {
+        ca: Ppm(118.),
+        mg: Ppm(4.),
+        na: Ppm(12.),
+        so4: Ppm(55.),
+        cl: Ppm(19.),
+        alkalinity_caco3: CaCO3(261.), // as HCO3 319
+        ph: Ph(7.9),
+    };
+
+    /// London
+    pub const LONDON: WaterProfile = WaterProfile {
+        ca: Ppm(52.),
+        mg: Ppm(32.),
+        na: Ppm(86.),
+        so4: Ppm(32.),
+        cl: Ppm(34.),
+        alkalinity_caco3: CaCO3(85.), // as HCO3 104
+        ph: Ph(7.6),
+    };
+
+    /// Dortmund
+    pub const DORTMUND: WaterProfile = WaterProfile {
+        ca: Ppm(225.),
+        mg: Ppm(40.),
+        na: Ppm(60.),
+        so4: Ppm(120.),
+        cl: Ppm(60.),
+        alkalinity_caco3: CaCO3(180.), // as HCO3 220
+        ph: Ph(7.7),
+    };
+
+    /// Vienna
+    pub const VIENNA: WaterProfile = WaterProfile {
+        ca: Ppm(200.),
+        mg: Ppm(60.),
+        na: Ppm(8.),
+        so4: Ppm(125.),
+        cl: Ppm(12.),
+        alkalinity_caco3: CaCO3(98.), // as HCO3 120
+        ph: Ph(7.6),
+    };
+
+    /// Edinburgh
+    pub const EDINBURGH: WaterProfile = WaterProfile {
+        ca: Ppm(125.),
+        mg: Ppm(25.),
+        na: Ppm(55.),
+        so4: Ppm(140.),
+        cl: Ppm(65.),
+        alkalinity_caco3: CaCO3(184.), // as HCO3 225
+        ph: Ph(7.7),
+    };
+
+    /// All of the named water profiles, with their names
+    pub fn all_named() -> impl Iterator<Item = (&'static str, WaterProfile)> {
+        [
+            ("Munich (boiled)", WaterProfile::MUNICH_BOILED),
+            ("Weihenstephan", WaterProfile::WEIHENSTEPHAN),
+            ("Reverse Osmosis", WaterProfile::REVERSE_OSMOSIS),
+            ("Pilsen", WaterProfile::PILSEN),
+            ("Burton-on-Trent", WaterProfile::BURTON),
+            ("Dublin", WaterProfile::DUBLIN),
+            ("London", WaterProfile::LONDON),
+            ("Dortmund", WaterProfile::DORTMUND),
+            ("Vienna", WaterProfile::VIENNA),
+            ("Edinburgh", WaterProfile::EDINBURGH),
+        ]
+        .into_iter()
+    }
+
+    /// Look up a named water profile, ignoring case. The first word of
+    /// the name is enough (e.g. "burton" or "munich").
+    #[must_use]
+    pub fn named(name: &str) -> Option<WaterProfile> {
+        let name = name.trim().to_lowercase();
+        WaterProfile::all_named()
+            .find(|(n, _)| {
+                let n = n.to_lowercase();
+                n == name || n.split([' ', '-']).next() == Some(name.as_str())
+            })
+            .map(|(_, profile)| profile)
+    }
+
+    /// Read a water report, such as the one from Ward Labs, with one
+    /// value per line after its label, e.g. `Calcium, Ca 38.6` or
+    /// `Total Alkalinity (CaCO3) 120`.
+    ///
+    /// Labels are matched ignoring case, by name or by symbol. Bicarbonate
+    /// as HCO3 is converted to alkalinity as CaCO3, and sulfate-sulfur
+    /// (SO4-S) to sulfate. Values reports commonly include but that we do
+    /// not use (hardness, TDS, potassium, ...) are skipped, as are lines we
+    /// do not recognize or that have no number, such as titles and column
+    /// headers. A value below the detection limit, e.g. `< 1`, reads as
+    /// zero. The pH is 7.0 if it is not given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WaterReportError::Missing` if any of calcium, magnesium,
+    /// sodium, sulfate, chloride or alkalinity is not given.
+    pub fn from_report(text: &str) -> Result<WaterProfile, WaterReportError> {
+        let mut ca: Option<Ppm> = None;
+        let mut mg: Option<Ppm> = None;
+        let mut na: Option<Ppm> = None;
+        let mut so4: Option<Ppm> = None;
+        let mut cl: Option<Ppm> = None;
+        let mut alkalinity: Option<CaCO3> = None;
+        let mut ph = Ph(7.0);
+
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let tokens: Vec<&str> = line
+                .split(|c: char| c.is_whitespace() || c == ':' || c == '=')
+                .filter(|t| !t.is_empty())
+                .collect();
+            let number = tokens.iter().enumerate().skip(1).find_map(|(i, t)| {
+                let t = t.trim_end_matches([',', ';']);
+                // Below the detection limit
+                if let Some(limit) = t.strip_prefix('<') {
+                    let limit = if limit.is_empty() {
+                        tokens.get(i + 1).copied().unwrap_or_default()
+                    } else {
+                        limit
+                    };
+                    return limit.parse::<f32>().ok().map(|_| (i, 0.0));
+                }
+                t.parse::<f32>().ok().map(|v| (i, v))
+            });
+            let Some((i, value)) = number else {
+                continue;
+            };
+
+            let Some(field) = ReportField::from_label(&tokens[..i].join(" ")) else {
+                continue;
+            };
+
+            match field {
+                ReportField::Calcium => ca = Some(Ppm(value)),
+                ReportField::Magnesium => mg = Some(Ppm(value)),
+                ReportField::Sodium => na = Some(Ppm(value)),
+                // Sulfate is 3 times the weight of its sulfur
+                ReportField::SulfateSulfur => so4 = Some(Ppm(value * 3.0)),
+                ReportField::Sulfate => so4 = Some(Ppm(value)),
+                ReportField::Chloride => cl = Some(Ppm(value)),
+                ReportField::AlkalinityCaCO3 => alkalinity = Some(CaCO3(value)),
+                ReportField::BicarbonateHCO3 => {
+                    // Prefer a total alkalinity, if given
+                    if alkalinity.is_none() {
+                        alkalinity = Some(HCO3(value).into());
+                    }
+                }
+                ReportField::Ph => ph = Ph(value),
+                ReportField::Ignored => {}
+            }
+        }
+
+        let mut missing: Vec<&'static str> = Vec::new();
+        for (name, present) in [
+            ("calcium", ca.is_some()),
+            ("magnesium", mg.is_some()),
+            ("sodium", na.is_some()),
+            ("sulfate", so4.is_some()),
+            ("chloride", cl.is_some()),
+            ("alkalinity", alkalinity.is_some()),
+        ] {
+            if !present {
+                missing.push(name);
+            }
+        }
+
+        match (ca, mg, na, so4, cl, alkalinity) {
+            (Some(ca), Some(mg), Some(na), Some(so4), Some(cl), Some(alkalinity_caco3)) => {
+                Ok(WaterProfile {
+                    ca,
+                    mg,
+                    na,
+                    so4,
+                    cl,
+                    alkalinity_caco3,
+                    ph,
+                })
+            }
+            _ => Err(WaterReportError::Missing(missing)),
+        }
+    }
+
+    /// Blend with another water profile, by volume.
+    ///
+    /// `self_fraction` is the fraction of the blend that is this water.
+    #[must_use]
+    pub fn blend(&self, other: &WaterProfile, self_fraction: f32) -> WaterProfile {
+        let other_fraction = 1.0 - self_fraction;
+        WaterProfile {
+            ca: self.ca * self_fraction + other.ca * other_fraction,
+            mg: self.mg * self_fraction + other.mg * other_fraction,
+            na: self.na * self_fraction + other.na * other_fraction,
+            so4: self.so4 * self_fraction + other.so4 * other_fraction,
+            cl: self.cl * self_fraction + other.cl * other_fraction,
+            alkalinity_caco3: self.alkalinity_caco3 * self_fraction
+                + other.alkalinity_caco3 * other_fraction,
+            ph: self.ph.blend(other.ph, other_fraction),
+        }
+    }
+
     /// Water hardness on account of Calcium, permanent, in CaCO3 units
     #[must_use]
     pub fn calcium_water_hardness(&self) -> CaCO3 {
@@ -73,12 +452,48 @@ impl WaterProfile {
         CaCO3(self.mg.0 * 4.118)
     }
 
-    /// Total water hardness, permanent, in CaCO3
+    /// Total water hardness, in CaCO3
     #[must_use]
     pub fn water_hardness(&self) -> CaCO3 {
         self.calcium_water_hardness() + self.magnesium_water_hardness()
     }
 
+    /// Temporary (carbonate) hardness, in CaCO3
+    ///
+    /// This is the hardness matched by alkalinity, which precipitates as
+    /// chalk when the water is boiled. It cannot exceed either.
+    #[must_use]
+    pub fn temporary_hardness(&self) -> CaCO3 {
+        let total = self.water_hardness();
+        if self.alkalinity_caco3 < total {
+            self.alkalinity_caco3
+        } else {
+            total
+        }
+    }
+
+    /// Permanent (non-carbonate) hardness, in CaCO3
+    ///
+    /// The hardness left after boiling, balanced by sulfate and chloride.
+    #[must_use]
+    pub fn permanent_hardness(&self) -> CaCO3 {
+        self.water_hardness() - self.temporary_hardness()
+    }
+
+    /// The hardness broken down into temporary and permanent, one per
+    /// line, in ppm as CaCO3 and in German degrees
+    #[must_use]
+    pub fn hardness_report(&self) -> String {
+        let line = |name: &str, hardness: CaCO3| {
+            let dh: Dh = Ppm(hardness.0).into();
+            format!("{name:<20}{:>5.0} ppm as CaCO3  ({dh})\n", hardness.0)
+        };
+        let mut output = line("Total hardness:", self.water_hardness());
+        output.push_str(&line("Temporary hardness:", self.temporary_hardness()));
+        output.push_str(&line("Permanent hardness:", self.permanent_hardness()));
+        output
+    }
+
     /// Effective water hardness as `CaCO3` ppm
     #[must_use]
     pub fn effective_water_hardness_caco3(&self) -> CaCO3 {
@@ -125,9 +540,10 @@ impl WaterProfile {
             let ppm = Ppm(salt_conc.ppm.0 * ion_fraction);
 
             match ion {
-                Ion::Hydrogen => {}  // lowers pH (TBD)
-                Ion::Hydroxide => {} // raises pH (TBD)
-                Ion::Water => {}     // no effect
+                Ion::Hydrogen => {}                           // lowers pH (TBD)
+                Ion::Hydroxide => {}                          // raises pH (TBD)
+                Ion::Water => {}                              // no effect
+                Ion::Lactate | Ion::DihydrogenPhosphate => {} // not tracked
                 Ion::Bicarbonate => {
                     self.alkalinity_caco3 = self.alkalinity_caco3 + HCO3(ppm.0).into();
                 }
@@ -140,50 +556,142 @@ impl WaterProfile {
         }
     }
 
-    /// Add acid
-    /// Not yet implemented, but will not error.
-    pub fn add_acid(&mut self, _acid_conc: AcidConcentration) {
-        /*
-        for ion in acid_conc.acid.ions() {
-            let ion_fraction = acid_conc.acid.ion_fraction(ion);
-            let ppm = Ppm(acid_conc.ppm.0 * ion_fraction);
+    /// The concentration of an ion, with alkalinity as bicarbonate.
+    /// Ions that we do not track are zero.
+    #[must_use]
+    pub fn ion_ppm(&self, ion: Ion) -> Ppm {
+        match ion {
+            Ion::Calcium => self.ca,
+            Ion::Magnesium => self.mg,
+            Ion::Sodium => self.na,
+            Ion::Sulfate => self.so4,
+            Ion::Chloride => self.cl,
+            Ion::Bicarbonate => {
+                let hco3: HCO3 = self.alkalinity_caco3.into();
+                Ppm(hco3.0)
+            }
+            Ion::Hydrogen
+            | Ion::Hydroxide
+            | Ion::Water
+            | Ion::Lactate
+            | Ion::DihydrogenPhosphate => Ppm(0.0),
+        }
+    }
 
-            match ion {
-                Ion::Hydrogen => {
-                    // FIXME
-                },
-                Ion::Hydroxide => {
-                    // FIXME
-                },
-                Ion::Water => { }, // no effect
-                Ion::Bicarbonate => {
-                    // Convert into CaCO3 first
-                    self.alkalinity_caco3 = self.alkalinity_caco3 + (ppm / 1.22);
-                },
-                Ion::Sodium => self.na = self.na + ppm,
-                Ion::Magnesium => self.mg = self.mg + ppm,
-                Ion::Sulfate => self.so4 = self.so4 + ppm,
-                Ion::Chloride => self.cl = self.cl + ppm,
-                Ion::Calcium => self.ca = self.ca + ppm,
+    /// Salts to add to this water so that it becomes the target water.
+    ///
+    /// This solves a non-negative least squares over the ions, so when
+    /// the target cannot be matched exactly the salts come as close as
+    /// they can. Salts with hydroxide are not used, as we do not model
+    /// their effect on alkalinity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WaterBuildError::NoSalts` if none of the available salts
+    /// can be used, and `WaterBuildError::Unreachable` if any ion ends up
+    /// further than 10% (or 5 ppm) from the target.
+    pub fn salt_additions_to(
+        &self,
+        target: &WaterProfile,
+        available: &[Salt],
+    ) -> Result<Vec<SaltConcentration>, WaterBuildError> {
+        let mut salts: Vec<Salt> = Vec::new();
+        for salt in available {
+            if !salt.ions().contains(&Ion::Hydroxide) && !salts.contains(salt) {
+                salts.push(*salt);
+            }
+        }
+        if salts.is_empty() {
+            return Err(WaterBuildError::NoSalts);
+        }
+
+        // ppm of each ion per ppm of each salt
+        let matrix: Vec<[f32; 6]> = salts
+            .iter()
+            .map(|salt| BUILD_IONS.map(|ion| salt.ion_fraction(ion)))
+            .collect();
+        let wanted = BUILD_IONS.map(|ion| target.ion_ppm(ion).0 - self.ion_ppm(ion).0);
+
+        // Coordinate descent, clamping each salt at zero
+        let mut ppms = vec![0.0_f32; salts.len()];
+        let mut residual = wanted;
+        for _ in 0..500 {
+            for (column, ppm) in matrix.iter().zip(ppms.iter_mut()) {
+                let norm: f32 = column.iter().map(|a| a * a).sum();
+                let gradient: f32 = column.iter().zip(&residual).map(|(a, r)| a * r).sum();
+                let new = (*ppm + gradient / norm).max(0.0);
+                for (r, a) in residual.iter_mut().zip(column) {
+                    *r -= a * (new - *ppm);
+                }
+                *ppm = new;
             }
         }
-        */
+
+        let additions: Vec<SaltConcentration> = salts
+            .iter()
+            .zip(&ppms)
+            .filter(|(_, ppm)| **ppm > 0.01)
+            .map(|(salt, ppm)| SaltConcentration {
+                salt: *salt,
+                ppm: Ppm(*ppm),
+            })
+            .collect();
+
+        let mut built = *self;
+        for addition in &additions {
+            built.add_salt(*addition);
+        }
+
+        let unreachable: Vec<IonConcentration> = BUILD_IONS
+            .iter()
+            .filter_map(|ion| {
+                let want = target.ion_ppm(*ion).0;
+                let got = built.ion_ppm(*ion).0;
+                if (got - want).abs() > (want * 0.1).max(5.0) {
+                    Some(IonConcentration {
+                        ion: *ion,
+                        ppm: Ppm(got),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if unreachable.is_empty() {
+            Ok(additions)
+        } else {
+            Err(WaterBuildError::Unreachable(unreachable))
+        }
+    }
+
+    /// Add acid
+    ///
+    /// The acid neutralizes alkalinity (never below zero), and the pH
+    /// drops according to the residual alkalinity relationship.
+    pub fn add_acid(&mut self, acid_conc: AcidConcentration) {
+        // ppm is mg/L of the acid solution
+        let meql = acid_conc.ppm.0 / 1000.0 * acid_conc.acid.meq_per_gram();
+        let neutralized: CaCO3 = AlkMEqL(meql).into();
+        let neutralized = if neutralized > self.alkalinity_caco3 {
+            self.alkalinity_caco3
+        } else {
+            neutralized
+        };
+
+        self.alkalinity_caco3 = self.alkalinity_caco3 - neutralized;
+
+        // pH shift = 0.00168 * RA (as CaCO3)
+        self.ph = Ph(self.ph.0 - 0.00168 * neutralized.0);
     }
 }
 
+/// Adding water profiles mixes equal volumes of each
 impl Add for WaterProfile {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        WaterProfile {
-            ca: self.ca + other.ca,
-            mg: self.mg + other.mg,
-            na: self.na + other.na,
-            so4: self.so4 + other.so4,
-            cl: self.cl + other.cl,
-            alkalinity_caco3: self.alkalinity_caco3 + other.alkalinity_caco3,
-            ph: Ph(10.0_f32.powf(-self.ph.0) + 10.0_f32.powf(-other.ph.0)),
-        }
+        self.blend(&other, 0.5)
     }
 }
 
@@ -197,3 +705,249 @@ impl fmt::Display for WaterProfile {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_water_profile_blend() {
+        let a = WaterProfile::MUNICH_BOILED;
+        let b = WaterProfile::WEIHENSTEPHAN;
+
+        for c in [a.blend(&b, 0.5), a + b] {
+            assert!(approx_eq!(f32, c.ca.0, 13.5, epsilon = 0.001));
+            assert!(approx_eq!(f32, c.mg.0, 13.5, epsilon = 0.001));
+            assert!(approx_eq!(f32, c.na.0, 2.0, epsilon = 0.001));
+            assert!(approx_eq!(f32, c.so4.0, 17.5, epsilon = 0.001));
+            assert!(approx_eq!(f32, c.cl.0, 12.5, epsilon = 0.001));
+            assert!(approx_eq!(
+                f32,
+                c.alkalinity_caco3.0,
+                f32::midpoint(122.0, 46.0 * 1.22),
+                epsilon = 0.001
+            ));
+            assert!(approx_eq!(f32, c.ph.0, 7.5, epsilon = 0.001));
+        }
+
+        // Two pH 7.0 waters stay at pH 7.0
+        let mut x = a;
+        x.ph = Ph(7.0);
+        assert!(approx_eq!(f32, (x + x).ph.0, 7.0, epsilon = 0.001));
+
+        // Unequal volumes weight the hydronium concentration
+        let mut y = a;
+        y.ph = Ph(5.0);
+        let blended = x.blend(&y, 0.9);
+        assert!(blended.ph.0 > 5.0 && blended.ph.0 < 6.1);
+    }
+
+    #[test]
+    fn test_add_acid() {
+        for acid in [
+            Acid::LacticAcid,
+            Acid::PhosphoricAcid,
+            Acid::PhosphoricAcid75,
+        ] {
+            let mut water = WaterProfile::MUNICH_BOILED;
+            water.add_acid(AcidConcentration {
+                acid,
+                ppm: Ppm(200.0),
+            });
+            assert!(water.alkalinity_caco3 < WaterProfile::MUNICH_BOILED.alkalinity_caco3);
+            assert!(
+                water.residual_alkalinity() < WaterProfile::MUNICH_BOILED.residual_alkalinity()
+            );
+            assert!(water.ph < WaterProfile::MUNICH_BOILED.ph);
+        }
+
+        // Never below zero alkalinity
+        let mut water = WaterProfile::MUNICH_BOILED;
+        water.add_acid(AcidConcentration {
+            acid: Acid::LacticAcid,
+            ppm: Ppm(100_000.0),
+        });
+        assert!(approx_eq!(
+            f32,
+            water.alkalinity_caco3.0,
+            0.0,
+            epsilon = 0.001
+        ));
+    }
+
+    #[test]
+    fn test_salt_additions_to() {
+        let salts = [
+            Salt::Gypsum,
+            Salt::CalciumChloride,
+            Salt::Epsom,
+            Salt::TableSalt,
+            Salt::BakingSoda,
+        ];
+
+        // Burton-ish, high in sulfate and bicarbonate
+        let burton = WaterProfile {
+            ca: Ppm(235.0),
+            mg: Ppm(30.0),
+            na: Ppm(62.0),
+            so4: Ppm(620.0),
+            cl: Ppm(60.0),
+            alkalinity_caco3: HCO3(145.0).into(),
+            ph: Ph(7.5),
+        };
+        let near_distilled = WaterProfile {
+            ca: Ppm(1.0),
+            mg: Ppm(0.5),
+            na: Ppm(1.0),
+            so4: Ppm(1.0),
+            cl: Ppm(1.0),
+            alkalinity_caco3: CaCO3(2.0),
+            ph: Ph(7.0),
+        };
+
+        let additions = near_distilled.salt_additions_to(&burton, &salts).unwrap();
+        assert!(additions.iter().any(|a| a.salt == Salt::Gypsum));
+        assert!(additions.iter().all(|a| a.ppm.0 > 0.0));
+
+        let mut built = near_distilled;
+        for addition in &additions {
+            built.add_salt(*addition);
+        }
+        for ion in BUILD_IONS {
+            let want = burton.ion_ppm(ion).0;
+            let got = built.ion_ppm(ion).0;
+            assert!((got - want).abs() < want * 0.1, "{ion}: {got} vs {want}");
+        }
+
+        // Without a chloride salt, chloride cannot be reached
+        match near_distilled.salt_additions_to(&burton, &[Salt::Gypsum, Salt::Epsom]) {
+            Err(WaterBuildError::Unreachable(ions)) => {
+                assert!(ions.iter().any(|i| i.ion == Ion::Chloride));
+            }
+            other => panic!("{other:?}"),
+        }
+
+        assert!(matches!(
+            near_distilled.salt_additions_to(&burton, &[Salt::SlakedLime]),
+            Err(WaterBuildError::NoSalts)
+        ));
+    }
+
+    #[test]
+    fn test_named_water_profiles() {
+        let burton = WaterProfile::named("burton").unwrap();
+        let pilsen = WaterProfile::named("Pilsen").unwrap();
+        assert!(approx_eq!(f32, burton.so4.0, 725.0, epsilon = 0.001));
+        assert!(burton.so4.0 > 50.0 * pilsen.so4.0);
+        assert!(WaterProfile::DUBLIN.residual_alkalinity() > pilsen.residual_alkalinity());
+
+        for (name, profile) in WaterProfile::all_named() {
+            assert!(profile.residual_alkalinity().0.is_finite(), "{name}");
+            assert!(WaterProfile::named(name).is_some(), "{name}");
+        }
+        assert!(WaterProfile::named("Munich").is_some());
+        assert!(WaterProfile::named("Atlantis").is_none());
+    }
+
+    #[test]
+    fn test_water_profile_from_report() {
+        let report = "
+            Ward Laboratories Water Analysis
+            pH  7.8
+            Total Dissolved Solids (TDS) Est, ppm  312
+            Electrical Conductivity, mmho/cm  0.52
+            Cations / Anions, me/L  4.9 / 4.7
+            Sodium, Na  21
+            Potassium, K  2
+            Calcium, Ca  38.6
+            Magnesium, Mg  11
+            Total Hardness, CaCO3  142
+            Nitrate, NO3-N  0.4 (SAFE)
+            Sulfate-Sulfur, SO4-S  4.2
+            Chloride, Cl  17
+            Carbonate, CO3  < 1.0
+            Bicarbonate, HCO3  146
+            Total Alkalinity, CaCO3  120
+        ";
+        let profile = WaterProfile::from_report(report).unwrap();
+        assert!(approx_eq!(f32, profile.ca.0, 38.6, epsilon = 0.001));
+        assert!(approx_eq!(f32, profile.mg.0, 11.0, epsilon = 0.001));
+        assert!(approx_eq!(f32, profile.na.0, 21.0, epsilon = 0.001));
+        assert!(approx_eq!(f32, profile.so4.0, 12.6, epsilon = 0.001));
+        assert!(approx_eq!(f32, profile.cl.0, 17.0, epsilon = 0.001));
+        assert!(approx_eq!(
+            f32,
+            profile.alkalinity_caco3.0,
+            120.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(f32, profile.ph.0, 7.8, epsilon = 0.001));
+
+        // Short labels, bicarbonate only and no pH
+        let report = "CA: 50\nmg 10\nNa 5\nSO4 80\nCl 40\nHCO3 122";
+        let profile = WaterProfile::from_report(report).unwrap();
+        assert!(approx_eq!(f32, profile.ca.0, 50.0, epsilon = 0.001));
+        assert!(approx_eq!(f32, profile.so4.0, 80.0, epsilon = 0.001));
+        assert!(approx_eq!(
+            f32,
+            profile.alkalinity_caco3.0,
+            100.0,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(f32, profile.ph.0, 7.0, epsilon = 0.001));
+
+        // Headers are skipped, and values below the detection limit are zero
+        let report = "Analyte  Result  Units\nCa 50 ppm\nMg 10\nNa <1\nSO4 80\n\
+                      Cl < 5 mg/L\nHCO3 122";
+        let profile = WaterProfile::from_report(report).unwrap();
+        assert!(approx_eq!(f32, profile.na.0, 0.0));
+        assert!(approx_eq!(f32, profile.cl.0, 0.0));
+        assert!(approx_eq!(f32, profile.ca.0, 50.0, epsilon = 0.001));
+
+        assert_eq!(
+            WaterProfile::from_report("Calcium 40\nChloride 10").unwrap_err(),
+            WaterReportError::Missing(vec!["magnesium", "sodium", "sulfate", "alkalinity"])
+        );
+    }
+
+    #[test]
+    fn test_temporary_permanent_hardness() {
+        for profile in [WaterProfile::BURTON, WaterProfile::DUBLIN] {
+            let sum = profile.temporary_hardness() + profile.permanent_hardness();
+            assert!(approx_eq!(
+                f32,
+                sum.0,
+                profile.water_hardness().0,
+                epsilon = 0.01
+            ));
+        }
+
+        // Burton has far more hardness than alkalinity, mostly permanent
+        let burton = WaterProfile::BURTON;
+        assert!(approx_eq!(
+            f32,
+            burton.temporary_hardness().0,
+            246.0,
+            epsilon = 0.01
+        ));
+        assert!(burton.permanent_hardness().0 > 2.0 * burton.temporary_hardness().0);
+
+        // Dublin's hardness is mostly matched by its alkalinity
+        let dublin = WaterProfile::DUBLIN;
+        assert!(dublin.temporary_hardness().0 > 4.0 * dublin.permanent_hardness().0);
+
+        // Soft alkaline water has no permanent hardness
+        let soft = WaterProfile {
+            ca: Ppm(10.0),
+            mg: Ppm(2.0),
+            alkalinity_caco3: CaCO3(100.0),
+            ..WaterProfile::DUBLIN
+        };
+        assert!(soft.permanent_hardness().0 <= 0.0);
+
+        let report = burton.hardness_report();
+        assert_eq!(report.lines().count(), 3);
+        assert!(report.contains("Permanent hardness:"));
+    }
+}
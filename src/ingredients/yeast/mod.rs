@@ -1,3 +1,4 @@
+use crate::style::{Fermentation, Style};
 use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -36,7 +37,7 @@ pub enum Flocculation {
 }
 
 /// A type of Yesat
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, EnumIter, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
 pub enum Yeast {
     SafAleBE134,
@@ -377,6 +378,31 @@ pub enum Yeast {
     WLP920,
     WLP925,
     WLP940,
+
+    /// Wyeast American Ale 1056
+    WY1056,
+
+    /// Wyeast Irish Ale 1084
+    WY1084,
+
+    /// Wyeast American Ale II 1272
+    WY1272,
+
+    /// Wyeast London ESB Ale 1968
+    WY1968,
+
+    /// Wyeast Bohemian Lager 2124
+    // I've heard this is strain 34/70
+    WY2124,
+
+    /// Wyeast Bavarian Lager 2206
+    WY2206,
+
+    /// Wyeast Weihenstephan Weizen 3068
+    WY3068,
+
+    /// Wyeast Belgian Saison 3724
+    WY3724,
     // EC-1118 dry sparkling wine yeast
     // great for bottle conditioning
 
@@ -653,6 +679,14 @@ impl Yeast {
             Self::WLP920 |
             Self::WLP925 |
             Self::WLP940 => YeastProvider::WhiteLabs,
+            Self::WY1056 |
+            Self::WY1084 |
+            Self::WY1272 |
+            Self::WY1968 |
+            Self::WY2124 |
+            Self::WY2206 |
+            Self::WY3068 |
+            Self::WY3724 => YeastProvider::Wyeast,
         }
     }
 
@@ -918,6 +952,14 @@ impl Yeast {
             Self::WLP920 => "WLP920 Old Bavarian Lager Yeast",
             Self::WLP925 => "WLP925 High Pressure Lager Yeast",
             Self::WLP940 => "WLP940 Mexican Lager Yeast",
+            Self::WY1056 => "WY1056 American Ale",
+            Self::WY1084 => "WY1084 Irish Ale",
+            Self::WY1272 => "WY1272 American Ale II",
+            Self::WY1968 => "WY1968 London ESB Ale",
+            Self::WY2124 => "WY2124 Bohemian Lager",
+            Self::WY2206 => "WY2206 Bavarian Lager",
+            Self::WY3068 => "WY3068 Weihenstephan Weizen",
+            Self::WY3724 => "WY3724 Belgian Saison",
         }
     }
 
@@ -927,6 +969,13 @@ impl Yeast {
     /// closer to 20b.  We pick a number in between.
     pub const CELLS_PER_GRAM_DRY: u64 = 13_000_000_000;
 
+    /// Dry yeast is sold in sachets of about this many grams
+    pub const GRAMS_PER_DRY_PACKAGE: f32 = 11.5;
+
+    /// Liquid yeast is sold in vials or smack packs of about this many
+    /// cells when fresh
+    pub const CELLS_PER_LIQUID_PACKAGE: u64 = 100_000_000_000;
+
     /// The minimum recommended temperature to ferment at
     #[must_use]
     #[allow(clippy::match_same_arms)]
@@ -1192,6 +1241,14 @@ impl Yeast {
             Self::WLP920 => (10.0, 13.0),
             Self::WLP925 => (17.0, 20.0),
             Self::WLP940 => (10.0, 13.0),
+            Self::WY1056 => (16.0, 22.0),
+            Self::WY1084 => (17.0, 22.0),
+            Self::WY1272 => (16.0, 22.0),
+            Self::WY1968 => (18.0, 22.0),
+            Self::WY2124 => (9.0, 14.0),
+            Self::WY2206 => (8.0, 14.0),
+            Self::WY3068 => (18.0, 24.0),
+            Self::WY3724 => (21.0, 35.0),
         };
 
         Celsius(min)..Celsius(max)
@@ -1440,6 +1497,14 @@ impl Yeast {
             Self::WLP920 => 0.66..0.73,
             Self::WLP925 => 0.73..0.82,
             Self::WLP940 => 0.70..0.78,
+            Self::WY1056 => 0.73..0.77,
+            Self::WY1084 => 0.71..0.75,
+            Self::WY1272 => 0.72..0.76,
+            Self::WY1968 => 0.67..0.71,
+            Self::WY2124 => 0.69..0.73,
+            Self::WY2206 => 0.73..0.77,
+            Self::WY3068 => 0.73..0.77,
+            Self::WY3724 => 0.76..0.80,
         }
     }
 
@@ -1687,6 +1752,14 @@ impl Yeast {
             Self::WLP920 => (5, 10),
             Self::WLP925 => (5, 10),
             Self::WLP940 => (5, 10),
+            Self::WY1056 => (10, 11),
+            Self::WY1084 => (10, 12),
+            Self::WY1272 => (9, 10),
+            Self::WY1968 => (8, 9),
+            Self::WY2124 => (8, 9),
+            Self::WY2206 => (8, 9),
+            Self::WY3068 => (9, 10),
+            Self::WY3724 => (11, 12),
         };
 
         (min as f32 / 100.0)..(max as f32 / 100.0)
@@ -1936,6 +2009,14 @@ impl Yeast {
             Self::WLP920 => Flocculation::Medium,
             Self::WLP925 => Flocculation::Medium,
             Self::WLP940 => Flocculation::Medium,
+            Self::WY1056 => Flocculation::LowMedium,
+            Self::WY1084 => Flocculation::Medium,
+            Self::WY1272 => Flocculation::High,
+            Self::WY1968 => Flocculation::VeryHigh,
+            Self::WY2124 => Flocculation::MediumHigh,
+            Self::WY2206 => Flocculation::MediumHigh,
+            Self::WY3068 => Flocculation::Low,
+            Self::WY3724 => Flocculation::Low,
         }
     }
 
@@ -1981,6 +2062,15 @@ impl Yeast {
             Self::LalBrewWindsor => true,
             Self::LalBrewWit => true,
 
+            Self::WY1056 => false,
+            Self::WY1084 => false,
+            Self::WY1272 => false,
+            Self::WY1968 => false,
+            Self::WY2124 => false,
+            Self::WY2206 => false,
+            Self::WY3068 => false,
+            Self::WY3724 => false,
+
             // Self::WLP300 => false,
             // Self::WLP351 => false,
             // Self::WLP380 => false,
@@ -2026,15 +2116,87 @@ impl Yeast {
                 | Self::WLP920
                 | Self::WLP925
                 | Self::WLP940
+                | Self::WY2124
+                | Self::WY2206
         )
     }
 
+    /// Is a German wheat beer (hefeweizen) yeast, with the banana and
+    /// clove character those styles call for
+    #[must_use]
+    pub fn is_weizen(&self) -> bool {
+        matches!(
+            self.strain(),
+            Some(
+                Strain::WeihenstephananWeizen66
+                    | Strain::WeihenstephananWeizen68
+                    | Strain::WeihenstephananWeizen175
+                    | Strain::ZumUerigeHefe
+            )
+        ) || matches!(*self, Self::SafAleWB06 | Self::LalBrewMunichClassic)
+    }
+
+    /// Whether the yeast suits the style.
+    ///
+    /// It must be a lager yeast for lager styles and an ale yeast for ale
+    /// styles, have weizen character only for German wheat beers, ferment
+    /// cool enough for a clean lager, not be STA1 positive, and have an
+    /// attenuation near that which the style's gravities imply.
+    #[must_use]
+    pub fn suits_style(&self, style: Style) -> bool {
+        let fermentation_matches = match style.fermentation() {
+            Fermentation::Lager => self.is_lager(),
+            Fermentation::Ale => !self.is_lager(),
+            Fermentation::Either | Fermentation::Wild => true,
+        };
+        if !fermentation_matches {
+            return false;
+        }
+
+        let weizen_style = style.is_a_wheat_beer() && style != Style::AmericanWheatBeer;
+        if self.is_weizen() != weizen_style {
+            return false;
+        }
+
+        if style.lager_style() && self.temp_range().start > Celsius(13.0) {
+            return false;
+        }
+
+        if self.is_sta1_positive() == Some(true) {
+            return false;
+        }
+
+        if let Some(range) = style.attenuation_range() {
+            let attenuation = self.attenuation_range();
+            if attenuation.end < range.start - 0.05 || attenuation.start > range.end + 0.05 {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Yeast pitching rate, g/hL
     #[must_use]
     pub fn pitching_rate_range_ghl(&self) -> Option<Range<f32>> {
         match *self {
+            // Fermentis spec sheets: 50-80 g/hL for ales,
+            // 80-120 g/hL for lagers
+            Self::SafAleBE134 => Some(50.0..80.0),
+            Self::SafAleBE256 => Some(50.0..80.0),
             Self::SafAleBW20 => Some(50.0..80.0),
+            Self::SafAleK97 => Some(50.0..80.0),
+            Self::SafAleS04 => Some(50.0..80.0),
+            Self::SafAleS33 => Some(50.0..80.0),
+            Self::SafAleT58 => Some(50.0..80.0),
+            Self::SafAleUS05 => Some(50.0..80.0),
             Self::SafAleW68 => Some(50.0..80.0),
+            Self::SafAleWB06 => Some(50.0..80.0),
+            Self::SafLagerE30 => Some(80.0..120.0),
+            Self::SafLagerS189 => Some(80.0..120.0),
+            Self::SafLagerS23 => Some(80.0..120.0),
+            Self::SafLagerSH45 => Some(80.0..120.0),
+            Self::SafLagerW3470 => Some(80.0..120.0),
 
             Self::LalBrewAbbaye => Some(50.0..100.0),
             Self::LalBrewAurora => Some(50.0..100.0),
@@ -2073,6 +2235,16 @@ impl Yeast {
         }
     }
 
+    /// Fraction of cells still viable at the given age since manufacture
+    ///
+    /// Liquid yeast loses about 21% per month; dry yeast about 1.5%.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn viability(&self, age: Days) -> f32 {
+        let loss_per_day = if self.is_dry() { 0.0005 } else { 0.007 };
+        (1.0 - loss_per_day * age.0 as f32).max(0.01)
+    }
+
     /// FAN requirements, minimum, if known, for standard gravity of 1.040
     /// Worts generally should have (different people say different things):
     ///     180-200 ppm standard
@@ -2207,6 +2379,13 @@ impl Yeast {
             Self::WLP860 => Some(Strain::Augustiner),
             Self::WLP885 => Some(Strain::Samichlaus),
             Self::WLP940 => Some(Strain::Modelo),
+            Self::WY1056 => Some(Strain::Chico),
+            Self::WY1084 => Some(Strain::Guinness),
+            Self::WY1968 => Some(Strain::Fullers),
+            Self::WY2124 => Some(Strain::WeihenstephananLager),
+            Self::WY2206 => Some(Strain::WeihenstephananLager206),
+            Self::WY3068 => Some(Strain::WeihenstephananWeizen68),
+            Self::WY3724 => Some(Strain::Dupont),
             // Self::WY1316 or 1318 => Some(Strain::Boddingtons),
             // Self::WY1275 => Some(Strain::HenleyOfThames),
             // Self::WY1469 => Some(Strain::TimothyTaylor),
@@ -2242,6 +2421,28 @@ impl Yeast {
             Self::WLP590 => Some(true),
             Self::WLP630 => Some(true),
             Self::WLP830 => Some(false),
+            Self::WY3724 => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Whether the yeast is STA1 positive (diastaticus), able to ferment
+    /// dextrins and starch and so to keep attenuating well past what is
+    /// expected. `None` if unknown.
+    ///
+    /// Where we have not listed it, this falls back to the STA1 gene of
+    /// a Gallone paper strain the yeast is confidently matched to.
+    #[must_use]
+    pub fn is_sta1_positive(&self) -> Option<bool> {
+        if let Some(sta1) = self.sta1() {
+            return Some(sta1);
+        }
+        match self.gallone_data() {
+            Some((gallone, confidence)) if confidence >= 0.8 => match gallone.get_sta1() {
+                STA1::Full => Some(true),
+                STA1::None => Some(false),
+                STA1::Partial => None,
+            },
             _ => None,
         }
     }
@@ -2515,6 +2716,14 @@ impl Yeast {
             Self::WLP920 => None,
             Self::WLP925 => None,
             Self::WLP940 => None,
+            Self::WY1056 => None,
+            Self::WY1084 => None,
+            Self::WY1272 => None,
+            Self::WY1968 => None,
+            Self::WY2124 => None,
+            Self::WY2206 => None,
+            Self::WY3068 => None,
+            Self::WY3724 => None,
         }
     }
 }
@@ -2524,3 +2733,70 @@ impl fmt::Display for Yeast {
         write!(f, "[{}: {:?} {}]", self.provider(), self, self.desc())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_yeast_accessors_are_total() {
+        for yeast in Yeast::iter() {
+            let _ = yeast.provider();
+            let _ = yeast.desc();
+            let _ = yeast.flocculation();
+            let _ = yeast.is_dry();
+            let _ = yeast.fan_requirement();
+            let temps = yeast.temp_range();
+            assert!(temps.start <= temps.end, "{yeast:?}");
+            let attenuation = yeast.attenuation_range();
+            assert!(attenuation.start <= attenuation.end, "{yeast:?}");
+            let tolerance = yeast.alcohol_tolerance_range();
+            assert!(tolerance.start <= tolerance.end, "{yeast:?}");
+            assert!(yeast.pitching_rate_cmlp() > 0);
+            assert!(yeast.viability(Days(10_000)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_wyeast() {
+        let wyeast: Vec<Yeast> = Yeast::iter()
+            .filter(|y| y.provider() == YeastProvider::Wyeast)
+            .collect();
+        assert_eq!(wyeast.len(), 8);
+        for yeast in wyeast {
+            assert!(yeast.desc().starts_with("WY"), "{yeast:?}");
+            assert!(!yeast.is_dry(), "{yeast:?}");
+            let _ = yeast.flocculation();
+            let _ = yeast.strain();
+            assert!(yeast.temp_range().start < yeast.temp_range().end);
+            assert!(yeast.attenuation() > 0.5);
+        }
+        assert_eq!(Yeast::WY1056.strain(), Some(Strain::Chico));
+        assert_eq!(
+            Yeast::WY3068.strain(),
+            Some(Strain::WeihenstephananWeizen68)
+        );
+        assert!(Yeast::WY2206.is_lager());
+    }
+
+    #[test]
+    fn test_is_sta1_positive() {
+        assert_eq!(Yeast::WLP565.is_sta1_positive(), Some(true));
+        assert_eq!(Yeast::WY3724.is_sta1_positive(), Some(true));
+        assert_eq!(Yeast::WLP830.is_sta1_positive(), Some(false));
+        // Sequenced, and without the gene
+        assert_eq!(Yeast::WLP001.is_sta1_positive(), Some(false));
+        assert_eq!(Yeast::SafAleS04.is_sta1_positive(), None);
+    }
+
+    #[test]
+    fn test_viability() {
+        use float_cmp::approx_eq;
+        assert!(approx_eq!(f32, Yeast::WLP001.viability(Days(0)), 1.0));
+        let vial = Yeast::WLP001.viability(Days(90));
+        assert!(vial < 0.4);
+        assert!(Yeast::SafAleUS05.viability(Days(90)) > 0.9);
+        assert!(approx_eq!(f32, Yeast::WLP001.viability(Days(365)), 0.01));
+    }
+}
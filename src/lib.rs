@@ -39,12 +39,17 @@ pub mod prelude {
     pub use crate::units::color::*;
     pub use crate::units::concentration::*;
     pub use crate::units::hardness::*;
+    pub use crate::units::length::*;
+    pub use crate::units::pressure::*;
     pub use crate::units::temperature::*;
     pub use crate::units::time::*;
     pub use crate::units::volume::*;
     pub use crate::units::weight::*;
-    pub use crate::units::{Ibu, Ph};
-    pub use crate::{Brewery, MashRest, Packaging, PhMethod, Process, Recipe, Steps, Warning};
+    pub use crate::units::{Ibu, Lintner, Ph};
+    pub use crate::{
+        Brewery, MashMethod, MashRest, MashStep, Packaging, PhMethod, Process, Recipe, Steps,
+        Warning,
+    };
 }
 
 mod chemistry;
@@ -54,7 +59,7 @@ mod brewery;
 pub use brewery::Brewery;
 
 mod style;
-pub use style::{Conditioning, Fermentation, Style, StyleOrigin};
+pub use style::{Conditioning, Fermentation, Style, StyleOrigin, classify};
 
 mod process;
 pub use process::Process;
@@ -63,13 +68,45 @@ mod packaging;
 pub use packaging::Packaging;
 
 mod mash;
-pub use mash::MashRest;
+pub use mash::{MashRest, MashStep, decoction_volume};
 
+mod carbonation;
+pub use carbonation::keg_pressure;
+
+mod beerjson;
+pub use beerjson::BeerJsonError;
+mod beerxml;
+pub use beerxml::BeerXmlError;
+mod malt_bill;
+pub use malt_bill::MaltBill;
 mod recipe;
-pub use recipe::{PhMethod, Recipe};
+pub use recipe::{MashMethod, PhMethod, Recipe};
+mod recipe_file;
+pub use recipe_file::{RECIPE_FORMAT_VERSION, RecipeFileError};
 
 mod printer;
-pub use printer::{Steps, print_process};
+pub use printer::{ChecklistItem, Stage, Steps, UnitSystem, print_process, print_process_markdown};
+
+mod shopping;
+pub use shopping::ShoppingItem;
+
+mod schedule;
+pub use schedule::FermentStep;
+
+mod timeline;
+pub use timeline::TimelineEvent;
+
+mod cost;
+pub use cost::PriceBook;
+
+mod clarity;
+pub use clarity::Clarity;
+
+mod dietary;
+pub use dietary::DietaryReport;
+
+mod summary;
+pub use summary::ProcessSummary;
 
 mod warnings;
 pub use warnings::Warning;
@@ -80,20 +117,25 @@ use units::temperature::{Celsius, Fahrenheit};
 
 /// Correct a specific gravity reading from a hydrometer when the
 /// sample is not at the calibration temperature of the hydrometer.
-// should be close to the table:
-//  10C    -0.002
-//  21.1C  +0.001
-//  26.7C  +0.003
-//  32.2C  +0.005
-//  37.8C  +0.008
-//  48.9C  +0.013
+///
+/// For a reading of 1.050 on a hydrometer calibrated at 15.6°C (60°F),
+/// this adds:
+///
+/// | Sample | Correction |
+/// |--------|------------|
+/// | 10.0°C | -0.0007    |
+/// | 21.1°C | +0.0011    |
+/// | 26.7°C | +0.0024    |
+/// | 32.2°C | +0.0041    |
+/// | 37.8°C | +0.0061    |
+/// | 48.9°C | +0.0110    |
 #[must_use]
 #[rustfmt::skip]
 pub fn hydrometer_temp_correction(
-    reading_gravity: f32,
+    reading: SpecificGravity,
     reading_temp: Celsius,
     calibration_temp: Celsius,
-) -> f32 {
+) -> SpecificGravity {
     let f: Fahrenheit = reading_temp.into();
     let t = f.0;
     let f: Fahrenheit = calibration_temp.into();
@@ -109,9 +151,30 @@ pub fn hydrometer_temp_correction(
         + 0.000_002_040_526 * cal.powi(2)
         - 0.000_000_002_328_209_4 * cal.powi(3);
 
-    reading_gravity * (num / denom)
+    reading * (num / denom)
 }
 
+/// Correct a specific gravity reading from a hydrometer when the
+/// sample is not at the calibration temperature of the hydrometer.
+#[must_use]
+#[deprecated(note = "use hydrometer_temp_correction, which takes a SpecificGravity")]
+pub fn hydrometer_temp_correction_f32(
+    reading_gravity: f32,
+    reading_temp: Celsius,
+    calibration_temp: Celsius,
+) -> f32 {
+    hydrometer_temp_correction(
+        SpecificGravity(reading_gravity),
+        reading_temp,
+        calibration_temp,
+    )
+    .0
+}
+
+/// A typical wort correction factor for refractometers read on the
+/// Brix scale. Calibrate your own refractometer for better results.
+pub const DEFAULT_WORT_CORRECTION_FACTOR: f32 = 1.04;
+
 /// Refractometer correction for Wort.
 ///
 /// Your refractometer will have 3 scales:
@@ -123,6 +186,11 @@ pub fn hydrometer_temp_correction(
 /// Otherwise if you use the SGsugar scale or convert from Brix, then you
 /// need the correction factor, which might be somewhere close to 1.04.
 ///
+/// The correction factor divides the Brix readings. Earlier versions
+/// divided the specific gravities instead, which gave gravities below
+/// 1.000 for any factor much above 1.0. Results with a factor of 1.0
+/// are unchanged.
+///
 /// Use the pre-fermentation reading as `original_brix`
 /// Use the current reading as `current_brix`
 ///
@@ -141,19 +209,13 @@ pub fn refractometer_correction(
     current_sg: SpecificGravity,
     wort_correction_factor: f32,
 ) -> (SpecificGravity, f32) {
-    let original_sg = original_sg / wort_correction_factor;
-    let current_sg = current_sg / wort_correction_factor;
-
     let original_brix: Brix = original_sg.into();
     let current_brix: Brix = current_sg.into();
 
-    let gravity = SpecificGravity(1.0 + 0.006_276 * current_brix.0 - 0.002_349 * original_brix.0);
-
-    let abw = 0.67062 * original_brix.0 - 0.66091 * current_brix.0;
-
-    let abv = (gravity.0 * abw) / 0.791;
-
-    (gravity, abv)
+    novotny(
+        Brix(original_brix.0 / wort_correction_factor),
+        Brix(current_brix.0 / wort_correction_factor),
+    )
 
     // I'm not using this one since I'll fall in line with the Zymurgy
     // article. But I don't want to forget it.
@@ -188,10 +250,53 @@ pub fn refractometer_correction(
     //       individual refractometers)
 }
 
-fn union_ranges<T: PartialOrd + Copy>(ranges: &[Range<T>]) -> Range<T> {
-    let mut start = ranges[0].start;
-    let mut end = ranges[0].end;
-    for range in ranges.iter().skip(1) {
+/// The current specific gravity and % ABV from actual (corrected)
+/// original and current Brix, per Novotný
+fn novotny(original_brix: Brix, current_brix: Brix) -> (SpecificGravity, f32) {
+    let gravity = SpecificGravity(1.0 + 0.006_276 * current_brix.0 - 0.002_349 * original_brix.0);
+
+    let abw = 0.67062 * original_brix.0 - 0.66091 * current_brix.0;
+
+    let abv = (gravity.0 * abw) / 0.791;
+
+    (gravity, abv)
+}
+
+/// The gravity of fermenting (or fermented) beer from a refractometer
+/// Brix reading, given the original gravity as measured before
+/// fermentation (e.g. by hydrometer).
+///
+/// Alcohol makes a refractometer read high, so the reading cannot be
+/// converted directly. See `refractometer_correction()`.
+#[must_use]
+pub fn refractometer_fg(
+    original_sg: SpecificGravity,
+    current_brix: Brix,
+    wort_correction_factor: f32,
+) -> SpecificGravity {
+    let original_brix: Brix = original_sg.into();
+    novotny(original_brix, Brix(current_brix.0 / wort_correction_factor)).0
+}
+
+/// The Brix reading a refractometer will show when the beer reaches the
+/// given final gravity. This is the inverse of `refractometer_fg()`.
+#[must_use]
+pub fn refractometer_fg_reading(
+    original_sg: SpecificGravity,
+    final_sg: SpecificGravity,
+    wort_correction_factor: f32,
+) -> Brix {
+    let original_brix: Brix = original_sg.into();
+    let actual = (final_sg.0 - 1.0 + 0.002_349 * original_brix.0) / 0.006_276;
+    Brix(actual * wort_correction_factor)
+}
+
+/// The smallest range covering all of `ranges`, or `None` if there are none
+fn union_ranges<T: PartialOrd + Copy>(ranges: &[Range<T>]) -> Option<Range<T>> {
+    let (first, rest) = ranges.split_first()?;
+    let mut start = first.start;
+    let mut end = first.end;
+    for range in rest {
         if range.start < start {
             start = range.start;
         }
@@ -199,5 +304,83 @@ fn union_ranges<T: PartialOrd + Copy>(ranges: &[Range<T>]) -> Range<T> {
             end = range.end;
         }
     }
-    start..end
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hydrometer_temp_correction() {
+        use float_cmp::approx_eq;
+
+        let reading = SpecificGravity(1.050);
+        let calibration = Celsius(15.6);
+        // The documented table
+        for (temp, correction) in [
+            (10.0, -0.0007),
+            (21.1, 0.0011),
+            (26.7, 0.0024),
+            (32.2, 0.0041),
+            (37.8, 0.0061),
+            (48.9, 0.0110),
+        ] {
+            let corrected = hydrometer_temp_correction(reading, Celsius(temp), calibration);
+            assert!(
+                approx_eq!(f32, corrected.0 - reading.0, correction, epsilon = 0.0001),
+                "{temp}: {corrected}"
+            );
+        }
+
+        // No correction at the calibration temperature
+        let corrected = hydrometer_temp_correction(reading, calibration, calibration);
+        assert!(approx_eq!(f32, corrected.0, reading.0, epsilon = 0.00001));
+
+        #[allow(deprecated)]
+        let raw = hydrometer_temp_correction_f32(1.050, Celsius(37.8), calibration);
+        let typed = hydrometer_temp_correction(reading, Celsius(37.8), calibration);
+        assert!(approx_eq!(f32, raw, typed.0, epsilon = 0.00001));
+    }
+
+    #[test]
+    fn test_refractometer_fg() {
+        use float_cmp::approx_eq;
+
+        let og = SpecificGravity(1.056);
+        let fg = SpecificGravity(1.012);
+
+        // The raw reading is well above the real final gravity
+        let reading = refractometer_fg_reading(og, fg, DEFAULT_WORT_CORRECTION_FACTOR);
+        let naive: SpecificGravity = reading.into();
+        assert!(naive.0 > 1.020);
+
+        let corrected = refractometer_fg(og, reading, DEFAULT_WORT_CORRECTION_FACTOR);
+        assert!(approx_eq!(f32, corrected.0, fg.0, epsilon = 0.0005));
+
+        // Agrees with the SGwort correction
+        let (gravity, abv) = refractometer_correction(og, SpecificGravity::from_brix(6.5), 1.0);
+        assert!(approx_eq!(
+            f32,
+            gravity.0,
+            refractometer_fg(og, Brix(6.5), 1.0).0,
+            epsilon = 0.0001
+        ));
+        assert!(abv > 5.0 && abv < 6.5, "{abv}");
+
+        // Pinned results, unchanged from earlier versions with a factor of
+        // 1.0, and with the factor dividing the Brix otherwise
+        let (gravity, abv) = refractometer_correction(og, SpecificGravity(1.025), 1.0);
+        assert!(approx_eq!(f32, gravity.0, 1.007_272, epsilon = 0.000_01));
+        assert!(approx_eq!(f32, abv, 6.4648, epsilon = 0.001));
+        let (gravity, abv) = refractometer_correction(og, SpecificGravity(1.025), 1.04);
+        assert!(approx_eq!(f32, gravity.0, 1.006_992, epsilon = 0.000_01));
+        assert!(approx_eq!(f32, abv, 6.2144, epsilon = 0.001));
+    }
+
+    #[test]
+    fn test_union_ranges() {
+        assert_eq!(union_ranges(&[1..3, 2..5, 0..4]), Some(0..5));
+        assert_eq!(union_ranges::<i32>(&[]), None);
+    }
 }
@@ -0,0 +1,194 @@
+use crate::Warning;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A grain bill under construction
+///
+/// This checks the malts against their recommended maximums and the
+/// fraction of base malt, as `Process::get_warnings()` does with it, but
+/// without needing a full `Process`, so a UI can give feedback as the
+/// grain bill is being edited.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaltBill {
+    malts: Vec<MaltProportion>,
+}
+
+impl MaltBill {
+    /// The minimum fraction of base malt for the mash to convert
+    pub const MIN_BASE_FRACTION: f32 = 0.7;
+
+    /// Create an empty malt bill
+    #[must_use]
+    pub fn new() -> MaltBill {
+        MaltBill::default()
+    }
+
+    /// Add a malt. Adding the same malt again increases its proportion.
+    #[must_use]
+    pub fn add(mut self, malt: Malt, proportion: f32) -> MaltBill {
+        match self.malts.iter_mut().find(|mp| mp.malt == malt) {
+            Some(mp) => mp.proportion += proportion,
+            None => self.malts.push(MaltProportion { malt, proportion }),
+        }
+        self
+    }
+
+    /// The sum of all proportions
+    #[must_use]
+    pub fn total_proportion(&self) -> f32 {
+        self.malts.iter().map(|mp| mp.proportion).sum()
+    }
+
+    /// The percentage of the grain bill that this malt makes up
+    #[must_use]
+    pub fn percent(&self, malt: Malt) -> f32 {
+        let total = self.total_proportion();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.malts
+            .iter()
+            .filter(|mp| mp.malt == malt)
+            .map(|mp| 100.0 * mp.proportion / total)
+            .sum()
+    }
+
+    /// The percentage of the grain bill that each malt makes up
+    #[must_use]
+    pub fn percentages(&self) -> Vec<(Malt, f32)> {
+        self.malts
+            .iter()
+            .map(|mp| (mp.malt, self.percent(mp.malt)))
+            .collect()
+    }
+
+    /// The fraction of the grain bill that is base malt
+    #[must_use]
+    pub fn base_fraction(&self) -> f32 {
+        let base: f32 = self
+            .malts
+            .iter()
+            .filter(|mp| mp.malt.category() == MaltCategory::Base)
+            .map(|mp| mp.malt)
+            .map(|malt| self.percent(malt))
+            .sum();
+        base / 100.0
+    }
+
+    /// The malts with proportions scaled to sum to 100
+    #[must_use]
+    pub fn normalized(&self) -> Vec<MaltProportion> {
+        self.percentages()
+            .into_iter()
+            .map(|(malt, proportion)| MaltProportion { malt, proportion })
+            .collect()
+    }
+
+    /// Check the malts against their recommended maximum usage, and
+    /// check that there is enough base malt for the mash to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `Warning::ExcessMalt` and `Warning::LowDiastaticPower`
+    /// warnings that apply.
+    pub fn validate(&self) -> Result<(), Vec<Warning>> {
+        let mut warnings: Vec<Warning> = Vec::new();
+
+        let fraction_base_malts = self.base_fraction();
+        if fraction_base_malts < Self::MIN_BASE_FRACTION {
+            warnings.push(Warning::LowDiastaticPower {
+                fraction_base_malts,
+            });
+        }
+
+        for (malt, percent) in self.percentages() {
+            if percent > malt.recommended_max_percent() {
+                warnings.push(Warning::ExcessMalt {
+                    malt,
+                    percent,
+                    max_recommended_percent: malt.recommended_max_percent(),
+                });
+            }
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+}
+
+impl From<Vec<MaltProportion>> for MaltBill {
+    fn from(malts: Vec<MaltProportion>) -> MaltBill {
+        malts
+            .into_iter()
+            .fold(MaltBill::new(), |bill, mp| bill.add(mp.malt, mp.proportion))
+    }
+}
+
+impl From<MaltBill> for Vec<MaltProportion> {
+    fn from(bill: MaltBill) -> Vec<MaltProportion> {
+        bill.malts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_malt_bill_excess_roast() {
+        let bill = MaltBill::new()
+            .add(Malt::GladfieldPilsner, 7.0)
+            .add(Malt::GladfieldDarkChocolate, 3.0);
+
+        assert!(approx_eq!(
+            f32,
+            bill.percent(Malt::GladfieldDarkChocolate),
+            30.0,
+            epsilon = 0.001
+        ));
+        let total: f32 = bill.normalized().iter().map(|mp| mp.proportion).sum();
+        assert!(approx_eq!(f32, total, 100.0, epsilon = 0.001));
+
+        let warnings = bill.validate().unwrap_err();
+        assert!(warnings.iter().any(|w| matches!(
+            w,
+            Warning::ExcessMalt {
+                malt: Malt::GladfieldDarkChocolate,
+                ..
+            }
+        )));
+
+        let bill = MaltBill::new()
+            .add(Malt::GladfieldPilsner, 95.0)
+            .add(Malt::GladfieldDarkChocolate, 5.0);
+        assert!(bill.validate().is_ok());
+    }
+
+    #[test]
+    fn test_malt_bill_process_warnings() {
+        let mut process = crate::process::test::test_process();
+        let bill = MaltBill::new()
+            .add(Malt::GladfieldPilsner, 6.0)
+            .add(Malt::GladfieldDarkChocolate, 4.0);
+        process.recipe.malts = bill.clone().into();
+
+        // The process gives the same malt bill warnings
+        let expected = bill.validate().unwrap_err().len();
+        let found = process
+            .get_warnings()
+            .iter()
+            .filter(|w| {
+                matches!(
+                    w,
+                    Warning::ExcessMalt { .. } | Warning::LowDiastaticPower { .. }
+                )
+            })
+            .count();
+        assert_eq!(found, expected);
+        assert_eq!(expected, 2);
+    }
+}
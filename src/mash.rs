@@ -1,6 +1,9 @@
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// The volume taken up by grain in the mash, in liters per kilogram
+pub(crate) const GRAIN_DISPLACEMENT: f32 = 0.67;
+
 /// A rest in a Mash
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MashRest {
@@ -11,39 +14,76 @@ pub struct MashRest {
     pub duration: Minutes,
 }
 
+/// How the mash is raised to the next rest temperature
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MashStep {
+    /// Add hot water
+    Infusion {
+        /// The rest temperature reached
+        target_temperature: Celsius,
+
+        /// Volume of infusion water
+        volume: Liters,
+    },
+
+    /// Pull thick mash, boil it, and return it
+    Decoction {
+        /// The rest temperature reached
+        target_temperature: Celsius,
+
+        /// Fraction of the mash to pull
+        fraction_pulled: f32,
+
+        /// Volume of mash to pull
+        volume: Liters,
+    },
+}
+
 /// Calculate an initial infusion
+///
+/// `tun_thermal_mass` is the water equivalent of the mash tun, which
+/// starts at the grain temperature. Pass zero if it is preheated.
 #[must_use]
 pub(crate) fn strike_water_temp(
     strike_volume: Liters,
     grain_weight: Kilograms,
     grain_temp: Celsius,
     target_temp: Celsius,
+    tun_thermal_mass: Liters,
 ) -> Celsius {
     /* INFUSION:
      *
-     * Tw = (0.2 / R) * (T2 - T1) + T2
+     * Tw = ((0.2 G + M) / W) * (T2 - T1) + T2
      *
      * Tw = strike water temp in F
-     * R = water-to-grist ratio (quarts per pound)
-     * T1 = initial temp of the grain in F
+     * G = total grain weight (lbs)
+     * M = water equivalent of the mash tun (quarts)
+     * W = strike water (quarts)
+     * T1 = initial temp of the grain (and mash tun) in F
      * T2 = target mash temp in F
      */
 
     let strike_volume: Quarts = strike_volume.into();
     let grain_weight: Pounds = grain_weight.into();
+    let tun_thermal_mass: Quarts = tun_thermal_mass.into();
     let grain_temp: Fahrenheit = grain_temp.into();
     let target_temp: Fahrenheit = target_temp.into();
 
-    let r = strike_volume.0 / grain_weight.0;
+    let w = strike_volume.0;
+    let g = grain_weight.0;
+    let m = tun_thermal_mass.0;
     let t2 = target_temp.0;
     let t1 = grain_temp.0;
 
-    let strike_water_temp_f = (0.2 / r) * (t2 - t1) + t2;
+    let strike_water_temp_f = ((0.2 * g + m) / w) * (t2 - t1) + t2;
 
     Fahrenheit(strike_water_temp_f).into()
 }
 
 /// Calculate a mash infusion
+///
+/// The mash tun (of water equivalent `tun_thermal_mass`) is heated along
+/// with the mash.
 #[must_use]
 pub(crate) fn mash_infusion(
     grain_weight: Kilograms,
@@ -51,13 +91,15 @@ pub(crate) fn mash_infusion(
     start_temp: Celsius,
     target_temp: Celsius,
     infusion_temp: Celsius,
+    tun_thermal_mass: Liters,
 ) -> Liters {
     /* MASH INFUSION
      *
-     * Wa = (T2 - T1) * (0.2 G + Wm) / (Tw - T2)
+     * Wa = (T2 - T1) * (0.2 G + Wm + M) / (Tw - T2)
      *
      * Wa = Volume of (near boiling) water added (in quarts)
      * Wm = Total volume of water in the mash (in quarts)
+     * M = water equivalent of the mash tun (quarts)
      * T1 = initial temp of mash (F)
      * T2 = target temp of mash (F)
      * Tw = actual temp of infusion water (F)
@@ -66,6 +108,7 @@ pub(crate) fn mash_infusion(
 
     let grain_weight: Pounds = grain_weight.into();
     let current_water: Quarts = current_water.into();
+    let tun_thermal_mass: Quarts = tun_thermal_mass.into();
     let start_temp: Fahrenheit = start_temp.into();
     let target_temp: Fahrenheit = target_temp.into();
     let infusion_temp: Fahrenheit = infusion_temp.into();
@@ -74,9 +117,10 @@ pub(crate) fn mash_infusion(
     let t2 = target_temp.0;
     let g = grain_weight.0;
     let wm = current_water.0;
+    let m = tun_thermal_mass.0;
     let tw = infusion_temp.0;
 
-    let wa = Quarts((t2 - t1) * (0.2 * g + wm) / (tw - t2));
+    let wa = Quarts((t2 - t1) * (0.2 * g + wm + m) / (tw - t2));
 
     wa.into()
 }
@@ -89,15 +133,17 @@ pub(crate) fn reverse_mash_infusion(
     start_temp: Celsius,
     target_temp: Celsius,
     infusion_temp: Celsius,
+    tun_thermal_mass: Liters,
 ) -> Liters {
     /* MASH INFUSION
      *
-     * W1 = ( W2 * (T2 - Tinf) + 0.2 G * (T2 - T1) ) / (T1 - Tinf)
+     * W1 = ( W2 * (T2 - Tinf) + (0.2 G + M) * (T2 - T1) ) / (T1 - Tinf)
      *
      * W1 = Volume of mash before infusion (quarts)
      * W2 = Volume of mash after infusion (quarts)
      * Tinf = actual temp of infusion water (F)
      * G = total grain weight (lbs)
+     * M = water equivalent of the mash tun (quarts)
      * T1 = initial temp of mash (F)
      * T2 = target temp of mash (F)
      */
@@ -107,14 +153,86 @@ pub(crate) fn reverse_mash_infusion(
     let grain_weight: Pounds = grain_weight.into();
     let final_water: Quarts = final_water.into();
     let infusion_temp: Fahrenheit = infusion_temp.into();
+    let tun_thermal_mass: Quarts = tun_thermal_mass.into();
 
     let w2 = final_water.0;
     let t1 = start_temp.0;
     let t2 = target_temp.0;
     let g = grain_weight.0;
+    let m = tun_thermal_mass.0;
     let tinf = infusion_temp.0;
 
-    let w1 = Quarts(((w2 * (t2 - tinf)) + (0.2 * g * (t2 - t1))) / (t1 - tinf));
+    let w1 = Quarts(((w2 * (t2 - tinf)) + ((0.2 * g + m) * (t2 - t1))) / (t1 - tinf));
     let infusion_volume = final_water - w1;
     infusion_volume.into()
 }
+
+/// Calculate the volume of mash to pull, boil, and return to raise the
+/// mash from the current temperature to the target.
+///
+/// This assumes the decoction has the same makeup as the whole mash, and
+/// that grain takes up about 0.67 L/kg. Pull a little more as thick mash
+/// if the decoction cools on its way back.
+///
+/// Returns None if the target is not above the current temperature, or
+/// is not below boiling, as no decoction can reach it.
+#[must_use]
+pub fn decoction_volume(
+    grain_weight: Kilograms,
+    current_water: Liters,
+    current_temp: Celsius,
+    target_temp: Celsius,
+    boil_temp: Celsius,
+) -> Option<Liters> {
+    if target_temp <= current_temp || target_temp >= boil_temp {
+        return None;
+    }
+
+    let fraction = (target_temp.0 - current_temp.0) / (boil_temp.0 - current_temp.0);
+    Some(Liters(
+        fraction * mash_volume(grain_weight, current_water).0,
+    ))
+}
+
+/// The volume of a mash of this grain and water
+#[must_use]
+pub(crate) fn mash_volume(grain_weight: Kilograms, water: Liters) -> Liters {
+    Liters(water.0 + GRAIN_DISPLACEMENT * grain_weight.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_single_decoction() {
+        let grain = Kilograms(5.0);
+        let water = Liters(15.0);
+        let volume =
+            decoction_volume(grain, water, Celsius(62.0), Celsius(72.0), Celsius(100.0)).unwrap();
+
+        // About a quarter of the 18.35 L mash
+        assert!(approx_eq!(
+            f32,
+            volume.0,
+            18.35 * 10.0 / 38.0,
+            epsilon = 0.001
+        ));
+        assert!(volume.0 > 4.0 && volume.0 < 5.5);
+    }
+
+    #[test]
+    fn test_decoction_out_of_range() {
+        let grain = Kilograms(5.0);
+        let water = Liters(15.0);
+        let boil = Celsius(100.0);
+
+        // Cooler or equal targets, and targets at or above boiling, cannot
+        // be reached by a decoction
+        assert!(decoction_volume(grain, water, Celsius(62.0), Celsius(55.0), boil).is_none());
+        assert!(decoction_volume(grain, water, Celsius(62.0), Celsius(62.0), boil).is_none());
+        assert!(decoction_volume(grain, water, Celsius(62.0), Celsius(100.0), boil).is_none());
+        assert!(decoction_volume(grain, water, Celsius(62.0), Celsius(105.0), boil).is_none());
+    }
+}
@@ -1,7 +1,91 @@
+use crate::beerxml::trim_brackets;
 use crate::prelude::*;
-use crate::{Packaging, Process};
+use crate::{
+    DEFAULT_WORT_CORRECTION_FACTOR, Packaging, Process, Warning, refractometer_fg_reading,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
+use std::ops::Range;
+
+/// Which units the printers show amounts in
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UnitSystem {
+    /// Celsius, liters, kilograms and grams
+    #[default]
+    Metric,
+
+    /// Fahrenheit, gallons, pounds and ounces
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Format a temperature
+    #[must_use]
+    pub fn temperature(self, temperature: Celsius) -> String {
+        match self {
+            UnitSystem::Metric => temperature.to_string(),
+            UnitSystem::Imperial => Fahrenheit::from(temperature).to_string(),
+        }
+    }
+
+    /// Format a change in temperature (given in Celsius degrees)
+    #[must_use]
+    pub fn temperature_change(self, degrees: f32) -> String {
+        match self {
+            UnitSystem::Metric => format!("{degrees:.0} °C"),
+            UnitSystem::Imperial => format!("{:.1} °F", degrees * 1.8),
+        }
+    }
+
+    /// Format a volume of wort, water or beer
+    #[must_use]
+    pub fn volume(self, volume: Liters) -> String {
+        match self {
+            UnitSystem::Metric => volume.to_string(),
+            UnitSystem::Imperial => Gallons::from(volume).to_string(),
+        }
+    }
+
+    /// Format a small volume, such as a bottle
+    #[must_use]
+    pub fn small_volume(self, volume: Liters) -> String {
+        match self {
+            UnitSystem::Metric => volume.to_string(),
+            UnitSystem::Imperial => FluidOunces::from(volume).to_string(),
+        }
+    }
+
+    /// Format a weight of grain or ice
+    #[must_use]
+    pub fn weight(self, weight: Kilograms) -> String {
+        match self {
+            UnitSystem::Metric => weight.to_string(),
+            UnitSystem::Imperial => Pounds::from(weight).to_string(),
+        }
+    }
+
+    /// Format a small weight, such as of hops or sugar
+    #[must_use]
+    pub fn small_weight(self, weight: Grams) -> String {
+        match self {
+            UnitSystem::Metric => weight.to_string(),
+            UnitSystem::Imperial => Ounces::from(weight).to_string(),
+        }
+    }
+
+    /// Format a mash thickness (given in liters per kilogram)
+    #[must_use]
+    pub fn mash_thickness(self, liters_per_kg: f32) -> String {
+        match self {
+            UnitSystem::Metric => format!("{liters_per_kg:.1}L/kg"),
+            UnitSystem::Imperial => {
+                let quarts: Quarts = Liters(liters_per_kg).into();
+                let pounds: Pounds = Kilograms(1.0).into();
+                format!("{:.2}qt/lb", quarts.0 / pounds.0)
+            }
+        }
+    }
+}
 
 /// Instructions for each major step of the process.
 ///
@@ -37,7 +121,82 @@ pub struct Steps {
     pub package: Vec<String>,
 }
 
+/// A stage of the process, as tagged on a [`ChecklistItem`]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Stage {
+    /// Header
+    Header,
+
+    /// Acquisition
+    Acquire,
+
+    /// Preparation
+    Prep,
+
+    /// Mashing
+    Mash,
+
+    /// The boil
+    Boil,
+
+    /// Chilling the wort
+    Chill,
+
+    /// Moving to fermenter and pitching the yeast
+    Pitch,
+
+    /// Fermenting
+    Ferment,
+
+    /// Packaging
+    Package,
+}
+
+/// A single numbered step, for use in a checklist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// The stage this step belongs to
+    pub stage: Stage,
+
+    /// The step number, counted across all stages starting at 1
+    pub number: usize,
+
+    /// The instruction
+    pub text: String,
+
+    /// Whether the step has been done
+    pub done: bool,
+}
+
 impl Steps {
+    /// Flatten all stages into a single numbered checklist
+    #[must_use]
+    pub fn to_checklist(&self) -> Vec<ChecklistItem> {
+        let stages: [(Stage, &Vec<String>); 9] = [
+            (Stage::Header, &self.header),
+            (Stage::Acquire, &self.acquire),
+            (Stage::Prep, &self.prep),
+            (Stage::Mash, &self.mash),
+            (Stage::Boil, &self.boil),
+            (Stage::Chill, &self.chill),
+            (Stage::Pitch, &self.pitch),
+            (Stage::Ferment, &self.ferment),
+            (Stage::Package, &self.package),
+        ];
+
+        stages
+            .iter()
+            .flat_map(|(stage, steps)| steps.iter().map(move |text| (*stage, text)))
+            .enumerate()
+            .map(|(i, (stage, text))| ChecklistItem {
+                stage,
+                number: i + 1,
+                text: text.clone(),
+                done: false,
+            })
+            .collect()
+    }
+
     /// Merge other steps in (at the end)
     pub fn merge(&mut self, other: &Steps) {
         self.header.extend_from_slice(&other.header);
@@ -88,134 +247,543 @@ impl Steps {
 /// If you pass in custom_steps, they will be the first steps in each
 /// section, followed by the standard steps.
 #[must_use]
-#[allow(clippy::similar_names)]
-#[allow(clippy::too_many_lines)]
 pub fn print_process(
     process: &Process,
     custom_steps: Option<Steps>,
     char_width: Option<usize>,
+    units: UnitSystem,
 ) -> String {
     let char_width = char_width.unwrap_or(78);
 
-    let mut steps = custom_steps.unwrap_or_default();
-    steps.prefix("CUSTOM");
+    let mut steps = process_steps(process, custom_steps, units);
 
-    if let Some(more_steps) = &process.recipe.custom_steps {
-        let mut more_steps = more_steps.clone();
-        more_steps.prefix(&process.recipe.name);
-        steps.merge(&more_steps);
+    // -- header ------------
+
+    let old_header = steps.header;
+    steps.header = Vec::new();
+
+    steps.header.push(format!(
+        "Recipe for {recipe_name}\n(generated by the beermaker)\n",
+        recipe_name = &process.recipe.name,
+    ));
+
+    let mut specification_text = String::from("Specification:\n");
+    for (label, value, range) in specification(process, units) {
+        let label = format!("{label}:");
+        let _ = write!(specification_text, "  {label:<18}{value}");
+        if let Some(range) = range {
+            let _ = write!(specification_text, "   [style: {range}]");
+        }
+        specification_text.push('\n');
     }
+    steps.header.push(specification_text);
 
-    // Local variables for format! substitutions
+    steps.header.push(format!(
+        "Volume History:\n{}",
+        &indent(&volume_history(process, units), 2, char_width)
+    ));
+
+    steps.header.push(format!(
+        "Grain Bill:\n{}",
+        &indent(&grain_bill(process, units), 2, char_width)
+    ));
+
+    steps.header.extend(old_header);
+
+    // -------------------------------
+
+    let mut output = String::new();
+
+    for block in &steps.header {
+        output.push_str(&indent(block, 0, char_width));
+        output.push('\n');
+    }
+
+    header(&mut output, "ACQUIRE", char_width);
+    for (i, block) in steps.acquire.iter().enumerate() {
+        label(&mut output, "ACQUIRE", i + 1, block, char_width);
+        output.push('\n');
+    }
+    header(&mut output, "PREP", char_width);
+    for (i, block) in steps.prep.iter().enumerate() {
+        label(&mut output, "PREP", i + 1, block, char_width);
+        output.push('\n');
+    }
+    if !steps.mash.is_empty() {
+        header(&mut output, "MASH", char_width);
+        for (i, block) in steps.mash.iter().enumerate() {
+            label(&mut output, "MASH", i + 1, block, char_width);
+            output.push('\n');
+        }
+    }
+    header(&mut output, "BOIL", char_width);
+    for (i, block) in steps.boil.iter().enumerate() {
+        label(&mut output, "BOIL", i + 1, block, char_width);
+        output.push('\n');
+    }
+    header(&mut output, "CHILL", char_width);
+    for (i, block) in steps.chill.iter().enumerate() {
+        label(&mut output, "CHILL", i + 1, block, char_width);
+        output.push('\n');
+    }
+    header(&mut output, "PITCH", char_width);
+    for (i, block) in steps.pitch.iter().enumerate() {
+        label(&mut output, "PITCH", i + 1, block, char_width);
+        output.push('\n');
+    }
+    header(&mut output, "FERMENT", char_width);
+    for (i, block) in steps.ferment.iter().enumerate() {
+        label(&mut output, "FERMENT", i + 1, block, char_width);
+        output.push('\n');
+    }
+    header(&mut output, "PACKAGE", char_width);
+    for (i, block) in steps.package.iter().enumerate() {
+        label(&mut output, "PACKAGE", i + 1, block, char_width);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Print a process in full detail as Markdown.
+///
+/// Sections become `##` headers, the specification, volume history and
+/// grain bill become tables, and steps become ordered lists.
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub fn print_process_markdown(
+    process: &Process,
+    custom_steps: Option<Steps>,
+    units: UnitSystem,
+) -> String {
+    let mut steps = process_steps(process, custom_steps, units);
+    let custom_header = std::mem::take(&mut steps.header);
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "# Recipe for {}
+
+_(generated by the beermaker)_
+",
+        process.recipe.name
+    );
+
+    output.push_str(
+        "## Specification
+
+",
+    );
+    let rows: Vec<Vec<String>> = specification(process, units)
+        .into_iter()
+        .map(|(label, value, range)| vec![label.to_owned(), value, range.unwrap_or_default()])
+        .collect();
+    output.push_str(&markdown_table(&["", "Value", "Style"], "llr", &rows));
+
+    output.push_str(
+        "
+## Volume History
+
+",
+    );
+    let rows: Vec<Vec<String>> = process
+        .volume_history()
+        .into_iter()
+        .map(|(label, change, total, description)| {
+            let sign = if change.0 < 0.0 { '-' } else { '+' };
+            vec![
+                label.trim_end_matches(':').to_owned(),
+                format!("{sign}{}", units.volume(Liters(change.0.abs()))),
+                units.volume(total),
+                description.to_owned(),
+            ]
+        })
+        .collect();
+    output.push_str(&markdown_table(
+        &["Step", "Change", "Total", "Volume"],
+        "lrrl",
+        &rows,
+    ));
+
+    output.push_str(
+        "
+## Grain Bill
+
+",
+    );
+    let total = process.fermentables_weight().0;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for dose in process.malt_doses() {
+        let percent = 100.0 * dose.weight.0 / total;
+        rows.push(vec![
+            trim_brackets(&dose.malt.to_string()).to_owned(),
+            units.weight(dose.weight),
+            format!("{percent:.1}"),
+        ]);
+    }
+    for dose in process.sugar_doses() {
+        let percent = 100.0 * dose.weight.0 / total;
+        rows.push(vec![
+            trim_brackets(&dose.sugar.to_string()).to_owned(),
+            units.weight(dose.weight),
+            format!("{percent:.1}"),
+        ]);
+    }
+    for dose in process.extract_doses() {
+        let percent = 100.0 * dose.weight.0 / total;
+        rows.push(vec![
+            trim_brackets(&dose.extract.to_string()).to_owned(),
+            units.weight(dose.weight),
+            format!("{percent:.1}"),
+        ]);
+    }
+    rows.push(vec![
+        "**Total**".to_owned(),
+        units.weight(process.fermentables_weight()),
+        "100.0".to_owned(),
+    ]);
+    output.push_str(&markdown_table(&["Malt", "Weight", "%"], "lrr", &rows));
+
+    for block in &custom_header {
+        let _ = write!(output, "\n{block}\n");
+    }
 
+    let sections: [(&str, &Vec<String>); 8] = [
+        ("ACQUIRE", &steps.acquire),
+        ("PREP", &steps.prep),
+        ("MASH", &steps.mash),
+        ("BOIL", &steps.boil),
+        ("CHILL", &steps.chill),
+        ("PITCH", &steps.pitch),
+        ("FERMENT", &steps.ferment),
+        ("PACKAGE", &steps.package),
+    ];
+    for (name, blocks) in sections {
+        if blocks.is_empty() {
+            continue;
+        }
+        let _ = writeln!(output, "\n## {name}\n");
+        for (i, block) in blocks.iter().enumerate() {
+            let prefix = format!("{}. ", i + 1);
+            let continuation = " ".repeat(prefix.len());
+            let lines: Vec<&str> = block.trim_end().lines().collect();
+            for (j, line) in lines.iter().enumerate() {
+                // Keep line breaks within a paragraph with a hard break
+                let hard_break = match lines.get(j + 1) {
+                    Some(next) if !line.is_empty() && !next.is_empty() => "  ",
+                    _ => "",
+                };
+                if j == 0 {
+                    let _ = writeln!(output, "{prefix}{line}{hard_break}");
+                } else if line.is_empty() {
+                    output.push('\n');
+                } else {
+                    let _ = writeln!(output, "{continuation}{line}{hard_break}");
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// The specification rows: label, value, and the style range if any
+fn specification(
+    process: &Process,
+    units: UnitSystem,
+) -> Vec<(&'static str, String, Option<String>)> {
     let style = process.recipe.style;
-    let time_until_done = process.time_until_done();
-    let batch_size = process.batch_size;
-    let fermenter = process.fermenter_volume();
     let mash_ph = process
         .mash_ph()
         .iter()
         .map(|s| format!("{s}"))
         .collect::<Vec<_>>()
         .join(", ");
-    let mut mash_thicknesses = String::new();
-    for f in process.mash_thicknesses() {
-        let _ = write!(mash_thicknesses, "{f:.1}L/kg, ");
-    }
-    let wort_fan = process.wort_fan();
-    let yeast_amount = if let Some(g) = process.yeast_grams() {
-        format!("{g}")
+    let mash_thicknesses = process
+        .mash_thicknesses()
+        .iter()
+        .map(|f| units.mash_thickness(*f))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (mash_ph, mash_thicknesses) = if process.has_mash() {
+        (mash_ph, mash_thicknesses)
     } else {
-        format!("{} billion cells", process.yeast_cells() / 1_000_000_000)
+        ("n/a".to_owned(), "n/a".to_owned())
     };
-    let yeast_max_temperature = process.recipe.yeast.temp_range().end;
-    let ibu = process.bitterness();
-    let min_ibu = process.recipe.style.bitterness_range().start.0;
-    let max_ibu = process.recipe.style.bitterness_range().end.0;
-    let color = process.color();
-    let min_color = process.recipe.style.color_range().start.0;
-    let max_color = process.recipe.style.color_range().end.0;
     let og = process.recipe.original_gravity;
-    let min_og = process.recipe.style.original_gravity_range().start.0;
-    let max_og = process.recipe.style.original_gravity_range().end.0;
     let fg = process.final_gravity();
-    let min_fg = process.recipe.style.final_gravity_range().start.0;
-    let max_fg = process.recipe.style.final_gravity_range().end.0;
-    let abv = process.abv();
-    let min_abv = process.recipe.style.abv_range().start;
-    let max_abv = process.recipe.style.abv_range().end;
-    let ice_weight = process.brewery.ice_weight();
-    let ice_bath_volume = process.brewery.chilled_water_volume();
-    let total_water_volume = process.total_water();
+    let ibu = style_range(style.bitterness_range(), |v| format!("{:.1}", v.0));
+    let color = style_range(style.color_range(), |v| format!("{:.1}", v.0));
+    let og_range = style_range(style.original_gravity_range(), |v| format!("{:.3}", v.0));
+    let fg_range = style_range(style.final_gravity_range(), |v| format!("{:.3}", v.0));
+    let abv = style_range(style.abv_range(), |v| format!("{v:.1}"));
+    let bottles_nz = (process.product_volume().0 / 0.330).floor();
+    let bottles_eu = (process.product_volume().0 / 0.500).floor();
+    let bottles_large = (process.product_volume().0 / 0.750).floor();
+
+    vec![
+        ("Style", style.to_string(), None),
+        ("Batch size", units.volume(process.batch_size), None),
+        ("Days", process.time_until_done().to_string(), None),
+        ("Fermenter", units.volume(process.fermenter_volume()), None),
+        (
+            "Ferment Temp",
+            units.temperature(process.recipe.ferment_temperature),
+            None,
+        ),
+        ("Mash pH", mash_ph, None),
+        ("Mash Thicknesses", mash_thicknesses, None),
+        ("Wort FAN", process.wort_fan().to_string(), None),
+        ("Yeast Pitch", yeast_amount(process, units), None),
+        ("Bitterness", process.bitterness().to_string(), Some(ibu)),
+        ("Color", process.color().to_string(), Some(color)),
+        (
+            "Original Gravity",
+            format!("{og} ({og:#} points)"),
+            Some(og_range),
+        ),
+        (
+            "Final Gravity",
+            format!("{fg} ({fg:#} points)"),
+            Some(fg_range),
+        ),
+        ("ABV", process.abv().to_string(), Some(abv)),
+        (
+            "Head Retention",
+            process.head_retention_note().to_owned(),
+            None,
+        ),
+        ("Malt Depth", process.malt_depth_note().to_owned(), None),
+        (
+            "Bottles",
+            format!("{bottles_nz}x330ml {bottles_eu}x500ml {bottles_large}x750ml"),
+            None,
+        ),
+    ]
+}
+
+/// A style range for display, or "n/a" if the style publishes none
+fn style_range<T>(range: Option<Range<T>>, show: impl Fn(&T) -> String) -> String {
+    range.map_or_else(
+        || "n/a".to_owned(),
+        |range| format!("{} .. {}", show(&range.start), show(&range.end)),
+    )
+}
+
+/// How much yeast to pitch, in grams if known, else in cells
+fn yeast_amount(process: &Process, units: UnitSystem) -> String {
+    if let Some(g) = process.yeast_grams(None) {
+        units.small_weight(g)
+    } else {
+        format!(
+            "{} billion cells",
+            process.yeast_cells(None) / 1_000_000_000
+        )
+    }
+}
+
+/// The volume history, one line per step
+fn volume_history(process: &Process, units: UnitSystem) -> String {
+    let mut output: String = String::new();
+    for (label, change, total, description) in process.volume_history() {
+        let sign = if change.0 < 0.0 { '-' } else { '+' };
+        let change = units.volume(Liters(change.0.abs()));
+        let total = units.volume(total);
+        let _ = writeln!(
+            output,
+            "{label:<15}{sign}{change}     = {total}  {description}"
+        );
+    }
+    output
+}
+
+/// The grain bill, one line per fermentable, and the totals
+fn grain_bill(process: &Process, units: UnitSystem) -> String {
+    let mut output: String = String::new();
+    let total = process.fermentables_weight().0;
+
+    let rows = process
+        .malt_doses()
+        .into_iter()
+        .map(|dose| (dose.weight, dose.malt.to_string()))
+        .chain(
+            process
+                .sugar_doses()
+                .into_iter()
+                .map(|dose| (dose.weight, dose.sugar.to_string())),
+        )
+        .chain(
+            process
+                .extract_doses()
+                .into_iter()
+                .map(|dose| (dose.weight, dose.extract.to_string())),
+        );
+    for (weight, name) in rows {
+        let percent = 100.0 * weight.0 / total;
+        let weight = units.weight(weight);
+        let _ = writeln!(output, "{weight}, {percent:04.1}%  {name}");
+    }
+
+    let _ = writeln!(
+        output,
+        "Total Malt Weight = {}",
+        units.weight(process.grain_weight())
+    );
+    let _ = write!(
+        output,
+        "Total Fermentable Weight = {}",
+        units.weight(process.fermentables_weight())
+    );
+
+    output
+}
+
+/// The ingredients to acquire
+fn ingredient_list(process: &Process, units: UnitSystem) -> String {
+    let mut output: String = String::new();
+    let _ = writeln!(
+        output,
+        "Total Water: {}",
+        units.volume(process.total_water())
+    );
+    for dose in &process.malt_doses() {
+        let _ = writeln!(output, "{} of {}", units.weight(dose.weight), dose.malt);
+    }
+    for dose in &process.sugar_doses() {
+        let _ = writeln!(output, "{} of {}", units.weight(dose.weight), dose.sugar);
+    }
+    for dose in &process.extract_doses() {
+        let _ = writeln!(output, "{} of {}", units.weight(dose.weight), dose.extract);
+    }
+    for dose in &process.hops_doses() {
+        let _ = writeln!(
+            output,
+            "{} of {}",
+            units.small_weight(dose.weight),
+            dose.hops
+        );
+    }
+    for addition in &process.recipe.dry_hops {
+        let _ = writeln!(
+            output,
+            "{} of {} (dry hop)",
+            units.small_weight(process.dry_hop_weight(addition)),
+            addition.hops
+        );
+    }
+    if process.recipe.fining_desired {
+        let agent = process.recipe.fining_agent;
+        let _ = writeln!(
+            output,
+            "{:.1} {} of {agent}",
+            process.fining_amount(),
+            agent.unit()
+        );
+    }
+    let _ = writeln!(output, "Yeast: {}", process.recipe.yeast);
+    let nutrient = process.yeast_nutrient_amount();
+    if nutrient > Grams(0.0) {
+        let _ = writeln!(
+            output,
+            "Yeast Nutrient: {} of {}",
+            units.small_weight(nutrient),
+            process.brewery.yeast_nutrient
+        );
+    }
+    output
+}
+
+/// The hops additions during the boil
+fn hops_additions(process: &Process, units: UnitSystem) -> String {
+    let mut output: String = String::new();
+    for (dose, ibu) in process
+        .ibu_breakdown()
+        .iter()
+        .filter(|(d, _)| !d.first_wort)
+    {
+        let after = Minutes(process.recipe.boil_length.0.saturating_sub(dose.timing.0));
+        let _ = writeln!(
+            output,
+            "\n{} from start {} from end:  Add {} of {} ({})",
+            after,
+            dose.timing,
+            units.small_weight(dose.weight),
+            dose.hops,
+            ibu
+        );
+    }
+    output
+}
+
+/// A Markdown table. Columns marked `r` in `align` are right aligned.
+fn markdown_table(headers: &[&str], align: &str, rows: &[Vec<String>]) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "| {} |", headers.join(" | "));
+    for a in align.chars() {
+        output.push_str(if a == 'r' { "|---:" } else { "|:---" });
+    }
+    output.push_str("|\n");
+    for row in rows {
+        let _ = writeln!(output, "| {} |", row.join(" | "));
+    }
+    output
+}
+
+/// Build the steps of a process, without the header blocks, for any of
+/// the printers.
+#[allow(clippy::similar_names)]
+#[allow(clippy::too_many_lines)]
+fn process_steps(process: &Process, custom_steps: Option<Steps>, units: UnitSystem) -> Steps {
+    let mut steps = custom_steps.unwrap_or_default();
+    steps.prefix("CUSTOM");
+
+    if let Some(more_steps) = &process.recipe.custom_steps {
+        let mut more_steps = more_steps.clone();
+        more_steps.prefix(&process.recipe.name);
+        steps.merge(&more_steps);
+    }
+
+    // Local variables for format! substitutions
+
+    let yeast_amount = yeast_amount(process, units);
+    let yeast_max_temperature = units.temperature(process.recipe.yeast.temp_range().end);
+    let og = process.recipe.original_gravity;
+    let fg = process.final_gravity();
+    let ice_weight = units.weight(process.brewery.ice_weight());
+    let ice_bath_volume = units.volume(process.brewery.chilled_water_volume());
+    let total_water_volume = units.volume(process.total_water());
     let water_doses = process.water_doses();
     let adjusted_water_profile = process.adjusted_water_profile();
-    let ingredient_list = process.ingredient_list_string();
-    let strike_volume = process.strike_volume();
-    let strike_temp = process.strike_temperature();
-    let infusion_temp = process.brewery.infusion_temperature;
-    let sparge_volume = process.sparge_volume();
+    let ingredient_list = ingredient_list(process, units);
+    let strike_volume = units.volume(process.strike_volume());
+    let infusion_temp = units.temperature(process.brewery.infusion_temperature);
+    let sparge_volume = units.volume(process.sparge_volume());
+    let expect_gravity = |gravity: Option<SpecificGravity>| {
+        gravity.map_or(String::new(), |g| format!(" Expect a gravity of about {g}"))
+    };
+    let expect_first_runnings = expect_gravity(process.first_runnings_gravity());
+    let expect_sparge_runnings = expect_gravity(process.sparge_runnings_gravity());
     let pre_boil_gravity = process.pre_boil_gravity();
     let boil_minutes = process.recipe.boil_length;
-    let hops_additions = process.hops_additions_string();
-    let whirlfloc = if process.recipe.fining_desired {
-        process.whirlfloc_amount()
-    } else {
-        0.0
-    };
+    let boiling_point = units.temperature(process.boiling_point());
+    let recommended_boil_minutes = process.recommended_boil_length();
+    let hops_additions = hops_additions(process, units);
+    let fining_agent = process.recipe.fining_agent;
+    let fining = format!(
+        "{:.1} {} of {fining_agent}",
+        process.fining_amount(),
+        fining_agent.unit()
+    );
     let yeast_nutrient = process.yeast_nutrient_amount();
     let zn = process.zinc_needed();
-    let post_boil_pre_loss_volume = process.post_boil_pre_loss_volume();
+    let post_boil_pre_loss_volume = units.volume(process.post_boil_pre_loss_volume());
     let partial_boil_dilution = process.partial_boil_dilution();
-    let fermentation_temp = process.recipe.ferment_temperature;
+    let fermentation_temp = units.temperature(process.recipe.ferment_temperature);
     let yeast = process.recipe.yeast;
     let fermentation_time = process.recipe.fermentation_time();
     let lagering_time = process.recipe.style.recommended_conditioning_time();
-    let diacetyl_rest_temp = process.recipe.diacetyl_rest_temperature();
+    let diacetyl_rest_temp = units.temperature(process.recipe.diacetyl_rest_temperature());
     let post_ferment_dilution = process.post_fermentation_dilution();
-    let bottles_nz = (process.product_volume().0 / 0.330).floor();
-    let bottles_eu = (process.product_volume().0 / 0.500).floor();
-    let bottles_large = (process.product_volume().0 / 0.750).floor();
-
-    // -- header ------------
-
-    let old_header = steps.header;
-    steps.header = Vec::new();
-
-    steps.header.push(format!(
-        "Recipe for {recipe_name}\n(generated by the beermaker)\n",
-        recipe_name = &process.recipe.name,
-    ));
-
-    steps.header.push(format!(
-        "Specification:\n  \
-             Style:            {style}\n  \
-             Batch size:       {batch_size}\n  \
-             Days:             {time_until_done}\n  \
-             Fermenter:        {fermenter}\n  \
-             Ferment Temp:     {fermentation_temp}\n  \
-             Mash pH:          {mash_ph}\n  \
-             Mash Thicknesses: {mash_thicknesses}\n  \
-             Wort FAN:         {wort_fan}\n  \
-             Yeast Pitch:      {yeast_amount}\n  \
-             Bitterness:       {ibu}   [style: {min_ibu:.1} .. {max_ibu:.1}]\n  \
-             Color:            {color}    [style: {min_color:.1} .. {max_color:.1}]\n  \
-             Original Gravity: {og} [style: {min_og:.3} .. {max_og:.3}]\n  \
-             Final Gravity:    {fg} [style: {min_fg:.3} .. {max_fg:.3}]\n  \
-             ABV:              {abv}       [style: {min_abv:.1} .. {max_abv:.1}]\n  \
-             Bottles:          {bottles_nz}x330ml {bottles_eu}x500ml {bottles_large}x750ml\n",
-    ));
-
-    steps.header.push(format!(
-        "Volume History:\n{}",
-        &indent(&process.volume_history_string(), 2, char_width)
-    ));
-
-    steps.header.push(format!(
-        "Grain Bill:\n{}",
-        &indent(&process.grain_bill_string(), 2, char_width)
-    ));
-
-    steps.header.extend(old_header);
 
     // -- acquire ------------
 
@@ -225,7 +793,7 @@ pub fn print_process(
 
     let mut bits: String = "Acquire sanitizer, iodine (optional), yeast nutrient".to_string();
     if process.recipe.fining_desired {
-        bits.push_str(", whirlfloc, fining agent");
+        let _ = write!(bits, ", {fining_agent}");
     }
     steps.acquire.push(bits);
 
@@ -242,6 +810,13 @@ pub fn print_process(
              recipe."
     ));
 
+    for hops in process.aroma_hops_to_buy_fresh() {
+        steps.acquire.push(format!(
+            "Use fresh {hops} hops. They keep poorly, and your late additions \
+             rely on their aroma."
+        ));
+    }
+
     steps
         .acquire
         .push(format!("You will need {yeast_amount} of {yeast}."));
@@ -298,97 +873,202 @@ pub fn print_process(
 
     // -- mash ------------
 
-    steps.mash.push(format!(
-        "Fill the mash tun with {strike_volume} of {strike_temp} treated source water."
-    ));
+    if let Some(strike_temp) = process.strike_temperature() {
+        let strike_temp = units.temperature(strike_temp);
+        if process.brewery.preheat_mash_tun {
+            steps.mash.push(format!(
+                "Preheat the mash tun with hot water to {}, then drain it.",
+                units.temperature(process.recipe.mash_rests[0].target_temperature)
+            ));
+        }
 
-    if process.recipe.mash_rests.len() > 1 {
-        steps
-            .mash
-            .push("Since we are doing a step mash, boil water for step additions.".to_string());
-    }
+        steps.mash.push(format!(
+            "Fill the mash tun with {strike_volume} of {strike_temp} treated source water."
+        ));
 
-    steps
-        .mash
-        .push("Add the mashable malts (see grain bill).".to_string());
+        if !process.brewery.preheat_mash_tun
+            && process.brewery.mash_tun_thermal_mass.0 > 0.1 * process.strike_volume().0
+            && let Some(preheated_temp) = process.strike_temperature_with(true)
+        {
+            steps.mash.push(format!(
+                "This strike temperature allows for heating the mash tun from room \
+                 temperature. Consider preheating it instead, in which case strike at {}.",
+                units.temperature(preheated_temp)
+            ));
+        }
 
-    steps.mash.push("Start the timer.".to_string());
+        if process.recipe.mash_rests.len() > 1 {
+            steps
+                .mash
+                .push("Since we are doing a step mash, boil water for step additions.".to_string());
+        }
 
-    steps
-        .mash
-        .push("Stir well, then take the temperature and record it.".to_string());
+        steps
+            .mash
+            .push("Add the mashable malts (see grain bill).".to_string());
 
-    steps
-        .mash
-        .push("Remove a sample and let it cool.".to_string());
+        steps.mash.push("Start the timer.".to_string());
 
-    steps.mash.push(
-        "Start to prepare sparge water. If you boil it now \
-                     it might be cooled enough when sparge happens."
-            .to_string(),
-    );
+        steps
+            .mash
+            .push("Stir well, then take the temperature and record it.".to_string());
+
+        steps
+            .mash
+            .push("Remove a sample and let it cool.".to_string());
+
+        if !process.is_full_volume_mash() {
+            steps.mash.push(
+                "Start to prepare sparge water. If you boil it now \
+                             it might be cooled enough when sparge happens."
+                    .to_string(),
+            );
+
+            for dose in process.sparge_acid_doses() {
+                steps.mash.push(format!(
+                    "Acidify the sparge water to about pH 5.8 by adding {} of {}. \
+                     This keeps tannins from being extracted late in the lauter.",
+                    dose.mg, dose.acid
+                ));
+            }
+        }
 
-    let infusions = process.mash_infusions();
-    for (i, rest) in process.recipe.mash_rests.iter().enumerate() {
-        let temp = rest.target_temperature;
-        let dur = rest.duration;
+        let mash_steps = process.mash_steps();
+        for (i, rest) in process.recipe.mash_rests.iter().enumerate() {
+            let temp = units.temperature(rest.target_temperature);
+            let dur = rest.duration;
+
+            if i > 0 {
+                match mash_steps[i - 1] {
+                    MashStep::Infusion { volume, .. } => {
+                        let volume = units.volume(volume);
+                        steps
+                            .mash
+                            .push(format!("Infuse {volume} of {infusion_temp} into the mash."));
+                    }
+                    MashStep::Decoction { volume, .. } if volume > Liters(0.0) => {
+                        let volume = units.volume(volume);
+                        steps.mash.push(format!(
+                            "Decoction: Pull {volume} of thick mash into a pot, bring it \
+                             slowly to a boil stirring constantly, boil it for 15 minutes, \
+                             and return it to the mash."
+                        ));
+                    }
+                    MashStep::Decoction { .. } => {}
+                }
+            }
 
-        if i == 0 {
             steps
                 .mash
                 .push(format!("Hold the mash at {temp} for {dur}."));
-        } else {
-            steps.mash.push(format!(
-                "Infuse {} of {infusion_temp} into the mash.",
-                infusions[i - 1]
-            ));
+        }
 
-            steps
-                .mash
-                .push(format!("Hold the mash at {temp} for {dur}."));
+        if let Some(note) = process.mash_body_note() {
+            steps.mash.push(note.to_string());
         }
-    }
 
-    steps.mash.push(
-        "You can exit the mash early if an iodine test indicates there \
-         is no more starch."
-            .to_string(),
-    );
+        if process.recipe.decoction && mash_steps.is_empty() {
+            steps.mash.push(
+                "Decoction: Pull about a third of the thick mash into a pot, bring \
+                 it slowly to a boil stirring constantly, boil for 15 minutes, and \
+                 return it to the mash."
+                    .to_string(),
+            );
+        }
 
-    steps
-        .mash
-        .push("Take the pH of the sample that cooled and record it.".to_string());
+        let conversion = process.estimated_conversion_time();
+        steps.mash.push(format!(
+            "Conversion should take about {conversion}, and may take up to {}. You can \
+             exit the mash early if an iodine test indicates there is no more starch.",
+            Minutes(conversion.0 * 3 / 2)
+        ));
 
-    steps.mash.push(
-        "Vorlauf: Lauter out of the mash tun into a jug, pouring back into the \
-               mash tun, until the wort runs clear. The clearer the wort the better. \
-               Solids that end up in the fermenter usually taste bad."
-            .to_string(),
-    );
+        steps
+            .mash
+            .push("Take the pH of the sample that cooled and record it.".to_string());
 
-    steps
-        .mash
-        .push("Lauter the first runnings into the boil kettle.".to_string());
+        for dose in process.hops_doses().iter().filter(|d| d.first_wort) {
+            steps.mash.push(format!(
+                "Add {} of {} to the kettle as you lauter (first-wort hops).",
+                units.small_weight(dose.weight),
+                dose.hops
+            ));
+        }
 
-    steps.mash.push(format!(
-        "Batch sparge the mash with {sparge_volume} water of about 77°C, stir it well."
-    ));
+        if process.recipe.mash_method == MashMethod::Biab {
+            steps.mash.push(format!(
+                "Lift the bag out of the mash and let it drain into the kettle, \
+                 then squeeze it gently to recover more wort.{expect_first_runnings}"
+            ));
+        } else if process.recipe.mash_method == MashMethod::NoSparge {
+            steps.mash.push(
+                "Vorlauf: Lauter out of the mash tun into a jug, pouring back into the \
+                       mash tun, until the wort runs clear. The clearer the wort the better. \
+                       Solids that end up in the fermenter usually taste bad."
+                    .to_string(),
+            );
 
-    steps.mash.push(
-        "Vorlauf again: Lauter out of the mash tun into a jug, pouring back into \
-               the mash tun, until the wort runs clear. The clearer the wort the better. \
-               Solids that end up in the fermenter usually taste bad."
-            .to_string(),
-    );
+            steps.mash.push(format!(
+                "Lauter all of the wort into the boil kettle. Do not sparge.\
+                 {expect_first_runnings}"
+            ));
+        } else {
+            steps.mash.push(
+                "Vorlauf: Lauter out of the mash tun into a jug, pouring back into the \
+                       mash tun, until the wort runs clear. The clearer the wort the better. \
+                       Solids that end up in the fermenter usually taste bad."
+                    .to_string(),
+            );
 
-    steps
-        .mash
-        .push("Lauter the second runnings into the boil kettle.".to_string());
+            steps.mash.push(format!(
+                "Lauter the first runnings into the boil kettle.{expect_first_runnings}"
+            ));
+
+            steps.mash.push(format!(
+                "Batch sparge the mash with {sparge_volume} water of about {}, stir it well.",
+                units.temperature(Celsius(77.0))
+            ));
+
+            steps.mash.push(
+                "Vorlauf again: Lauter out of the mash tun into a jug, pouring back into \
+                       the mash tun, until the wort runs clear. The clearer the wort the better. \
+                       Solids that end up in the fermenter usually taste bad."
+                    .to_string(),
+            );
+
+            steps.mash.push(format!(
+                "Lauter the second runnings into the boil kettle.{expect_sparge_runnings}"
+            ));
+        }
 
-    steps.mash.push("Discard the grains.".to_string());
+        steps.mash.push("Discard the grains.".to_string());
+    }
 
     // -- boil ------------
 
+    if !process.has_mash() {
+        steps.boil.push(format!(
+            "Fill the boil kettle with {} of the treated source water.",
+            units.volume(process.pre_boil_volume())
+        ));
+
+        for dose in process.hops_doses().iter().filter(|d| d.first_wort) {
+            steps.boil.push(format!(
+                "Add {} of {} to the kettle as it heats (first-wort hops).",
+                units.small_weight(dose.weight),
+                dose.hops
+            ));
+        }
+    }
+
+    if !process.recipe.extracts.is_empty() {
+        steps.boil.push(
+            "With the burner off, stir the malt extracts (see grain bill) into \
+             the boil kettle until fully dissolved, so they do not scorch."
+                .to_string(),
+        );
+    }
+
     if !process.recipe.sugars.is_empty() {
         steps
             .boil
@@ -397,11 +1077,12 @@ pub fn print_process(
 
     steps.boil.push(format!(
         "Take a sample of the wort into a temperature-safe container \
-         and let it cool to below 49°C.  Then measure and record the \
+         and let it cool to below {}.  Then measure and record the \
          pre-boil Specific Gravity.  The actual correct gravity \
          can be determined by using the hydrometer_correct binary:\n\
          'cargo run --bin hydrometer_correct'\n\
-         The target temp-correct pre-boil gravity is {pre_boil_gravity}"
+         The target temp-correct pre-boil gravity is {pre_boil_gravity}",
+        units.temperature(Celsius(49.0))
     ));
 
     steps
@@ -416,32 +1097,50 @@ pub fn print_process(
         .boil
         .push("Optionally at hot-break, skim off and discard the protein foam.".to_string());
 
-    steps
-        .boil
-        .push("Maintain the boil at a rapid rolling boil for the duration.".to_string());
+    steps.boil.push(format!(
+        "Maintain the boil at a rapid rolling boil for the duration. At your \
+         elevation the wort boils at about {boiling_point}."
+    ));
 
     steps
         .boil
         .push(format!("We will be boiling for {boil_minutes}."));
 
-    steps.boil.push(format!(
-        "At various times, add hops:\n\
-             \
-             {hops_additions}"
-    ));
+    if boil_minutes < recommended_boil_minutes
+        && recommended_boil_minutes > process.recipe.style.recommended_boil_length()
+    {
+        steps.boil.push(format!(
+            "Consider boiling for {recommended_boil_minutes} instead. The cooler \
+             boil at your elevation drives off DMS from pilsner malt more slowly."
+        ));
+    }
 
-    if process.recipe.fining_desired {
+    if !hops_additions.is_empty() {
         steps.boil.push(format!(
-            "At 10 minutes before the end of the boil, add \
-                 {whirlfloc} whirlfloc tablets."
+            "At various times, add hops:\n\
+                 \
+                 {hops_additions}"
+        ));
+    }
+
+    if process.recipe.fining_desired && fining_agent.is_added_to_boil() {
+        steps.boil.push(format!(
+            "At 10 minutes before the end of the boil, add {fining}."
         ));
     }
 
     if yeast_nutrient > Grams(0.0) {
         steps.boil.push(format!(
             "At 10 minutes before the end of the boil, add \
-             {yeast_nutrient} of yeast nutrient."
+             {} of {} yeast nutrient.",
+            units.small_weight(yeast_nutrient),
+            process.brewery.yeast_nutrient
         ));
+        if zn > Milligrams(0.0) {
+            steps.boil.push(format!(
+                "At the same time, add {zn} of Zn+2 (e.g. via Zinc Sulfate)."
+            ));
+        }
     } else {
         steps.boil.push(format!(
             "Do not add yeast nutrient. However, if the yeast is repitched \
@@ -477,14 +1176,27 @@ pub fn print_process(
 
     // -- chill ------------
 
+    for dose in &process.hops_doses() {
+        if let Some((temperature, minutes)) = dose.whirlpool {
+            steps.chill.push(format!(
+                "Hop stand: let the wort cool to {} and hold it there \
+                 for {minutes} before chilling, with the {} of {} in the kettle.",
+                units.temperature(temperature),
+                units.small_weight(dose.weight),
+                dose.hops
+            ));
+        }
+    }
+
     if partial_boil_dilution > Liters(0.0) {
         steps.chill.push(format!(
-            "Dilute the wort with {partial_boil_dilution} of \
-                     boiled-then-cooled water"
+            "Dilute the wort with {} of \
+                     boiled-then-cooled water",
+            units.volume(partial_boil_dilution)
         ));
     }
 
-    if process.recipe.style.fermentation() == Fermentation::Lager {
+    if process.effective_fermentation() == Fermentation::Lager {
         steps.chill.push(
             "Rapid chilling is important for multiple reasons to avoid to \
              off-flavors (including DMS), contamination, and drop haze \
@@ -499,11 +1211,11 @@ pub fn print_process(
         );
     }
 
-    steps.chill.push(
-        "After the wort drops below 62C, it is no longer Pasteurized and can \
-               become infected. Sanitization is now important."
-            .to_string(),
-    );
+    steps.chill.push(format!(
+        "After the wort drops below {}, it is no longer Pasteurized and can \
+               become infected. Sanitization is now important.",
+        units.temperature(Celsius(62.0))
+    ));
 
     if process.brewery.ice_bath {
         steps.chill.push(
@@ -525,6 +1237,13 @@ pub fn print_process(
             .push("Chill the wart according to your setup and equipment".to_string());
     }
 
+    if let Some(fermenter) = process.recommended_fermenter() {
+        steps.chill.push(format!(
+            "Use your {} vessel as the fermenter.",
+            units.volume(fermenter)
+        ));
+    }
+
     steps
         .chill
         .push("Sanitize the fermenter and any equipment used for transfer.".to_string());
@@ -537,13 +1256,14 @@ pub fn print_process(
             .to_string(),
     );
 
-    steps
-        .chill
-        .push("Chill until the temperature gets to 20°C.".to_string());
+    let room_temperature = units.temperature(Celsius(20.0));
+    steps.chill.push(format!(
+        "Chill until the temperature gets to {room_temperature}."
+    ));
 
     steps.chill.push(format!(
         "Original Gravity Reading\n\n\
-             When the temperature is down to 20°C, take an Original Gravity reading. \
+             When the temperature is down to {room_temperature}, take an Original Gravity reading. \
              Optionally return the sample after testing. Target is {og}.\n\n\
              If the calculator is needed it is at \n\
              ( 'cargo run --bin hydrometer_correct' )."
@@ -559,7 +1279,20 @@ pub fn print_process(
     if process.recipe.yeast.is_dry() {
         steps.pitch.push("Wort oxygenation is not required for dry yeast since they have plenty of sterols already.".to_string());
     } else {
-        steps.pitch.push("Oxygenate the wort.".to_string());
+        let oxygen = process.target_dissolved_oxygen();
+        let seconds = process.pure_oxygen_seconds();
+        let shaking = if oxygen.0 > 8.0 {
+            "Shaking or splashing only reaches about 8 ppm, as that is all air \
+             can dissolve, so use pure oxygen."
+        } else {
+            "Vigorously shaking or splashing the wort for a few minutes is \
+             enough to reach this."
+        };
+        steps.pitch.push(format!(
+            "Oxygenate the wort to about {:.0} ppm dissolved oxygen: {seconds:.0} seconds \
+             of pure oxygen at 1 L/min through a 0.5 micron stone. {shaking}",
+            oxygen.0
+        ));
     }
 
     steps.pitch.push(format!(
@@ -570,7 +1303,7 @@ pub fn print_process(
 
     steps
         .pitch
-        .push(format!("Pitch {yeast_amount} of {yeast}.",));
+        .push(format!("Pitch {yeast_amount} of {yeast}."));
 
     // -- ferment ------------
 
@@ -589,24 +1322,79 @@ pub fn print_process(
             .to_string(),
     );
 
-    steps.ferment.push(format!(
-        "Diacetyl rest: As soon as it starts to slow, or when gravity is 2-5 \
-         points above {fg}, do a 2 day diacetyl rest at {diacetyl_rest_temp}, or \
-         just let it ferment on the trub at {fermentation_temp} for 3-5 days \
-         after fermentation stops."
-    ));
+    if process.recipe.diacetyl_rest_temperature() > process.recipe.ferment_temperature {
+        steps.ferment.push(format!(
+            "Diacetyl rest: As soon as it starts to slow, or when gravity is 2-5 \
+             points above {fg}, do a 2 day diacetyl rest at {diacetyl_rest_temp}, or \
+             just let it ferment on the trub at {fermentation_temp} for 3-5 days \
+             after fermentation stops."
+        ));
+    } else {
+        steps.ferment.push(format!(
+            "Diacetyl rest: Let it ferment on the trub at {fermentation_temp} \
+             for 3-5 days after fermentation stops."
+        ));
+    }
 
-    steps.ferment.push(
-        "Forced diacetyl test: Take a sample of beer, heat it to 66 C in \
+    for addition in &process.recipe.dry_hops {
+        steps.ferment.push(format!(
+            "Dry hop: On day {} add {} of {} to the fermenter. Remove them (or \
+             rack off of them) after {}.",
+            addition.day.0,
+            units.small_weight(process.dry_hop_weight(addition)),
+            addition.hops,
+            addition.duration
+        ));
+    }
+    if process
+        .get_warnings()
+        .iter()
+        .any(|w| matches!(w, Warning::HopCreep { .. }))
+    {
+        steps.ferment.push(
+            "Hop creep: Heavy dry hopping can restart fermentation. Check the \
+             gravity a few days after dry hopping and repeat the forced diacetyl \
+             test before packaging."
+                .to_string(),
+        );
+    }
+
+    steps.ferment.push(format!(
+        "Forced diacetyl test: Take a sample of beer, heat it to {} in \
          a water bath for 20 minutes. Then let it cool back to room temperature. \
          Smell and taste it. If it has diacetyl then let the beer ferment for \
-         another day and try again."
-            .to_string(),
-    );
+         another day and try again.",
+        units.temperature(Celsius(66.0))
+    ));
+
+    let residual_points = process.unfermentable_sugar_points();
+    if residual_points > 0.0 {
+        let sugars: Vec<String> = process
+            .recipe
+            .sugars
+            .iter()
+            .filter(|sp| sp.sugar.is_unfermentable())
+            .map(|sp| sp.sugar.to_string())
+            .collect();
+        steps.ferment.push(format!(
+            "Residual sweetness: {} will not ferment, and leaves about {residual_points:.0} \
+             gravity points of sweetness and body in the final gravity.",
+            sugars.join(", ")
+        ));
+    }
 
+    let fg_brix = refractometer_fg_reading(
+        process.recipe.original_gravity,
+        fg,
+        DEFAULT_WORT_CORRECTION_FACTOR,
+    );
     steps.ferment.push(format!(
         "Final Gravity Reading: Measure the final gravity. Return sample to carboy. \
-         Target is {fg}",
+         Target is {fg}\n\n\
+         A refractometer reads high once there is alcohol. Its target is about \
+         {fg_brix} (for a wort correction factor of {DEFAULT_WORT_CORRECTION_FACTOR}). \
+         If the calculator is needed it is at\n\
+         ( 'cargo run --bin refractometer_fg' ).",
     ));
 
     if lagering_time > Days(28) {
@@ -617,15 +1405,34 @@ pub fn print_process(
         );
     }
 
-    if process.recipe.fining_desired {
-        steps.ferment.push("Fining: Add fining agent.".to_string());
+    if process.recipe.fining_desired && !fining_agent.is_added_to_boil() {
+        let preparation = if fining_agent == FiningAgent::Gelatin {
+            "dissolved in a little water heated to about 70 °C"
+        } else {
+            "diluted in a little cold water"
+        };
+        steps.ferment.push(format!(
+            "Fining: Once the beer is cold, gently stir in {fining}, {preparation}. \
+             Give it 2 to 3 days to drop clear before packaging."
+        ));
     }
+    steps.ferment.push(format!(
+        "Expect the finished beer to be {}.",
+        process.expected_clarity()
+    ));
 
     if process.recipe.style.conditioning() == Conditioning::Lagered {
+        if let Some(lagerer) = process.recommended_lagerer() {
+            steps.ferment.push(format!(
+                "Use your {} vessel for lagering.",
+                units.volume(lagerer)
+            ));
+        }
+
         match process.recipe.style.origin() {
             StyleOrigin::American => {
                 steps.ferment.push(format!(
-                    "Crash the temperature down to  0°C - 1°C, and then hold \
+                    "Crash the temperature down to {} - {}, and then hold \
                      at this low temperature for {lagering_time}. Be aware that without \
                      taking some kind of remedial action, the fermenter will suck in \
                      whatever is in your airlock and a bunch of atmosphere (with oxygen) \
@@ -633,14 +1440,22 @@ pub fn print_process(
                      So consider these: Replace sanitizer in the airlock with strong alcohol; \
                      Apply continuous low pressure CO2; use a Co2-filled balloon as the \
                      airlock; use a blow-off tube long enough that the water wont be sucked \
-                     all the way into the fermenter."
+                     all the way into the fermenter. The headspace gas will contract by \
+                     about {}, so anything in the airlock or blow-off reservoir less than \
+                     that will all be pulled in.",
+                    units.temperature(Celsius(0.0)),
+                    units.temperature(Celsius(1.0)),
+                    units.volume(process.crash_vacuum_volume())
                 ));
             }
             _ => {
                 steps.ferment.push(format!(
-                    "Slowly lower the temperature by 1°C per day until you get near to \
-                     the lagering temperature range of 4°C - 7°C. Hold at \
-                     this low temperature for {lagering_time}."
+                    "Slowly lower the temperature by {} per day until you get near to \
+                     the lagering temperature range of {} - {}. Hold at \
+                     this low temperature for {lagering_time}.",
+                    units.temperature_change(1.0),
+                    units.temperature(Celsius(4.0)),
+                    units.temperature(Celsius(7.0))
                 ));
             }
         }
@@ -648,8 +1463,9 @@ pub fn print_process(
 
     if post_ferment_dilution > Liters(0.0) {
         steps.ferment.push(format!(
-            "Dilute the fermented beer with {post_ferment_dilution} \
-                     of boiled-then-cooled water."
+            "Dilute the fermented beer with {} \
+                     of boiled-then-cooled water.",
+            units.volume(post_ferment_dilution)
         ));
     }
 
@@ -677,30 +1493,56 @@ pub fn print_process(
         let total_priming_amount = sugar.priming_amount(
             process.recipe.style.carbonation_volume(),
             process.product_volume(),
-            process.brewery.room_temperature,
+            process.peak_beer_temperature(),
         );
-
-        steps.package.push(format!(
-            "If priming the entire batch at once, which you can do if you are \
-             now using a secondary fermenter, or if you use a bottling bucket, \
-             then mix in {total_priming_amount} of {sugar}. \
-             Try not to oxygenate, but do mix in \
-             the sugar until fully dissolved and distributed.",
-        ));
-
         let bottle_priming_amount = sugar.priming_amount(
             process.recipe.style.carbonation_volume(),
             bottle_volume,
-            process.brewery.room_temperature,
+            process.peak_beer_temperature(),
         );
 
         let num_bottles = (process.product_volume().0 / bottle_volume.0).ceil();
+        let bottle_volume = units.small_volume(bottle_volume);
+
+        if let (Some(total_priming_amount), Some(bottle_priming_amount)) =
+            (total_priming_amount, bottle_priming_amount)
+        {
+            let total_priming_amount = units.small_weight(total_priming_amount);
+            steps.package.push(format!(
+                "If priming the entire batch at once, which you can do if you are \
+                 now using a secondary fermenter, or if you use a bottling bucket, \
+                 then mix in {total_priming_amount} of {sugar}. \
+                 Try not to oxygenate, but do mix in \
+                 the sugar until fully dissolved and distributed.",
+            ));
 
-        steps.package.push(format!(
-            "If priming each bottle separately, add {bottle_priming_amount} \
-             of {sugar} to each bottle. Expect to fill up to \
-             {num_bottles} bottles.",
-        ));
+            let mut table = String::new();
+            for (temp, amount) in process.priming_table() {
+                let _ = writeln!(
+                    table,
+                    "    {}: {}",
+                    units.temperature(temp),
+                    units.small_weight(amount)
+                );
+            }
+            steps.package.push(format!(
+                "The amount of priming sugar depends on the temperature of the beer. \
+                 Use the warmest temperature it has been at since fermentation \
+                 finished. For the entire batch:\n\n{table}"
+            ));
+
+            let bottle_priming_amount = units.small_weight(bottle_priming_amount);
+            steps.package.push(format!(
+                "If priming each bottle separately, add {bottle_priming_amount} \
+                 of {sugar} to each bottle. Expect to fill up to \
+                 {num_bottles} bottles.",
+            ));
+        } else {
+            steps.package.push(format!(
+                "{sugar} is not fermentable, so it cannot prime the beer. Choose a \
+                 fermentable priming sugar. Expect to fill up to {num_bottles} bottles.",
+            ));
+        }
 
         // This is a bit nutty IMHO too
         //steps.package.push(format!(
@@ -721,64 +1563,31 @@ pub fn print_process(
         );
     } else {
         let carb_volume = process.recipe.style.carbonation_volume();
-        steps.package.push(format!(
-            "Kegging instructions are TBD. Carbonation volume target is {carb_volume}"
-        ));
-    }
+        let pressure = crate::keg_pressure(carb_volume, process.brewery.serving_temperature);
+        let serving_temperature = units.temperature(process.brewery.serving_temperature);
+        let bar: Bar = pressure.into();
 
-    steps.package.push("The beer is done.".to_string());
-
-    // -------------------------------
+        steps.package.push(
+            "Sanitize the keg, its lid and posts, and the siphon racking cane and tube."
+                .to_string(),
+        );
 
-    let mut output = String::new();
+        steps.package.push(
+            "Rack the beer into the keg. Try not to oxygenate. Seal it, and purge \
+             the headspace with CO₂ a few times."
+                .to_string(),
+        );
 
-    for block in &steps.header {
-        output.push_str(&indent(block, 0, char_width));
-        output.push('\n');
+        steps.package.push(format!(
+            "Chill the keg to {serving_temperature}, then set your regulator to \
+             {pressure} ({bar}) at {serving_temperature} to reach {carb_volume}. \
+             Leave it connected for about two weeks."
+        ));
     }
 
-    header(&mut output, "ACQUIRE", char_width);
-    for (i, block) in steps.acquire.iter().enumerate() {
-        label(&mut output, "ACQUIRE", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "PREP", char_width);
-    for (i, block) in steps.prep.iter().enumerate() {
-        label(&mut output, "PREP", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "MASH", char_width);
-    for (i, block) in steps.mash.iter().enumerate() {
-        label(&mut output, "MASH", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "BOIL", char_width);
-    for (i, block) in steps.boil.iter().enumerate() {
-        label(&mut output, "BOIL", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "CHILL", char_width);
-    for (i, block) in steps.chill.iter().enumerate() {
-        label(&mut output, "CHILL", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "PITCH", char_width);
-    for (i, block) in steps.pitch.iter().enumerate() {
-        label(&mut output, "PITCH", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "FERMENT", char_width);
-    for (i, block) in steps.ferment.iter().enumerate() {
-        label(&mut output, "FERMENT", i + 1, block, char_width);
-        output.push('\n');
-    }
-    header(&mut output, "PACKAGE", char_width);
-    for (i, block) in steps.package.iter().enumerate() {
-        label(&mut output, "PACKAGE", i + 1, block, char_width);
-        output.push('\n');
-    }
+    steps.package.push("The beer is done.".to_string());
 
-    output
+    steps
 }
 
 fn header(output: &mut String, label: &str, char_width: usize) {
@@ -836,3 +1645,147 @@ fn indent2(s: &str, indent: usize, char_width: usize) -> String {
     output
 }
 */
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_checklist() {
+        let steps = Steps {
+            header: vec!["Read this".to_owned()],
+            acquire: vec!["Buy malt".to_owned(), "Buy hops".to_owned()],
+            mash: vec!["Mash in".to_owned()],
+            package: vec!["Bottle".to_owned(), "Label".to_owned()],
+            ..Default::default()
+        };
+
+        let checklist = steps.to_checklist();
+        let total = steps.header.len()
+            + steps.acquire.len()
+            + steps.prep.len()
+            + steps.mash.len()
+            + steps.boil.len()
+            + steps.chill.len()
+            + steps.pitch.len()
+            + steps.ferment.len()
+            + steps.package.len();
+        assert_eq!(checklist.len(), total);
+
+        for (i, item) in checklist.iter().enumerate() {
+            assert_eq!(item.number, i + 1);
+            assert!(!item.done);
+        }
+        assert_eq!(checklist[3].stage, Stage::Mash);
+        assert_eq!(checklist[4].stage, Stage::Package);
+
+        let json = serde_json::to_string(&checklist).unwrap();
+        assert!(json.contains(r#""stage":"Acquire","number":2,"text":"Buy malt","done":false"#));
+    }
+
+    #[test]
+    fn test_print_process_markdown() {
+        let process = crate::process::test::test_process();
+        let md = print_process_markdown(&process, None, UnitSystem::Metric);
+        assert!(md.starts_with("# Recipe for Test Märzen"));
+        assert!(md.contains("\n## MASH\n"));
+        assert!(md.contains("\n| Malt | Weight | % |\n|:---|---:|---:|\n"));
+        assert!(md.contains("| Step | Change | Total | Volume |"));
+        assert!(md.contains("\n1. Fill the mash tun with"));
+    }
+
+    #[test]
+    fn test_print_process_imperial() {
+        let process = crate::process::test::test_process();
+        let output = print_process(&process, None, None, UnitSystem::Imperial);
+        assert!(output.contains(" gal"));
+        assert!(output.contains(" °F"));
+        assert!(output.contains(" lb"));
+        assert!(output.contains(" oz"));
+        assert!(!output.contains("°C"));
+        assert!(!output.contains(" L "));
+
+        let output = print_process(&process, None, None, UnitSystem::Metric);
+        assert!(output.contains(" L "));
+        assert!(!output.contains("°F"));
+
+        // The specification lists the same rows as the Markdown table
+        for (label, value, _) in specification(&process, UnitSystem::Metric) {
+            assert!(output.contains(&format!("  {:<18}{value}", format!("{label}:"))));
+        }
+
+        let md = print_process_markdown(&process, None, UnitSystem::Imperial);
+        assert!(md.contains(" gal |"));
+        assert!(md.contains("qt/lb"));
+    }
+
+    #[test]
+    fn test_grain_bill() {
+        let mut process = crate::process::test::test_process();
+        process.recipe.sugars.push(SugarProportion {
+            sugar: Sugar::Dextrose,
+            proportion: 5.0,
+        });
+        process.recipe.extracts.push(ExtractProportion {
+            extract: MaltExtract::LightDme,
+            proportion: 5.0,
+        });
+        let bill = grain_bill(&process, UnitSystem::Metric);
+        let rows: Vec<&str> = bill.lines().filter(|l| !l.starts_with("Total")).collect();
+        assert_eq!(
+            rows.len(),
+            process.recipe.malts.len() + process.recipe.sugars.len() + 1
+        );
+
+        // Malts, sugars and extracts share one format
+        for row in rows {
+            let (weight, rest) = row.split_once(", ").unwrap();
+            let (percent, name) = rest.split_once("%  ").unwrap();
+            assert!(weight.ends_with(" kg"));
+            assert!(percent.parse::<f32>().is_ok());
+            assert!(name.starts_with('['));
+        }
+    }
+
+    #[test]
+    fn test_fining_steps() {
+        let mut process = crate::process::test::test_process();
+        assert!(process.recipe.fining_desired);
+
+        // Whirlfloc goes in the kettle, a tablet per 19 L
+        let steps = process_steps(&process, None, UnitSystem::Metric);
+        assert!(
+            steps
+                .boil
+                .iter()
+                .any(|s| s.contains("add 1.1 tablets of [Whirlfloc]"))
+        );
+        assert!(!steps.ferment.iter().any(|s| s.starts_with("Fining")));
+
+        // Gelatin goes in after fermentation, dosed for what is left
+        process.recipe.fining_agent = FiningAgent::Gelatin;
+        let grams = 0.15 * process.post_ferment_volume().0;
+        assert!(float_cmp::approx_eq!(
+            f32,
+            process.fining_amount(),
+            grams,
+            epsilon = 0.001
+        ));
+        let steps = process_steps(&process, None, UnitSystem::Metric);
+        assert!(!steps.boil.iter().any(|s| s.contains("[Gelatin]")));
+        let dose = format!("{grams:.1} g of [Gelatin]");
+        assert!(
+            steps
+                .ferment
+                .iter()
+                .any(|s| s.starts_with("Fining") && s.contains(&dose))
+        );
+        assert!(steps.acquire.iter().any(|s| s.contains(&dose)));
+        assert!(
+            process
+                .shopping_list_json()
+                .iter()
+                .any(|item| item.category == "fining" && item.item == "Gelatin")
+        );
+    }
+}
@@ -4,6 +4,19 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
 
+/// Zinc wanted in the wort. JP says 0.1-0.3 mg/L.
+const ZINC_TARGET: Ppm = Ppm(0.2);
+
+/// Zinc above this stresses the yeast and can taste metallic
+const ZINC_MAX: Ppm = Ppm(0.5);
+
+/// First-wort hops are usually counted as a boil addition of this length
+const FIRST_WORT_EQUIVALENT: Minutes = Minutes(20);
+
+/// First-wort hops give about 10% more bitterness than the boil addition
+/// they are counted as
+const FIRST_WORT_BONUS: f32 = 1.1;
+
 /// Process
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Process {
@@ -37,9 +50,76 @@ impl Process {
         }
     }
 
-    /// The length of time before the beer is ready
+    /// This process scaled to a different batch size.
+    ///
+    /// The recipe is kept in proportions, so all doses recompute for the
+    /// new size while the gravity, IBU, hop timings and mash thickness
+    /// stay the same. If the new size does not fit the equipment, the
+    /// usual warnings apply (and a partial boil may be needed).
     #[must_use]
-    pub fn time_until_done(&self) -> Days {
+    pub fn rescale(&self, new_batch_size: Liters) -> Process {
+        Process {
+            batch_size: new_batch_size,
+            ..self.clone()
+        }
+    }
+
+    /// The fermentation type, with `Fermentation::Either` styles resolved
+    /// by whether the chosen yeast is a lager yeast.
+    #[must_use]
+    pub fn effective_fermentation(&self) -> Fermentation {
+        match self.recipe.style.fermentation() {
+            Fermentation::Either if self.recipe.yeast.is_lager() => Fermentation::Lager,
+            Fermentation::Either => Fermentation::Ale,
+            other => other,
+        }
+    }
+
+    /// The warmest fermentation temperature that keeps esters appropriate
+    /// for the style, if the style calls for a clean fermentation.
+    #[must_use]
+    pub fn style_max_ferment_temperature(&self) -> Option<Celsius> {
+        // Lagers should be clean, lager yeasts throw esters when warm
+        if self.effective_fermentation() == Fermentation::Lager {
+            Some(Celsius(13.0))
+        } else {
+            None
+        }
+    }
+
+    /// A recommended fermentation temperature: the yeast midpoint,
+    /// lowered if needed for the style but kept within the yeast range.
+    #[must_use]
+    pub fn recommended_ferment_temperature(&self) -> Celsius {
+        let range = self.recipe.yeast.temp_range();
+        let mut temp = self.recipe.yeast.temp();
+        if let Some(max) = self.style_max_ferment_temperature()
+            && temp > max
+        {
+            temp = max;
+        }
+        if temp < range.start {
+            temp = range.start;
+        }
+        temp
+    }
+
+    /// The warmest the beer gets after fermentation, which sets how much
+    /// CO2 remains dissolved for priming: the diacetyl rest, if warmer
+    #[must_use]
+    pub fn peak_beer_temperature(&self) -> Celsius {
+        let rest = self.recipe.diacetyl_rest_temperature();
+        if rest > self.recipe.ferment_temperature {
+            rest
+        } else {
+            self.recipe.ferment_temperature
+        }
+    }
+
+    /// The length of time the beer conditions (or lagers) after the
+    /// diacetyl rest
+    #[must_use]
+    pub fn conditioning_time(&self) -> Days {
         let mut conditioning = self.recipe.style.recommended_conditioning_time();
 
         if let Packaging::Bottle(_, _) = self.brewery.packaging {
@@ -50,53 +130,139 @@ impl Process {
             }
         }
 
+        conditioning
+    }
+
+    /// The length of time before the beer is ready
+    #[must_use]
+    pub fn time_until_done(&self) -> Days {
         // 2 days of diacetyl rest
-        self.recipe.fermentation_time() + Days(2) + conditioning
+        self.recipe.fermentation_time() + Days(2) + self.conditioning_time()
     }
 
     /// Water salts to adjust ions
+    ///
+    /// When computing acid additions, acid can only lower the mash pH. If
+    /// the grist brings the mash pH below the target (as dark grists in
+    /// soft water do) this gives the baking soda that raises it instead,
+    /// if the brewery has some.
     #[must_use]
     #[allow(clippy::missing_panics_doc)]
     pub fn water_salts(&self) -> Vec<SaltConcentration> {
-        if matches!(self.recipe.ph_method, PhMethod::AdjustWater) {
-            let water_adjustment = WaterAdjustment {
-                profile: self.brewery.water_profile,
-                mash_ph_distilled: self.mash_ph_distilled().pop().unwrap(),
-                target_ph: self.recipe.mash_ph_target,
-                sulfate_chloride_target: self.recipe.sulfate_chloride_target,
-            };
+        if !self.has_mash() {
+            return Vec::new();
+        }
 
-            water_adjustment.salts_needed()
-        } else {
-            Vec::new()
+        match self.recipe.ph_method {
+            PhMethod::AdjustWater => {
+                let water_adjustment = WaterAdjustment {
+                    profile: self.brewery.source_water_profile(),
+                    mash_ph_distilled: self.mash_ph_distilled().pop().unwrap(),
+                    target_ph: self.recipe.mash_ph_target,
+                    sulfate_chloride_target: self.recipe.sulfate_chloride_target,
+                };
+
+                water_adjustment.salts_needed()
+            }
+            PhMethod::ComputeAcid(_) => self.water_bases(),
+            PhMethod::None => Vec::new(),
+        }
+    }
+
+    /// Baking soda to raise the mash pH to the target, when the grist
+    /// alone takes it below. This is dosed into the mash water only.
+    fn water_bases(&self) -> Vec<SaltConcentration> {
+        if !self.brewery.salts_available.contains(&Salt::BakingSoda) {
+            return Vec::new();
+        }
+
+        let Some(start) = self
+            .mash_ph_with(&self.brewery.source_water_profile())
+            .pop()
+        else {
+            return Vec::new();
+        };
+        let end = self.recipe.mash_ph_target;
+        if start >= end {
+            return Vec::new();
         }
+
+        // Alkalinity needed against the buffering of the grist, spread
+        // over the mash water. Baking soda gives one mEq per mole.
+        let meq = (end.0 - start.0) * self.mash_buffering_capacity();
+        let meq_per_liter = meq / self.mash_volume().0;
+        let ppm = Ppm(meq_per_liter * Salt::BakingSoda.molecular_weight());
+
+        vec![SaltConcentration {
+            salt: Salt::BakingSoda,
+            ppm,
+        }]
     }
 
     /// Water acids to adjust mash pH
+    ///
+    /// Uses the recipe's acid if the brewery has it, otherwise the first
+    /// acid the brewery has available.
     #[must_use]
     #[allow(clippy::missing_panics_doc)]
     pub fn water_acids(&self) -> Vec<AcidConcentration> {
-        if let PhMethod::ComputeAcid(acid) = self.recipe.ph_method {
-            let start = self.mash_ph_preacid().pop().unwrap();
-            let end = self.recipe.mash_ph_target;
+        let PhMethod::ComputeAcid(wanted) = self.recipe.ph_method else {
+            return Vec::new();
+        };
+
+        let Some(acid) = self.chosen_acid(Some(wanted)) else {
+            return Vec::new();
+        };
 
-            let shift = start.0 - end.0;
+        if !self.has_mash() {
+            return Vec::new();
+        }
 
-            // FIXME this is only for Lactic Acid 88%
-            // FIXME this is not very accurate
-            // 25-80 ppm to drop by 0.15 pH
-            let ppm = Ppm(300.0 * shift);
+        // Baking soda has already brought the mash up to the target
+        if !self.water_bases().is_empty() {
+            return Vec::new();
+        }
 
-            vec![AcidConcentration { acid, ppm }]
-        } else {
-            Vec::new()
+        let start = self.mash_ph_preacid().pop().unwrap();
+        let end = self.recipe.mash_ph_target;
+        if start <= end {
+            return Vec::new();
+        }
+
+        // Acid needed against the buffering of the grist, spread over
+        // the mash water
+        let meq = (start.0 - end.0) * self.mash_buffering_capacity();
+        let grams_per_liter = meq / acid.meq_per_gram() / self.mash_volume().0;
+        let ppm = Ppm(grams_per_liter * 1000.0);
+
+        vec![AcidConcentration { acid, ppm }]
+    }
+
+    /// The acid to use: `wanted` if the brewery has it, otherwise the
+    /// first acid the brewery has available.
+    fn chosen_acid(&self, wanted: Option<Acid>) -> Option<Acid> {
+        match wanted {
+            Some(acid) if self.brewery.acids_available.contains(&acid) => Some(acid),
+            _ => self.brewery.acids_available.first().copied(),
         }
     }
 
+    /// The buffering capacity of the grist in mEq per pH unit.
+    ///
+    /// Pale malts buffer roughly 40 mEq/(kg·pH), and more acidic (darker)
+    /// malts buffer more.
+    #[must_use]
+    pub fn mash_buffering_capacity(&self) -> f32 {
+        self.malt_doses()
+            .iter()
+            .map(|dose| dose.weight.0 * malt_buffering(dose.malt))
+            .sum()
+    }
+
     /// The water profile (after salts, before acids)
     #[must_use]
     pub fn adjusted_water_profile_preacid(&self) -> WaterProfile {
-        let mut profile = self.brewery.water_profile;
+        let mut profile = self.brewery.source_water_profile();
 
         for salt_conc in &self.water_salts() {
             profile.add_salt(*salt_conc);
@@ -108,7 +274,7 @@ impl Process {
     /// The water profile (after salts and acids)
     #[must_use]
     pub fn adjusted_water_profile(&self) -> WaterProfile {
-        let mut profile = self.brewery.water_profile;
+        let mut profile = self.brewery.source_water_profile();
 
         for salt_conc in &self.water_salts() {
             profile.add_salt(*salt_conc);
@@ -131,26 +297,62 @@ impl Process {
         self.batch_size * 0.20
     }
 
-    /// Chosen fermenter volume
+    /// The smallest of the brewery's fermenters that has room for the
+    /// batch plus head space, if any does.
     #[must_use]
-    pub fn fermenter_volume(&self) -> Liters {
+    pub fn recommended_fermenter(&self) -> Option<Liters> {
         let needed = self.batch_size + self.fermentation_head_space();
 
-        // Choose the smallest fermenter that handles this
-        let chosen = self
-            .brewery
+        self.brewery
             .fermenters
             .iter()
             .copied()
             .filter(|&f| f >= needed)
-            .min();
+            .min()
+    }
+
+    /// The smallest of the brewery's lagering vessels that holds the
+    /// post-fermentation volume, if any does.
+    #[must_use]
+    pub fn recommended_lagerer(&self) -> Option<Liters> {
+        let needed = self.post_ferment_volume();
+
+        self.brewery
+            .lagerers
+            .iter()
+            .copied()
+            .filter(|&f| f >= needed)
+            .min()
+    }
+
+    /// Chosen fermenter volume
+    #[must_use]
+    pub fn fermenter_volume(&self) -> Liters {
+        self.recommended_fermenter()
+            .unwrap_or(self.batch_size + self.fermentation_head_space())
+    }
 
-        chosen.unwrap_or(needed)
+    /// Weight of a dry hop addition
+    #[must_use]
+    pub fn dry_hop_weight(&self, addition: &DryHopAddition) -> Grams {
+        Grams(addition.grams_per_liter * self.batch_size.0)
+    }
+
+    /// Beer soaked up by the dry hops
+    #[must_use]
+    pub fn dry_hops_absorption(&self) -> Liters {
+        let grams: f32 = self
+            .recipe
+            .dry_hops
+            .iter()
+            .map(|addition| self.dry_hop_weight(addition).0)
+            .sum();
+        self.brewery.hops_absorption_per_kg * (grams / 1000.0)
     }
 
     /// Post ferment volume
     pub(crate) fn post_ferment_volume(&self) -> Liters {
-        self.batch_size * (1.0 - self.recipe.ferment_loss_fraction())
+        self.batch_size * (1.0 - self.recipe.ferment_loss_fraction()) - self.dry_hops_absorption()
     }
 
     /// Ferment losses
@@ -165,10 +367,105 @@ impl Process {
         self.brewery.boil_evaporation_per_hour / 60.0 * self.recipe.boil_length.0 as f32
     }
 
+    /// The boiling point of wort at the brewery's elevation, from the
+    /// standard atmosphere and Clausius-Clapeyron.
+    #[must_use]
+    pub fn boiling_point(&self) -> Celsius {
+        // Fraction of sea-level pressure
+        let pressure = (1.0 - 2.255_77e-5 * self.brewery.elevation.0).powf(5.255_88);
+
+        // Latent heat of vaporization of water over the gas constant (K)
+        let l_over_r = 40_660.0 / 8.314;
+        let kelvin = 1.0 / (1.0 / 373.15 - pressure.ln() / l_over_r);
+        Celsius(kelvin - 273.15)
+    }
+
+    /// Recommended boil length for the style, lengthened at elevation when
+    /// the grist has a lot of pilsner malt, since a cooler boil drives off
+    /// DMS more slowly.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn recommended_boil_length(&self) -> Minutes {
+        let minutes = self.recipe.style.recommended_boil_length();
+
+        let pilsner: f32 = self
+            .recipe
+            .malts
+            .iter()
+            .filter(|mp| {
+                matches!(
+                    mp.malt,
+                    Malt::GladfieldGermanPilsner
+                        | Malt::GladfieldPilsner
+                        | Malt::WeyermannBohemianPilsner
+                        | Malt::WeyermannPilsner
+                )
+            })
+            .map(|mp| mp.proportion)
+            .sum();
+        let total: f32 = self
+            .recipe
+            .malts
+            .iter()
+            .map(|mp| mp.proportion)
+            .sum::<f32>()
+            + self
+                .recipe
+                .sugars
+                .iter()
+                .map(|sp| sp.proportion)
+                .sum::<f32>()
+            + self
+                .recipe
+                .extracts
+                .iter()
+                .map(|ep| ep.proportion)
+                .sum::<f32>();
+
+        let depression = 100.0 - self.boiling_point().0;
+        if total > 0.0 && pilsner / total >= 0.3 && depression > 2.0 {
+            // About 5 minutes more per degree below 100°C
+            minutes + Minutes((depression * 5.0).round() as usize)
+        } else {
+            minutes
+        }
+    }
+
+    /// Wort soaked up by the boil hops, and left behind in the kettle
+    ///
+    /// The hop weights depend on the boil gravity, which depends on this
+    /// through any partial boil, so they are taken at the gravity the boil
+    /// would have if the hops soaked up nothing.
+    #[must_use]
+    pub fn hops_absorption(&self) -> Liters {
+        if self.brewery.hops_absorption_per_kg.0 <= 0.0 {
+            return Liters(0.0);
+        }
+
+        let dilution = self.partial_boil_dilution_with(Liters(0.0));
+        let points = GravityPoints::from(self.recipe.original_gravity);
+        let boil_gravity = (points * (self.batch_size.0 / (self.batch_size - dilution).0)).into();
+        let grams: f32 = self
+            .hops_doses_in(boil_gravity)
+            .iter()
+            .map(|dose| dose.weight.0)
+            .sum();
+        self.brewery.hops_absorption_per_kg * (grams / 1000.0)
+    }
+
     /// Partial boil dilution
     #[must_use]
     pub fn partial_boil_dilution(&self) -> Liters {
-        let volume_needed = self.batch_size + self.brewery.kettle_losses + self.boil_evaporation();
+        self.partial_boil_dilution_with(self.hops_absorption())
+    }
+
+    /// Partial boil dilution, given the wort the hops soak up
+    fn partial_boil_dilution_with(&self, hops_absorption: Liters) -> Liters {
+        let volume_needed = self.batch_size
+            + self.brewery.kettle_losses
+            + hops_absorption
+            + self.boil_evaporation();
 
         if self.brewery.max_kettle_volume < volume_needed {
             volume_needed - self.brewery.max_kettle_volume
@@ -183,10 +480,11 @@ impl Process {
         self.batch_size - self.partial_boil_dilution()
     }
 
-    /// The volume of undiluted wart after the boil, before kettle losses.
+    /// The volume of undiluted wart after the boil, before kettle losses
+    /// and hop absorption.
     #[must_use]
     pub fn post_boil_pre_loss_volume(&self) -> Liters {
-        self.post_boil_volume() + self.brewery.kettle_losses
+        self.post_boil_volume() + self.brewery.kettle_losses + self.hops_absorption()
     }
 
     /// The pre-boil volume
@@ -197,8 +495,30 @@ impl Process {
 
     /// Multipler on the grain bill that achieves the original
     /// gravity at the batch size.
+    ///
+    /// The extract has to fill the kettle at the post-boil gravity,
+    /// including the wort left behind as kettle losses.
+    ///
+    /// Sugars and extracts scale just like malts, so an all-sugar recipe
+    /// works. If the
+    /// recipe has no fermentables at all (or their proportions sum to
+    /// zero) no multiplier can reach the gravity, and this is 0.0 (see
+    /// `Warning::NoFermentables`).
     #[must_use]
     pub fn grain_bill_multiplier(&self) -> f32 {
+        let actual_points = self.unit_bill_points();
+        if actual_points.0 <= f32::EPSILON {
+            return 0.0;
+        }
+
+        let ideal_points = GravityPoints::from(self.post_boil_gravity());
+
+        ideal_points.0 / actual_points.0
+    }
+
+    /// Post-boil gravity points of the bill as if each proportion were
+    /// that many kilograms
+    fn unit_bill_points(&self) -> GravityPoints {
         let malt_doses: Vec<MaltDose> = self
             .recipe
             .malts
@@ -219,18 +539,56 @@ impl Process {
             })
             .collect();
 
+        let extract_doses: Vec<ExtractDose> = self
+            .recipe
+            .extracts
+            .iter()
+            .map(|proportion| ExtractDose {
+                extract: proportion.extract,
+                weight: Kilograms(proportion.proportion), // as if 1.0 is 1.0 kg.
+            })
+            .collect();
+
         let sg = SpecificGravity::from_recipe(
             &malt_doses,
             &sugar_doses,
-            self.batch_size.into(),
-            self.brewery.mash_efficiency,
+            &extract_doses,
+            self.post_boil_pre_loss_volume(),
+            self.mash_efficiency(),
         );
 
-        let actual_points = sg.0 - 1.0;
+        GravityPoints::from(sg)
+    }
 
-        let ideal_points = self.recipe.original_gravity.0 - 1.0;
+    /// The original gravity reached with the given total weight of malt,
+    /// in the recipe's ratios, at the batch size.
+    ///
+    /// Sugars and extracts keep their proportion to the malts. This is
+    /// for brewing with whatever grain is on hand.
+    #[must_use]
+    pub fn achievable_og(&self, total_grain: Kilograms) -> SpecificGravity {
+        let malt_proportions: f32 = self.recipe.malts.iter().map(|mp| mp.proportion).sum();
+        if malt_proportions <= 0.0 {
+            return SpecificGravity(1.0);
+        }
+        let multiplier = total_grain.0 / malt_proportions;
+        let post_boil_points = self.unit_bill_points() * multiplier;
+        (post_boil_points * (self.post_boil_volume().0 / self.batch_size.0)).into()
+    }
 
-        ideal_points / actual_points
+    /// The total weight of malt, in the recipe's ratios, needed to reach
+    /// the given original gravity at the batch size. This is the inverse
+    /// of `achievable_og()`.
+    #[must_use]
+    pub fn grain_needed_for_og(&self, target: SpecificGravity) -> Kilograms {
+        let unit_points = self.unit_bill_points();
+        if unit_points.0 <= f32::EPSILON {
+            return Kilograms(0.0);
+        }
+        let malt_proportions: f32 = self.recipe.malts.iter().map(|mp| mp.proportion).sum();
+        let post_boil_points =
+            GravityPoints::from(target) * (self.batch_size.0 / self.post_boil_volume().0);
+        Kilograms(post_boil_points.0 / unit_points.0 * malt_proportions)
     }
 
     /// Malt doses
@@ -263,36 +621,184 @@ impl Process {
             .collect()
     }
 
+    /// Malt extract doses
+    #[must_use]
+    pub fn extract_doses(&self) -> Vec<ExtractDose> {
+        let multiplier = self.grain_bill_multiplier();
+
+        self.recipe
+            .extracts
+            .iter()
+            .map(|proportion| ExtractDose {
+                extract: proportion.extract,
+                weight: Kilograms(proportion.proportion * multiplier),
+            })
+            .collect()
+    }
+
     /// The pre-boil original gravity (OG) of the wort
     #[must_use]
     pub fn pre_boil_gravity(&self) -> SpecificGravity {
-        let pre_boil_volume: Gallons = self.pre_boil_volume().into();
         SpecificGravity::from_recipe(
             &self.malt_doses(),
             &self.sugar_doses(),
-            pre_boil_volume,
-            self.brewery.mash_efficiency,
+            &self.extract_doses(),
+            self.pre_boil_volume(),
+            self.mash_efficiency(),
         )
     }
 
-    /// Hops doses
+    /// The gravity of the wort in the kettle at the end of the boil.
+    ///
+    /// This is higher than the original gravity when a partial boil is
+    /// diluted afterwards.
     #[must_use]
-    #[allow(clippy::cast_precision_loss)]
-    pub fn hops_doses(&self) -> Vec<HopsDose> {
-        // We use Tinseth
+    pub fn post_boil_gravity(&self) -> SpecificGravity {
+        let points = GravityPoints::from(self.recipe.original_gravity);
+        (points * (self.batch_size.0 / self.post_boil_volume().0)).into()
+    }
 
-        let bigness_factor = 1.65 * (0.000_125_f32).powf(self.recipe.original_gravity.0 - 1.0);
-        let gallons: Gallons = self.batch_size.into();
+    /// IBU contributed to the batch by a single hops addition
+    ///
+    /// `alpha_acid` is a fraction, as from `HopsDose::alpha_acid()`, so
+    /// that a measured crop and storage losses are counted.
+    ///
+    /// Utilization is computed at the boil volume and gravity, and then
+    /// diluted by any post-boil top-up water.
+    #[must_use]
+    pub fn hops_ibu(&self, alpha_acid: f32, weight: Grams, timing: Minutes) -> Ibu {
+        self.hops_ibu_at(alpha_acid, weight, timing, self.recipe.ibu)
+    }
+
+    /// IBU contributed by a single hops addition of the given alpha acid,
+    /// given the expected bitterness of the whole beer (which Garetz
+    /// depends upon)
+    fn hops_ibu_at(&self, alpha_acid: f32, weight: Grams, timing: Minutes, beer_ibu: Ibu) -> Ibu {
+        self.hops_ibu_in(
+            self.post_boil_gravity(),
+            alpha_acid,
+            weight,
+            timing,
+            beer_ibu,
+        )
+    }
+
+    /// IBU contributed by a single hops addition, boiled at the given
+    /// gravity
+    fn hops_ibu_in(
+        &self,
+        boil_gravity: SpecificGravity,
+        alpha_acid: f32,
+        weight: Grams,
+        timing: Minutes,
+        beer_ibu: Ibu,
+    ) -> Ibu {
+        let utilization = crate::ingredients::utilization(
+            self.recipe.ibu_model,
+            boil_gravity,
+            timing,
+            beer_ibu,
+            self.brewery.elevation,
+        );
+
+        // Diluting the boil volume to the batch size leaves the IBU per
+        // batch volume
+        let ounces: Ounces = weight.into();
+        let batch_gallons: Gallons = self.batch_size.into();
+        Ibu(utilization * alpha_acid * ounces.0 * 7490.0 / batch_gallons.0)
+    }
 
-        let mut nominal_ibus: f32 = 0.0;
+    /// IBU contributed by a hops addition, which may be first-wort hops
+    /// and may be held in a whirlpool after the boil
+    ///
+    /// First-wort hops sit in the kettle for the whole boil, but their
+    /// bitterness is smoother and far less than that of a boil-length
+    /// addition.  We count them as a 20 minute addition, plus 10%.
+    ///
+    /// Whirlpool hops keep isomerizing through the hop stand. They gain
+    /// the utilization from the end of their boil time to the end of the
+    /// stand, at a rate reduced for the stand temperature.
+    fn addition_ibu(
+        &self,
+        alpha_acid: f32,
+        weight: Grams,
+        timing: Minutes,
+        first_wort: bool,
+        whirlpool: Option<(Celsius, Minutes)>,
+        beer_ibu: Ibu,
+    ) -> Ibu {
+        self.addition_ibu_in(
+            self.post_boil_gravity(),
+            alpha_acid,
+            weight,
+            timing,
+            first_wort,
+            whirlpool,
+            beer_ibu,
+        )
+    }
 
-        for hops_prop in &self.recipe.hops {
-            let ounces: Ounces = Grams(hops_prop.proportion).into();
-            let boil_time_factor = (1.0 - (-0.04 * hops_prop.timing.0 as f32).exp()) / 4.15;
-            let utilization = bigness_factor * boil_time_factor;
-            nominal_ibus +=
-                utilization * hops_prop.hops.alpha_acid() * ounces.0 * 7490.0 / gallons.0;
+    /// IBU contributed by a hops addition, boiled at the given gravity
+    #[allow(clippy::too_many_arguments)]
+    fn addition_ibu_in(
+        &self,
+        boil_gravity: SpecificGravity,
+        alpha_acid: f32,
+        weight: Grams,
+        timing: Minutes,
+        first_wort: bool,
+        whirlpool: Option<(Celsius, Minutes)>,
+        beer_ibu: Ibu,
+    ) -> Ibu {
+        let boil_time = if first_wort {
+            self.recipe.boil_length
+        } else {
+            timing
+        };
+        let ibu_at = |minutes: Minutes| {
+            self.hops_ibu_in(boil_gravity, alpha_acid, weight, minutes, beer_ibu)
+        };
+        let boil = if first_wort {
+            Ibu(ibu_at(FIRST_WORT_EQUIVALENT).0 * FIRST_WORT_BONUS)
+        } else {
+            ibu_at(boil_time)
+        };
+        match whirlpool {
+            Some((temperature, minutes)) => {
+                let through = ibu_at(boil_time + minutes);
+                let before = ibu_at(boil_time);
+                let stand = (through.0 - before.0).max(0.0);
+                Ibu(boil.0 + stand * crate::ingredients::whirlpool_factor(temperature))
+            }
+            None => boil,
         }
+    }
+
+    /// Hops doses
+    #[must_use]
+    pub fn hops_doses(&self) -> Vec<HopsDose> {
+        self.hops_doses_in(self.post_boil_gravity())
+    }
+
+    /// Hops doses to reach the IBU target when boiled at the given gravity
+    fn hops_doses_in(&self, boil_gravity: SpecificGravity) -> Vec<HopsDose> {
+        let nominal_ibus: f32 = self
+            .recipe
+            .hops
+            .iter()
+            .map(|prop| {
+                self.addition_ibu_in(
+                    boil_gravity,
+                    prop.alpha_acid(),
+                    Grams(prop.proportion),
+                    prop.timing,
+                    prop.first_wort,
+                    prop.whirlpool,
+                    self.recipe.ibu,
+                )
+                .0
+            })
+            .sum();
 
         let scaling_factor = self.recipe.ibu.0 / nominal_ibus;
 
@@ -303,6 +809,10 @@ impl Process {
                 hops: prop.hops,
                 weight: Grams(prop.proportion * scaling_factor),
                 timing: prop.timing,
+                first_wort: prop.first_wort,
+                whirlpool: prop.whirlpool,
+                age: prop.age,
+                measured_alpha_acid: prop.measured_alpha_acid,
             })
             .collect()
     }
@@ -311,8 +821,8 @@ impl Process {
     #[must_use]
     pub fn hops_additions_string(&self) -> String {
         let mut output: String = String::new();
-        for hopsdose in &self.hops_doses() {
-            let after = self.recipe.boil_length - hopsdose.timing;
+        for hopsdose in self.hops_doses().iter().filter(|d| !d.first_wort) {
+            let after = Minutes(self.recipe.boil_length.0.saturating_sub(hopsdose.timing.0));
             writeln!(
                 output,
                 "\n{} from start {} from end:  Add {} of {}",
@@ -323,20 +833,115 @@ impl Process {
         output
     }
 
-    /// The amount of whirlfloc tablet to use
+    /// Late (aroma) hops that keep poorly, and so should be bought fresh
     #[must_use]
-    pub fn whirlfloc_amount(&self) -> f32 {
-        if self.recipe.fining_desired {
-            1.0 * (self.batch_size.0 / 19.0)
-        } else {
-            0.0
+    pub fn aroma_hops_to_buy_fresh(&self) -> Vec<Hops> {
+        let mut output: Vec<Hops> = Vec::new();
+        for prop in &self.recipe.hops {
+            if prop.timing <= Minutes(20)
+                && prop.hops.keeps_poorly()
+                && !output.contains(&prop.hops)
+            {
+                output.push(prop.hops);
+            }
+        }
+        output
+    }
+
+    /// Total weight of all hops, in the boil and the fermenter
+    #[must_use]
+    pub fn total_hop_mass(&self) -> Grams {
+        let split = self.hop_mass_split();
+        split.bittering + split.aroma
+    }
+
+    /// How the hops divide between bittering and aroma
+    ///
+    /// Additions with 20 minutes or less left in the boil, or that get a
+    /// whirlpool, count as aroma, as do dry hops.
+    #[must_use]
+    pub fn hop_mass_split(&self) -> HopMassSplit {
+        let mut bittering = Grams(0.0);
+        let mut aroma = Grams(0.0);
+        for dose in &self.hops_doses() {
+            if !dose.first_wort && (dose.timing <= Minutes(20) || dose.whirlpool.is_some()) {
+                aroma = aroma + dose.weight;
+            } else {
+                bittering = bittering + dose.weight;
+            }
+        }
+        for addition in &self.recipe.dry_hops {
+            aroma = aroma + self.dry_hop_weight(addition);
+        }
+        HopMassSplit { bittering, aroma }
+    }
+
+    /// Bitterness units to gravity units ratio (BU:GU)
+    ///
+    /// This is the usual measure of balance. Below about 0.5 a beer leans
+    /// malty, around 0.5 to 0.8 it is balanced or a little bitter, and
+    /// above that it is distinctly bitter.
+    #[must_use]
+    pub fn bu_gu_ratio(&self) -> f32 {
+        self.recipe.ibu.0 / self.recipe.original_gravity.points()
+    }
+
+    /// The amount of the recipe's fining agent to use, in its `unit()`s
+    ///
+    /// Kettle finings are dosed for the batch, and finings added after
+    /// fermentation for the beer left in the fermenter.
+    #[must_use]
+    pub fn fining_amount(&self) -> f32 {
+        if !self.recipe.fining_desired {
+            return 0.0;
         }
+        let agent = self.recipe.fining_agent;
+        let volume = if agent.is_added_to_boil() {
+            self.batch_size
+        } else {
+            self.post_ferment_volume()
+        };
+        agent.amount_per_liter() * volume.0
     }
 
     /// The weight of the malts in the mash
     #[must_use]
     pub fn grain_weight(&self) -> Kilograms {
-        self.malt_doses().iter().map(|dose| dose.weight).sum()
+        let weight: Kilograms = self.malt_doses().iter().map(|dose| dose.weight).sum();
+        // An empty sum is -0.0
+        if weight.0 > 0.0 {
+            weight
+        } else {
+            Kilograms(0.0)
+        }
+    }
+
+    /// Whether there is any grain to mash
+    ///
+    /// Extract and sugar recipes have none, and skip the mash entirely:
+    /// the water goes straight into the kettle.
+    #[must_use]
+    pub fn has_mash(&self) -> bool {
+        self.grain_weight().0 > 0.0
+    }
+
+    /// Diastatic power of the whole grist, weighted by grain weight
+    ///
+    /// Around 30 °Lintner is the practical floor for a mash to convert
+    /// itself.
+    #[must_use]
+    pub fn total_diastatic_power(&self) -> Lintner {
+        let grain_weight = self.grain_weight();
+        if grain_weight.0 <= 0.0 {
+            return Lintner(0.0);
+        }
+
+        let lintner_kg: f32 = self
+            .malt_doses()
+            .iter()
+            .map(|dose| dose.malt.diastatic_power().0 * dose.weight.0)
+            .sum();
+        Lintner(lintner_kg / grain_weight.0)
     }
 
     /// Mash pHs with distilled water, considering only the malts.
@@ -345,93 +950,84 @@ impl Process {
     /// This is used to help figure out water adjustments and acid additions.
     /// It also feeds into the mash_ph() function
     /// (rather than "beer color" which is a poorer proxy)
+    ///
+    /// Each malt buffers toward its own distilled water mash pH, so the
+    /// grist settles where the charges balance: the average of those pHs
+    /// weighted by each malt's buffering capacity (Riffe, Troester).
+    /// Malts with a measured distilled water mash pH anchor the estimate.
+    /// For the others it is inferred from their acidity, which is measured
+    /// as the mEq/kg needed to bring them to pH 5.7.
+    ///
+    /// Without water there is no dependence on mash thickness, so every
+    /// step has the same pH.
     #[must_use]
     pub fn mash_ph_distilled(&self) -> Vec<Ph> {
         // http://braukaiser.com/documents/effect_of_water_and_grist_on_mash_pH.pdf
 
-        let grain_weight = self.grain_weight();
-
-        // base malt term (all steps)
-        let base_malt_ph: f32 = {
-            let mut ph: f32 = 0.0;
-            for dose in self.malt_doses() {
-                if dose.malt.category() != MaltCategory::Base {
-                    continue;
-                }
-
-                let proportion = dose.weight.0 / grain_weight.0;
-                let phbi = dose.malt.distilled_water_mash_ph().unwrap_or(Ph(5.75));
+        let mut weighted_ph: f32 = 0.0;
+        let mut buffering: f32 = 0.0;
+        for dose in self.malt_doses() {
+            let malt_buffering = malt_buffering(dose.malt);
+            let phdi = match dose.malt.distilled_water_mash_ph() {
+                Some(ph) => ph.0,
+                None if dose.malt.category() == MaltCategory::Base => 5.75,
+                None => 5.7 - dose.malt.acidity() / malt_buffering,
+            };
 
-                ph += phbi.0 * proportion;
-            }
+            weighted_ph += dose.weight.0 * malt_buffering * phdi;
+            buffering += dose.weight.0 * malt_buffering;
+        }
 
-            ph
+        let ph = if buffering > 0.0 {
+            Ph(weighted_ph / buffering)
+        } else {
+            Ph(5.75)
         };
 
-        // specialty malt term (all steps)
-        let specialty_malt_ph_term_one: f32 = {
-            let mut ph: f32 = 0.0;
-            for dose in self.malt_doses() {
-                if dose.malt.category() == MaltCategory::Base {
-                    continue;
-                }
-
-                let proportion = dose.weight.0 / grain_weight.0;
+        vec![ph; self.mash_thicknesses().len()]
+    }
 
-                ph += 5.7 * proportion;
-            }
+    /// Estimated mash pH prior to acid additions
+    ///
+    /// The residual alkalinity of the mash water (after salts) pushes the
+    /// pH up from the distilled water mash pH, against the buffering of
+    /// the grist. Thicker mashes have less water and so shift less.
+    ///
+    /// Residual alkalinity includes Kolbach's hardness term, which is the
+    /// pH drop from calcium and magnesium reacting with malt phosphates.
+    /// So calcium salts (gypsum, calcium chloride) lower the mash pH here
+    /// even though they add no acid.
+    ///
+    /// Finally, more dilute mashes read slightly higher, by about 0.05 pH
+    /// per doubling of the mash thickness (relative to 3 L/kg).
+    #[must_use]
+    pub fn mash_ph_preacid(&self) -> Vec<Ph> {
+        self.mash_ph_with(&self.adjusted_water_profile_preacid())
+    }
 
-            ph
-        };
+    /// Estimated mash pH with the given mash water, without acids
+    fn mash_ph_with(&self, water: &WaterProfile) -> Vec<Ph> {
+        let distilled = self.mash_ph_distilled();
 
-        // specialty malt term (per step, thickness-dependent)
-        let specialty_malt_ph_term_two: Vec<f32> = {
-            let mut acidity: f32 = 0.0;
-            for dose in self.malt_doses() {
-                if dose.malt.category() == MaltCategory::Base {
-                    continue;
-                }
+        // mEq per liter of water, as CaCO3 is 50 mg/mEq
+        let ra = water.residual_alkalinity();
+        let ra_meq_per_liter = ra.0 / 50.0;
 
-                let proportion = dose.weight.0 / grain_weight.0;
+        let buffering = self.mash_buffering_capacity();
+        if buffering <= 0.0 {
+            return distilled;
+        }
 
-                acidity += dose.malt.acidity() * proportion;
-            }
-
-            let mut output: Vec<f32> = Vec::new();
-            let mash_thicknesses = self.mash_thicknesses();
-            for mt in &mash_thicknesses {
-                let ph_adjust = -0.14 * acidity / mt;
-                output.push(ph_adjust);
-            }
-
-            output
-        };
-
-        let mut output: Vec<Ph> = Vec::new();
-
-        for t2 in &specialty_malt_ph_term_two {
-            output.push(Ph(base_malt_ph + specialty_malt_ph_term_one + t2));
-        }
-
-        output
-    }
-
-    /// Estimated mash pH prior to acid additions
-    #[must_use]
-    pub fn mash_ph_preacid(&self) -> Vec<Ph> {
-        // Start from the pH effect of the grains only
-        let mut output = self.mash_ph_distilled();
-
-        // from https://byo.com/articles/understanding-residual-alkalinity-ph/
-        // pH shift = 0.00168 * RA (as CaCO3) or pH shift = 0.084 * RA (as mEq/L)
-        let ra = self.adjusted_water_profile_preacid().residual_alkalinity();
-        let shift = 0.00168 * ra.0;
-
-        for out in &mut output {
-            out.0 += shift;
-        }
-
-        output
+        let grain_weight = self.grain_weight();
+        distilled
+            .iter()
+            .zip(self.mash_thicknesses())
+            .map(|(ph, mt)| {
+                let water_shift = ra_meq_per_liter * mt * grain_weight.0 / buffering;
+                let thickness_shift = 0.05 * (mt / 3.0).log2();
+                *ph + water_shift + thickness_shift
+            })
+            .collect()
     }
 
     /// Estimated mash pH
@@ -442,13 +1038,11 @@ impl Process {
 
         let acids = self.water_acids();
 
+        let buffering = self.mash_buffering_capacity();
         for out in &mut output {
-            // FIXME this is only for Lactic Acid 88%
             for acid in &acids {
-                // FIXME this is not very accurate
-                // 25-80 ppm to drop by 0.15 pH
-                let shift = acid.ppm.0 / 300.0;
-                out.0 -= shift;
+                let meq = acid.ppm.0 / 1000.0 * acid.acid.meq_per_gram() * self.mash_volume().0;
+                *out -= meq / buffering;
             }
         }
 
@@ -467,6 +1061,11 @@ impl Process {
                     .sugar_doses()
                     .iter()
                     .map(|dose| dose.weight.0)
+                    .sum::<f32>()
+                + self
+                    .extract_doses()
+                    .iter()
+                    .map(|dose| dose.weight.0)
                     .sum::<f32>(),
         )
     }
@@ -496,6 +1095,16 @@ impl Process {
             .unwrap();
         }
 
+        for extract_dose in self.extract_doses() {
+            let percent = 100.0 * extract_dose.weight.0 / self.fermentables_weight().0;
+            writeln!(
+                output,
+                "{} {:.1}% {}",
+                extract_dose.weight, percent, extract_dose.extract
+            )
+            .unwrap();
+        }
+
         writeln!(output, "Total Malt Weight = {}", self.grain_weight()).unwrap();
         write!(
             output,
@@ -513,16 +1122,52 @@ impl Process {
         self.brewery.grain_absorption_per_kg * self.grain_weight().0
     }
 
+    /// Whether the whole water volume goes into the mash, with no sparge
+    #[must_use]
+    pub fn is_full_volume_mash(&self) -> bool {
+        matches!(
+            self.recipe.mash_method,
+            MashMethod::NoSparge | MashMethod::Biab
+        )
+    }
+
+    /// Mash efficiency, a few points lower than the brewery's when not
+    /// sparging since more sugar stays behind in the grain
+    #[must_use]
+    pub fn mash_efficiency(&self) -> f32 {
+        if self.is_full_volume_mash() {
+            self.brewery.mash_efficiency - 0.04
+        } else {
+            self.brewery.mash_efficiency
+        }
+    }
+
     /// The pre-sparge volume
     #[must_use]
     pub fn pre_sparge_volume(&self) -> Liters {
+        if !self.has_mash() {
+            return Liters(0.0);
+        }
+        if self.is_full_volume_mash() {
+            return self.pre_lauter_volume();
+        }
         Liters(self.grain_weight().0 * self.recipe.mash_thickness - self.water_absorption().0)
     }
 
     /// The post-sparge volume
     #[must_use]
     pub fn pre_lauter_volume(&self) -> Liters {
-        self.pre_boil_volume() + self.brewery.mash_tun_losses
+        self.pre_boil_volume() + self.lauter_losses()
+    }
+
+    /// Wort left behind in the mash tun, if there is a mash
+    #[must_use]
+    pub fn lauter_losses(&self) -> Liters {
+        if self.has_mash() {
+            self.brewery.mash_tun_losses
+        } else {
+            Liters(0.0)
+        }
     }
 
     /// The volume at the end of the mash, before losses from grain absorption
@@ -537,6 +1182,40 @@ impl Process {
         self.pre_lauter_volume() - self.pre_sparge_volume()
     }
 
+    /// Gravity of the first runnings, drained before sparging.
+    ///
+    /// We model a batch sparge: the mash liquid is at the first runnings
+    /// gravity, and the sparge water mixes with what the grain absorbed.
+    /// The collected runnings together hold the pre-boil extract.
+    ///
+    /// None if there is no mash.
+    #[must_use]
+    pub fn first_runnings_gravity(&self) -> Option<SpecificGravity> {
+        if !self.has_mash() {
+            return None;
+        }
+        let collected_extract = self.pre_boil_gravity().points() * self.pre_lauter_volume().0;
+
+        let absorbed = self.water_absorption().0;
+        let sparge = self.sparge_volume().0;
+        let sparge_share = absorbed * sparge / (absorbed + sparge);
+
+        let volume = Liters(self.pre_sparge_volume().0 + sparge_share);
+        Some(GravityPoints::per_volume(collected_extract, volume).into())
+    }
+
+    /// Gravity of the sparge runnings.
+    ///
+    /// None if there is no mash.
+    #[must_use]
+    pub fn sparge_runnings_gravity(&self) -> Option<SpecificGravity> {
+        let absorbed = self.water_absorption().0;
+        let sparge = self.sparge_volume().0;
+
+        let points = GravityPoints::from(self.first_runnings_gravity()?);
+        Some((points * (absorbed / (absorbed + sparge))).into())
+    }
+
     /// Strike volume
     #[must_use]
     pub fn strike_volume(&self) -> Liters {
@@ -549,6 +1228,11 @@ impl Process {
         let mut current_water = self.mash_volume();
         let mut current_temp: Option<Celsius> = None;
 
+        // Decoctions add no water
+        if self.recipe.decoction || !self.has_mash() {
+            return current_water;
+        }
+
         // Iterate backwards through each mash rest
         for rest in self.recipe.mash_rests.clone().into_iter().rev() {
             if let Some(current_tmp) = current_temp {
@@ -559,6 +1243,7 @@ impl Process {
                     rest.target_temperature,
                     current_tmp,
                     self.brewery.infusion_temperature,
+                    self.brewery.mash_tun_thermal_mass,
                 );
 
                 // Subtract that much water
@@ -573,23 +1258,53 @@ impl Process {
     }
 
     /// Strike temperature
+    ///
+    /// Unless the mash tun is preheated, the strike water also has to heat
+    /// the mash tun up from room temperature.
+    ///
+    /// None if there is no mash.
+    #[must_use]
+    pub fn strike_temperature(&self) -> Option<Celsius> {
+        self.strike_temperature_with(self.brewery.preheat_mash_tun)
+    }
+
+    /// Strike temperature, given whether the mash tun is preheated
+    ///
+    /// None if there is no mash.
     #[must_use]
-    pub fn strike_temperature(&self) -> Celsius {
-        crate::mash::strike_water_temp(
+    pub fn strike_temperature_with(&self, preheated: bool) -> Option<Celsius> {
+        if !self.has_mash() {
+            return None;
+        }
+        let first_rest = self.recipe.mash_rests.first()?;
+
+        let tun_thermal_mass = if preheated {
+            Liters(0.0)
+        } else {
+            self.brewery.mash_tun_thermal_mass
+        };
+
+        Some(crate::mash::strike_water_temp(
             self.strike_volume(),
             self.grain_weight(),
             self.brewery.room_temperature,
-            self.recipe.mash_rests[0].target_temperature,
-        )
+            first_rest.target_temperature,
+            tun_thermal_mass,
+        ))
     }
 
     /// Mash steps
     ///
-    /// This gives you the volume of infusion water for each step.
+    /// This gives you how the mash is raised to each rest after the first,
+    /// by infusion or by decoction, and the volume involved. A decoction
+    /// that cannot reach its rest pulls nothing.
     #[allow(clippy::missing_panics_doc)]
     #[must_use]
-    pub fn mash_infusions(&self) -> Vec<Liters> {
-        let mut infusions: Vec<Liters> = Vec::new();
+    pub fn mash_steps(&self) -> Vec<MashStep> {
+        let mut steps: Vec<MashStep> = Vec::new();
+        if !self.has_mash() {
+            return steps;
+        }
 
         let mut current_water = self.strike_volume();
         let mut current_temp: Option<Celsius> = None;
@@ -601,27 +1316,65 @@ impl Process {
             }
             let cur_tmp = current_temp.unwrap();
 
-            let infusion = crate::mash::mash_infusion(
-                self.grain_weight(),
-                current_water,
-                cur_tmp,
-                rest.target_temperature,
-                self.brewery.infusion_temperature,
-            );
+            if self.recipe.decoction {
+                let volume = crate::mash::decoction_volume(
+                    self.grain_weight(),
+                    current_water,
+                    cur_tmp,
+                    rest.target_temperature,
+                    self.boiling_point(),
+                )
+                .unwrap_or(Liters(0.0));
+                let mash_volume = crate::mash::mash_volume(self.grain_weight(), current_water);
+                steps.push(MashStep::Decoction {
+                    target_temperature: rest.target_temperature,
+                    fraction_pulled: volume.0 / mash_volume.0,
+                    volume,
+                });
+            } else {
+                let infusion = crate::mash::mash_infusion(
+                    self.grain_weight(),
+                    current_water,
+                    cur_tmp,
+                    rest.target_temperature,
+                    self.brewery.infusion_temperature,
+                    self.brewery.mash_tun_thermal_mass,
+                );
 
-            infusions.push(infusion);
-            current_water = current_water + infusion;
+                steps.push(MashStep::Infusion {
+                    target_temperature: rest.target_temperature,
+                    volume: infusion,
+                });
+                current_water = current_water + infusion;
+            }
             current_temp = Some(rest.target_temperature);
         }
 
-        infusions
+        steps
+    }
+
+    /// Mash infusions
+    ///
+    /// This gives you the volume of infusion water for each infusion step.
+    #[must_use]
+    pub fn mash_infusions(&self) -> Vec<Liters> {
+        self.mash_steps()
+            .into_iter()
+            .filter_map(|step| match step {
+                MashStep::Infusion { volume, .. } => Some(volume),
+                MashStep::Decoction { .. } => None,
+            })
+            .collect()
     }
 
     /// Thickness of the mash, in liters of water per kilograms of
-    /// grain, at each mash step.
+    /// grain, at each mash step. Empty if there is no mash.
     #[must_use]
     pub fn mash_thicknesses(&self) -> Vec<f32> {
         let mut thicknesses: Vec<f32> = Vec::new();
+        if !self.has_mash() {
+            return thicknesses;
+        }
 
         let mut liters = self.strike_volume();
         thicknesses.push(liters.0 / self.grain_weight().0);
@@ -635,10 +1388,14 @@ impl Process {
     }
 
     /// Number of yeast cells needed for a good pitch
+    ///
+    /// If the age of the yeast is given, this is scaled up to make up for
+    /// cells that are no longer viable.
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
     #[allow(clippy::cast_sign_loss)]
-    pub fn yeast_cells(&self) -> u64 {
+    pub fn yeast_cells(&self, age: Option<Days>) -> u64 {
         let ml: Milliliters = self.batch_size.into();
         let plato: Plato = self.recipe.original_gravity.into();
         let mut pitch_rate: u64 = self.recipe.yeast.pitching_rate_cmlp();
@@ -646,13 +1403,19 @@ impl Process {
             pitch_rate *= 600;
             pitch_rate /= 750;
         }
-        pitch_rate * (ml.0 * plato.0) as u64
+        let cells = pitch_rate * (ml.0 * plato.0) as u64;
+        match age {
+            Some(age) => (cells as f64 / f64::from(self.recipe.yeast.viability(age))) as u64,
+            None => cells,
+        }
     }
 
-    /// Grams of yeast needed for pitch
+    /// Grams of yeast needed for pitch, scaled up for viability loss if
+    /// the age of the yeast is given
     #[must_use]
     #[allow(clippy::cast_precision_loss)]
-    pub fn yeast_grams(&self) -> Option<Grams> {
+    pub fn yeast_grams(&self, age: Option<Days>) -> Option<Grams> {
+        let viability = age.map_or(1.0, |age| self.recipe.yeast.viability(age));
         if let Some(ghl_range) = self.recipe.yeast.pitching_rate_range_ghl() {
             // We assume their low number maps to 1.035
             // We assume their high number maps to 1.070
@@ -667,9 +1430,9 @@ impl Process {
             };
 
             let ghl = ghl_range.start + (ghl_range.end - ghl_range.start) * fraction_in;
-            Some(Grams(ghl / 100.0 * self.batch_size.0))
+            Some(Grams(ghl / 100.0 * self.batch_size.0 / viability))
         } else {
-            let cells = self.yeast_cells();
+            let cells = self.yeast_cells(age);
             if self.recipe.yeast.is_dry() {
                 Some(Grams((cells / Yeast::CELLS_PER_GRAM_DRY) as f32))
             } else {
@@ -678,36 +1441,60 @@ impl Process {
         }
     }
 
+    /// Packages of yeast to buy for the pitch (sachets of dry yeast,
+    /// or vials or smack packs of liquid yeast)
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn yeast_packages(&self) -> u32 {
+        let packages = if self.recipe.yeast.is_dry()
+            && let Some(grams) = self.yeast_grams(None)
+        {
+            grams.0 / Yeast::GRAMS_PER_DRY_PACKAGE
+        } else {
+            self.yeast_cells(None) as f32 / Yeast::CELLS_PER_LIQUID_PACKAGE as f32
+        };
+        (packages.ceil() as u32).max(1)
+    }
+
     /// Estimated FAN amount in the wort from malts, in ppm (mg/L)
     #[must_use]
     pub fn fan_from_malt(&self) -> Ppm {
         let mut total: Ppm = Ppm(0.0);
-        let gallons: Gallons = self.batch_size.into();
         for malt_dose in self.malt_doses() {
-            let pounds: Pounds = malt_dose.weight.into();
             let points = {
-                let ppg = malt_dose.malt.ppg(); // points/(pounds*gallons) at 100% eff.
-                self.brewery.mash_efficiency * ppg * pounds.0 / gallons.0
+                let pkl = malt_dose.malt.pkl(); // points/(kg*liters) at 100% eff.
+                self.mash_efficiency() * pkl * malt_dose.weight.0 / self.batch_size.0
             };
             let malt_fan_per_point: Ppm = malt_dose.malt.fan() / 40.0;
             total = total + malt_fan_per_point * points;
         }
 
+        // Extract was already mashed, so mash efficiency does not apply
+        for extract_dose in self.extract_doses() {
+            let points = extract_dose.extract.pkl() * extract_dose.weight.0 / self.batch_size.0;
+            let extract_fan_per_point: Ppm = extract_dose.extract.fan() / 40.0;
+            total = total + extract_fan_per_point * points;
+        }
+
         total
     }
 
-    /// Yeast nutrient needed
+    /// Yeast nutrient needed, of the brewery's `yeast_nutrient`
     #[must_use]
     pub fn yeast_nutrient_amount(&self) -> Grams {
+        let nutrient = self.brewery.yeast_nutrient;
+        if nutrient.is_dosed_for_zinc() {
+            // It barely supplies FAN, so dose it to the zinc target
+            return Grams(ZINC_TARGET.0 * self.batch_size.0 / nutrient.zinc_per_gram());
+        }
+
         if self.recipe.fan_requirement_of_yeast() < self.fan_from_malt() {
             Grams(0.0)
         } else {
             let ppm_needed = self.recipe.fan_requirement_of_yeast().0 - self.fan_from_malt().0;
-
-            // yeast nutrient generally provides about 0.4 ppm
-            // per gram in one hectoliter
-            // FIXME: ONLY in high FAN nutrients
-            let fan_rate = 0.4 * 100.0; // ppm*L/g
+            let fan_rate = self.brewery.yeast_nutrient.fan_per_gram(); // ppm*L/g
 
             Grams(
                 self.batch_size.0         // L
@@ -720,10 +1507,7 @@ impl Process {
     /// Total wort FAN from malts and yeast nutrient
     #[must_use]
     pub fn wort_fan(&self) -> Ppm {
-        // yeast nutrient generally provides about 0.4 ppm
-        // per gram in one hectoliter
-        // FIXME: ONLY in high FAN nutrients
-        let fan_rate = 0.4 * 100.0; // ppm*L/g
+        let fan_rate = self.brewery.yeast_nutrient.fan_per_gram(); // ppm*L/g
 
         let nutrient_ppm = Ppm(
             self.yeast_nutrient_amount().0       // g
@@ -734,7 +1518,7 @@ impl Process {
         self.fan_from_malt() + nutrient_ppm
     }
 
-    /// Zinc drops needed
+    /// Supplemental zinc needed, beyond what the yeast nutrient provides
     #[must_use]
     pub fn zinc_needed(&self) -> Milligrams {
         // Different sourcess quote different Zn ranges:
@@ -745,39 +1529,196 @@ impl Process {
         // JP says 0.1-0.3 mg/L with 0.5 as a maximum
         //
         // Wyeast packs already has zinc. TODO.
+        let target = ZINC_TARGET.0 * self.batch_size.0;
+        let from_nutrient = self.nutrient_zinc().0 * self.batch_size.0;
 
-        Milligrams(0.2 * self.batch_size.0)
+        Milligrams((target - from_nutrient).max(0.0))
     }
 
-    /// The estimated gravity after fermentation, before any dilution
-    // TODO: this doesn't adjust for the presence of many
-    //       unfermentable sugars
+    /// Zinc supplied by the yeast nutrient
     #[must_use]
-    pub fn post_ferment_gravity(&self) -> SpecificGravity {
-        let og = self.recipe.original_gravity;
+    pub fn nutrient_zinc(&self) -> Ppm {
+        let mg = self.yeast_nutrient_amount().0 * self.brewery.yeast_nutrient.zinc_per_gram();
+        Ppm(mg / self.batch_size.0)
+    }
+
+    /// How the mash temperature shifts the fermentability of the wort,
+    /// relative to a mash at 66°C.
+    ///
+    /// Cooler saccharification rests favour beta amylase and make a more
+    /// fermentable wort, hotter ones favour alpha amylase and leave more
+    /// dextrins. Rests are weighted by their duration, and rests below
+    /// 60°C (which do not convert much starch) are ignored. Without a
+    /// mash there is no shift.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mash_fermentability(&self) -> f32 {
+        if !self.has_mash() {
+            return 1.0;
+        }
+
+        let mut minutes: f32 = 0.0;
+        let mut degree_minutes: f32 = 0.0;
+        for rest in &self.recipe.mash_rests {
+            if rest.target_temperature >= Celsius(60.0) {
+                minutes += rest.duration.0 as f32;
+                degree_minutes += rest.duration.0 as f32 * rest.target_temperature.0;
+            }
+        }
+        if minutes <= 0.0 {
+            return 1.0;
+        }
+
+        let temperature = degree_minutes / minutes;
+        (1.0 + 0.02 * (66.0 - temperature)).clamp(0.8, 1.1)
+    }
+
+    /// Estimated time for the mash to convert, when an iodine test will
+    /// show no more starch
+    ///
+    /// This is about 30 minutes for well-modified base malt at 65°C,
+    /// halving for every 4°C hotter, at the primary (longest
+    /// saccharification) rest. Grists with less base malt, such as those
+    /// with a lot of unmalted adjuncts, take longer.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn estimated_conversion_time(&self) -> Minutes {
+        let primary = self
+            .recipe
+            .mash_rests
+            .iter()
+            .filter(|rest| rest.target_temperature >= Celsius(60.0))
+            .max_by_key(|rest| rest.duration)
+            .or_else(|| {
+                self.recipe
+                    .mash_rests
+                    .iter()
+                    .max_by(|a, b| a.target_temperature.0.total_cmp(&b.target_temperature.0))
+            });
+        let temperature = primary.map_or(65.0, |rest| rest.target_temperature.0);
+
+        let grain = self.grain_weight().0;
+        let base: f32 = self
+            .malt_doses()
+            .iter()
+            .filter(|dose| dose.malt.category() == MaltCategory::Base)
+            .map(|dose| dose.weight.0)
+            .sum();
+        let modified = if grain > 0.0 { base / grain } else { 1.0 };
+
+        let minutes = 30.0 * 2.0_f32.powf((65.0 - temperature) / 4.0) / modified.max(0.3);
+        Minutes(minutes.round() as usize)
+    }
 
-        let mut attenuation = self.recipe.yeast.attenuation();
+    /// The apparent attenuation expected from this recipe.
+    ///
+    /// The yeast's attenuation applies to the extract of a pale base malt
+    /// wort. Each fermentable shifts it by its own fermentability,
+    /// weighted by the gravity points it contributes, and the mash
+    /// temperature shifts the malts. Malt wort cannot be attenuated beyond
+    /// what the yeast is capable of, but simple sugars ferment out.
+    #[must_use]
+    pub fn effective_attenuation(&self) -> f32 {
+        let yeast = self.recipe.yeast;
+        let base = yeast.attenuation();
+        let capability = yeast.attenuation_range().end;
+        let mash = self.mash_fermentability();
+        let efficiency = self.mash_efficiency();
 
-        let mut reduction_percent: f32 = 0.0;
+        let mut points: f32 = 0.0;
+        let mut attenuated: f32 = 0.0;
+
+        for dose in &self.malt_doses() {
+            let pts = dose.malt.pkl() * dose.weight.0 * efficiency;
+            let attenuation = (base * dose.malt.fermentability() * mash).min(capability);
+            points += pts;
+            attenuated += pts * attenuation;
+        }
 
-        for malt_dose in &self.malt_doses() {
-            if malt_dose.malt.category() == MaltCategory::Crystal {
-                let pounds: Pounds = malt_dose.weight.into();
-                let lovabond: Lovabond = malt_dose.malt.ebc().into();
-                let gallons: Gallons = self.batch_size.into();
-                // for 1 pound in a in a 5 gallon batch.
-                let mult = (pounds.0 / gallons.0) / (1.0 / 5.0);
+        for dose in &self.sugar_doses() {
+            let pts = dose.sugar.pkl() * dose.weight.0;
+            let sugar = dose.sugar;
+            let attenuation =
+                sugar.fermentability() / (sugar.fermentability() + sugar.unfermentability());
+            points += pts;
+            attenuated += pts * attenuation;
+        }
 
-                // 10 lovabond -> 1%, 60 lovabond -> 3%
-                reduction_percent += (0.0004 * lovabond.0 + 0.006) * mult;
-            }
+        // Extract is made from a hotter, less fermentable mash
+        for dose in &self.extract_doses() {
+            let pts = dose.extract.pkl() * dose.weight.0;
+            points += pts;
+            attenuated += pts * (base * 0.9).min(capability);
+        }
+
+        if points <= f32::EPSILON {
+            return base;
+        }
+
+        attenuated / points
+    }
+
+    /// Dissolved oxygen to aim for when pitching
+    ///
+    /// Dry yeast is made with plenty of sterols and needs none. Liquid
+    /// yeast needs about 8 ppm for a normal gravity wort, rising with the
+    /// gravity. Lagers, which are pitched cold and at a higher rate, need
+    /// about 1 ppm more.
+    #[must_use]
+    pub fn target_dissolved_oxygen(&self) -> Ppm {
+        if self.recipe.yeast.is_dry() {
+            return Ppm(0.0);
+        }
 
-            // TODO: dextrin / carapils, chocolate roast, etc.
+        let points = self.recipe.original_gravity.points();
+        let mut ppm = 8.0 + (points - 40.0).max(0.0) * 0.075;
+        if self.recipe.yeast.is_lager() {
+            ppm += 1.0;
         }
+        Ppm(ppm.min(14.0))
+    }
+
+    /// Seconds of pure oxygen through a 0.5 micron stone at 1 L/min to
+    /// reach the `target_dissolved_oxygen()`.
+    ///
+    /// About 60 seconds gives 8-10 ppm in 19 liters.
+    #[must_use]
+    pub fn pure_oxygen_seconds(&self) -> f32 {
+        self.target_dissolved_oxygen().0 / 9.0 * 60.0 * self.batch_size.0 / 19.0
+    }
+
+    /// Gravity points left in the beer by unfermentable sugars (such as
+    /// lactose), before any dilution. These add sweetness and body.
+    #[must_use]
+    pub fn unfermentable_sugar_points(&self) -> f32 {
+        self.sugar_doses()
+            .iter()
+            .filter(|dose| dose.sugar.is_unfermentable())
+            .map(|dose| {
+                let sugar = dose.sugar;
+                let unfermentable =
+                    sugar.unfermentability() / (sugar.fermentability() + sugar.unfermentability());
+                sugar.pkl() * dose.weight.0 * unfermentable / self.batch_size.0
+            })
+            .sum()
+    }
+
+    /// The estimated gravity after fermentation, before any dilution
+    #[must_use]
+    pub fn post_ferment_gravity(&self) -> SpecificGravity {
+        let og = self.recipe.original_gravity;
 
-        attenuation *= 1.0 - reduction_percent;
+        let mut attenuation = self.effective_attenuation();
 
-        SpecificGravity(og.0 - (og.0 - 1.0) * attenuation)
+        // STA1 positive yeast breaks down dextrins, and keeps attenuating
+        // well past its listed attenuation, so we go halfway to a
+        // super-attenuated 95%
+        if self.recipe.yeast.is_sta1_positive() == Some(true) {
+            attenuation += (0.95 - attenuation) * 0.5;
+        }
+
+        (GravityPoints::from(og) * (1.0 - attenuation)).into()
     }
 
     /// The post-fermentation dilution fraction to apply to achieve closer to
@@ -785,10 +1726,11 @@ impl Process {
     #[must_use]
     pub fn post_fermentation_dilution_fraction(&self) -> f32 {
         if let Some(target_abv) = self.recipe.target_abv {
-            let natural_abv = Abv::from_gravity(
+            let natural_abv = Abv::from_gravity_model(
                 self.recipe.original_gravity,
                 self.post_ferment_gravity(),
                 1.0,
+                self.recipe.abv_model,
             );
             if natural_abv > target_abv {
                 return (natural_abv.0 / target_abv.0).min(self.recipe.max_post_ferment_dilution);
@@ -813,111 +1755,211 @@ impl Process {
         self.post_ferment_volume() + self.post_fermentation_dilution()
     }
 
-    /// Volume history
+    /// How much the gas in the fermenter headspace contracts when the
+    /// beer is crashed from the diacetyl rest down to 0°C
+    ///
+    /// This is how much will be pulled back in through the airlock or
+    /// blow-off tube. It is at least this much, as the cold beer also
+    /// dissolves some of the CO2.
     #[must_use]
-    pub fn volume_history_string(&self) -> String {
-        let mut output: String = String::new();
-        let mut total = self.strike_volume();
+    pub fn crash_vacuum_volume(&self) -> Liters {
+        self.crash_vacuum_volume_to(Celsius(0.0))
+    }
 
-        writeln!(
-            output,
-            "Strike         +{}     = {total}  strike volume",
-            self.strike_volume()
-        )
-        .unwrap();
+    /// How much the gas in the fermenter headspace contracts when the
+    /// beer is crashed from the diacetyl rest down to the given temperature
+    ///
+    /// The headspace is that left above the beer once fermentation is
+    /// done; any dilution comes later. By the ideal gas law the volume
+    /// scales with absolute temperature.
+    #[must_use]
+    pub fn crash_vacuum_volume_to(&self, crash_temperature: Celsius) -> Liters {
+        let headspace = self.fermenter_volume() - self.post_ferment_volume();
+        if headspace.0 <= 0.0 {
+            return Liters(0.0);
+        }
+        let warm = self.recipe.diacetyl_rest_temperature().0 + 273.15;
+        let cold = crash_temperature.0 + 273.15;
+        if cold >= warm {
+            return Liters(0.0);
+        }
+        headspace * (1.0 - cold / warm)
+    }
 
-        for infusion in self.mash_infusions() {
-            total = total + infusion;
+    /// Priming sugar for the whole batch across a span of beer
+    /// temperatures, as the residual CO2 depends on the temperature.
+    ///
+    /// Empty unless packaging in bottles with a fermentable sugar.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn priming_table(&self) -> Vec<(Celsius, Grams)> {
+        let Packaging::Bottle(_, sugar) = self.brewery.packaging else {
+            return Vec::new();
+        };
+
+        (1..=6)
+            .filter_map(|i| {
+                let temp = Celsius(4.0 * i as f32);
+                sugar
+                    .priming_amount(
+                        self.recipe.style.carbonation_volume(),
+                        self.product_volume(),
+                        temp,
+                    )
+                    .map(|amount| (temp, amount))
+            })
+            .collect()
+    }
+
+    /// Volume history: each gain or loss, with the running total and a
+    /// description of the resulting volume
+    #[must_use]
+    pub fn volume_history(&self) -> Vec<(&'static str, Liters, Liters, &'static str)> {
+        let mut history: Vec<(&'static str, Liters, Liters, &'static str)> = Vec::new();
+        let mut total = Liters(0.0);
+        let mut step = |label: &'static str, change: Liters, description: &'static str| {
+            total = total + change;
+            history.push((label, change, total, description));
+        };
+
+        if self.has_mash() {
+            step("Strike", self.strike_volume(), "strike volume");
+            for infusion in self.mash_infusions() {
+                step("Mash Infusion:", infusion, "mash volume");
+            }
+            step(
+                "Absorption:",
+                Liters(-self.water_absorption().0),
+                "pre sparge volume",
+            );
+            step("Sparge:", self.sparge_volume(), "pre lauter volume");
+            step(
+                "Lauter loss:",
+                Liters(-self.lauter_losses().0),
+                "pre boil volume",
+            );
+        } else {
+            step("Water:", self.pre_boil_volume(), "pre boil volume");
+        }
+        step(
+            "Boil off:",
+            Liters(-self.boil_evaporation().0),
+            "post boil pre loss volume",
+        );
+        step(
+            "Hops Absorbed:",
+            Liters(-self.hops_absorption().0),
+            "post hops volume",
+        );
+        step(
+            "Kettle Losses:",
+            Liters(-self.brewery.kettle_losses.0),
+            "post boil volume",
+        );
+        step("Dilution:", self.partial_boil_dilution(), "batch size");
+        step(
+            "Ferment Loss:",
+            Liters(-self.ferment_losses().0),
+            "post ferment volume",
+        );
+        step(
+            "Dilution:",
+            self.post_fermentation_dilution(),
+            "product volume",
+        );
+
+        history
+    }
+
+    /// Volume history as a string
+    #[must_use]
+    pub fn volume_history_string(&self) -> String {
+        let mut output: String = String::new();
+        for (label, change, total, description) in self.volume_history() {
+            let sign = if change.0 < 0.0 { '-' } else { '+' };
+            let change = Liters(change.0.abs());
             writeln!(
                 output,
-                "Mash Infusion: +{infusion}     = {total}  mash volume"
+                "{label:<15}{sign}{change}     = {total}  {description}"
             )
             .unwrap();
         }
+        output
+    }
 
-        total = total - self.water_absorption();
-        writeln!(
-            output,
-            "Absorption:    -{}     = {total}  pre sparge volume",
-            self.water_absorption()
-        )
-        .unwrap();
+    /// The volume flow as a Graphviz DOT graph, with each volume as a
+    /// node and each gain or loss on the edges.
+    #[must_use]
+    pub fn volume_flow_dot(&self) -> String {
+        let infusions: Liters = self.mash_infusions().into_iter().sum();
+
+        let nodes: [(&str, Liters); 10] = [
+            ("strike_volume", self.strike_volume()),
+            ("mash_volume", self.mash_volume()),
+            ("pre_sparge_volume", self.pre_sparge_volume()),
+            ("pre_lauter_volume", self.pre_lauter_volume()),
+            ("pre_boil_volume", self.pre_boil_volume()),
+            (
+                "post_boil_pre_loss_volume",
+                self.post_boil_pre_loss_volume(),
+            ),
+            ("post_boil_volume", self.post_boil_volume()),
+            ("batch_size", self.batch_size),
+            ("post_ferment_volume", self.post_ferment_volume()),
+            ("product_volume", self.product_volume()),
+        ];
+
+        let edges: [String; 9] = [
+            format!("+{infusions} infusions"),
+            format!("-{} absorption", self.water_absorption()),
+            format!("+{} sparge", self.sparge_volume()),
+            format!("-{} lauter loss", self.lauter_losses()),
+            format!("-{} boil off", self.boil_evaporation()),
+            format!(
+                "-{} kettle losses",
+                self.brewery.kettle_losses + self.hops_absorption()
+            ),
+            format!("+{} dilution", self.partial_boil_dilution()),
+            format!("-{} ferment loss", self.ferment_losses()),
+            format!("+{} dilution", self.post_fermentation_dilution()),
+        ];
+
+        let mut output: String = "digraph volume_flow {\n    rankdir=TB;\n".to_string();
+
+        for (name, volume) in &nodes {
+            let _ = writeln!(output, "    {name} [label=\"{name}\\n{volume}\"];");
+        }
 
-        total = total + self.sparge_volume();
-        writeln!(
-            output,
-            "Sparge:        +{}     = {total}  pre lauter volume",
-            self.sparge_volume()
-        )
-        .unwrap();
+        for (i, label) in edges.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "    {} -> {} [label=\"{label}\"];",
+                nodes[i].0,
+                nodes[i + 1].0
+            );
+        }
 
-        total = total - self.brewery.mash_tun_losses;
-        writeln!(
-            output,
-            "Lauter loss:   -{}     = {total}  pre boil volume",
-            self.brewery.mash_tun_losses
-        )
-        .unwrap();
+        output.push_str("}\n");
+        output
+    }
 
-        total = total - self.boil_evaporation();
-        writeln!(
-            output,
-            "Boil off:      -{}     = {total}  post boil pre loss volume",
-            self.boil_evaporation()
+    /// ABV in %
+    #[must_use]
+    pub fn abv(&self) -> Abv {
+        Abv::from_gravity_model(
+            self.recipe.original_gravity,
+            self.post_ferment_gravity(),
+            self.post_fermentation_dilution_fraction(),
+            self.recipe.abv_model,
         )
-        .unwrap();
-
-        total = total - self.brewery.kettle_losses;
-        writeln!(
-            output,
-            "Kettle Losses: -{}     = {total}  post boil volume",
-            self.brewery.kettle_losses
-        )
-        .unwrap();
-
-        total = total + self.partial_boil_dilution();
-        writeln!(
-            output,
-            "Dilution:      +{}     = {total}  batch size",
-            self.partial_boil_dilution()
-        )
-        .unwrap();
-
-        total = total - self.ferment_losses();
-        writeln!(
-            output,
-            "Ferment Loss:  -{}     = {total}  post ferment volume",
-            self.ferment_losses()
-        )
-        .unwrap();
-
-        total = total + self.post_fermentation_dilution();
-        writeln!(
-            output,
-            "Dilution:      +{}     = {total}  product volume",
-            self.post_fermentation_dilution()
-        )
-        .unwrap();
-
-        output
-    }
-
-    /// ABV in %
-    #[must_use]
-    pub fn abv(&self) -> Abv {
-        Abv::from_gravity(
-            self.recipe.original_gravity,
-            self.post_ferment_gravity(),
-            self.post_fermentation_dilution_fraction(),
-        )
-    }
+    }
 
     /// Final gravity
     #[must_use]
     pub fn final_gravity(&self) -> SpecificGravity {
-        let points = self.post_ferment_gravity().0 - 1.0;
+        let points = GravityPoints::from(self.post_ferment_gravity());
         let ratio = self.post_ferment_volume().0 / self.product_volume().0;
-        SpecificGravity(1.0 + points * ratio)
+        (points * ratio).into()
     }
 
     /// Real extract
@@ -943,16 +1985,19 @@ impl Process {
     }
 
     /// Salt doses
+    ///
+    /// Without a volume, salts are dosed into the total water, except
+    /// baking soda which only goes into the mash water.
     #[must_use]
     pub fn salt_doses(&self, liters: Option<Liters>) -> Vec<SaltDose> {
         let mut output: Vec<SaltDose> = Vec::new();
 
-        let water_liters = match liters {
-            Some(l) => l,
-            None => self.total_water(),
-        };
-
         for salt_concentration in &self.water_salts() {
+            let water_liters = match liters {
+                Some(l) => l,
+                None if salt_concentration.salt == Salt::BakingSoda => self.mash_volume(),
+                None => self.total_water(),
+            };
             let mg = Milligrams(water_liters.0 * salt_concentration.ppm.0);
             output.push(SaltDose {
                 salt: salt_concentration.salt,
@@ -963,45 +2008,98 @@ impl Process {
         output
     }
 
-    /// Acid doses
+    /// Acid doses for the mash water (strike and infusions), to reach the
+    /// mash pH target
     #[must_use]
-    pub fn acid_doses(&self, liters: Option<Liters>) -> Vec<AcidDose> {
-        let mut output: Vec<AcidDose> = Vec::new();
+    pub fn mash_acid_doses(&self) -> Vec<AcidDose> {
+        let mash_volume = self.mash_volume();
+
+        self.water_acids()
+            .iter()
+            .map(|acid_concentration| AcidDose {
+                acid: acid_concentration.acid,
+                mg: Milligrams(mash_volume.0 * acid_concentration.ppm.0),
+            })
+            .collect()
+    }
 
-        let water_liters = match liters {
-            Some(l) => l,
-            None => self.total_water(),
+    /// Acid doses for the sparge water.
+    ///
+    /// Alkaline sparge water raises the pH of the grain bed late in the
+    /// lauter, which extracts tannins. So we neutralize the alkalinity
+    /// down to about pH 5.8, where most of the bicarbonate has become
+    /// carbonic acid.
+    #[must_use]
+    pub fn sparge_acid_doses(&self) -> Vec<AcidDose> {
+        const SPARGE_PH: f32 = 5.8;
+
+        let sparge_volume = self.sparge_volume();
+        if !self.has_mash() || self.is_full_volume_mash() || sparge_volume <= Liters(0.0) {
+            return Vec::new();
+        }
+
+        let wanted = match self.recipe.ph_method {
+            PhMethod::ComputeAcid(acid) => Some(acid),
+            _ => None,
+        };
+        let Some(acid) = self.chosen_acid(wanted) else {
+            return Vec::new();
         };
 
-        for acid_concentration in &self.water_acids() {
-            let mg = Milligrams(water_liters.0 * acid_concentration.ppm.0);
-            output.push(AcidDose {
-                acid: acid_concentration.acid,
-                mg,
-            });
+        // Of the bicarbonate (pKa 6.35), the fraction that remains at the
+        // target pH does not need neutralizing.
+        // Baking soda only goes into the mash water.
+        let mut profile = self.brewery.source_water_profile();
+        for salt_conc in &self.water_salts() {
+            if salt_conc.salt != Salt::BakingSoda {
+                profile.add_salt(*salt_conc);
+            }
+        }
+        let alkalinity = profile.alkalinity_caco3;
+        let remaining = 1.0 / (1.0 + 10.0_f32.powf(6.35 - SPARGE_PH));
+        let reduction = CaCO3(alkalinity.0 * (1.0 - remaining));
+        if reduction.0 <= 0.0 {
+            return Vec::new();
         }
 
-        output
+        let ml = acid.ml_for_alkalinity_reduction(reduction, sparge_volume);
+        let grams = ml.0 * acid.density();
+        vec![AcidDose {
+            acid,
+            mg: Milligrams(grams * 1000.0),
+        }]
     }
 
     /// Strike water additions string
     #[must_use]
     pub fn water_doses(&self) -> String {
         let salt_doses = self.salt_doses(None);
-        let acid_doses = self.acid_doses(None);
         let mut output: String = String::new();
         for salt_dose in &salt_doses {
+            let water = if salt_dose.salt == Salt::BakingSoda {
+                "the mash water"
+            } else {
+                "total water"
+            };
             writeln!(
                 output,
-                "Add in {} of {} to total water.",
-                salt_dose.mg, salt_dose.salt
+                "Add in {} of {} to {}.",
+                salt_dose.mg, salt_dose.salt, water
+            )
+            .unwrap();
+        }
+        for acid_dose in &self.mash_acid_doses() {
+            writeln!(
+                output,
+                "Add in {} of {} to the mash water.",
+                acid_dose.mg, acid_dose.acid
             )
             .unwrap();
         }
-        for acid_dose in &acid_doses {
+        for acid_dose in &self.sparge_acid_doses() {
             writeln!(
                 output,
-                "Add in {} of {} to total water.",
+                "Add in {} of {} to the sparge water.",
                 acid_dose.mg, acid_dose.acid
             )
             .unwrap();
@@ -1024,35 +2122,66 @@ impl Process {
         for sugar in &self.sugar_doses() {
             writeln!(output, "{} of {}", sugar.weight, sugar.sugar).unwrap();
         }
+        for extract in &self.extract_doses() {
+            writeln!(output, "{} of {}", extract.weight, extract.extract).unwrap();
+        }
         for hops in &self.hops_doses() {
             writeln!(output, "{} of {}", hops.weight, hops.hops).unwrap();
         }
         writeln!(output, "Yeast: {}", self.recipe.yeast).unwrap();
         if self.yeast_nutrient_amount() > Grams(0.0) {
-            writeln!(output, "Yeast Nutrient: {}", self.yeast_nutrient_amount()).unwrap();
+            writeln!(
+                output,
+                "Yeast Nutrient: {} of {}",
+                self.yeast_nutrient_amount(),
+                self.brewery.yeast_nutrient
+            )
+            .unwrap();
         }
         output
     }
 
     /// Compute bitterness in IBU
     #[must_use]
-    #[allow(clippy::cast_precision_loss)]
     pub fn bitterness(&self) -> Ibu {
-        // We use Tinseth
-
-        let bigness_factor = 1.65 * (0.000_125_f32).powf(self.recipe.original_gravity.0 - 1.0);
-        let gallons: Gallons = self.batch_size.into();
-
-        let mut ibu: f32 = 0.0;
+        Ibu(self.ibu_breakdown().iter().map(|(_, ibu)| ibu.0).sum())
+    }
 
-        for dose in &self.hops_doses() {
-            let ounces: Ounces = dose.weight.into();
-            let boil_time_factor = (1.0 - (-0.04 * dose.timing.0 as f32).exp()) / 4.15;
-            let utilization = bigness_factor * boil_time_factor;
-            ibu += utilization * dose.hops.alpha_acid() * ounces.0 * 7490.0 / gallons.0;
+    /// The IBU contributed by each hops addition, under the recipe's IBU
+    /// model. These sum to `bitterness()`.
+    #[must_use]
+    pub fn ibu_breakdown(&self) -> Vec<(HopsDose, Ibu)> {
+        let doses = self.hops_doses();
+        let breakdown = |estimate: Ibu| -> Vec<(HopsDose, Ibu)> {
+            doses
+                .iter()
+                .map(|dose| {
+                    let ibu = self.addition_ibu(
+                        dose.alpha_acid(),
+                        dose.weight,
+                        dose.timing,
+                        dose.first_wort,
+                        dose.whirlpool,
+                        estimate,
+                    );
+                    (*dose, ibu)
+                })
+                .collect()
+        };
+        let total = |b: &[(HopsDose, Ibu)]| Ibu(b.iter().map(|(_, ibu)| ibu.0).sum());
+
+        // Garetz depends on the bitterness itself, so iterate to convergence
+        let mut additions = breakdown(self.recipe.ibu);
+        if self.recipe.ibu_model == IbuModel::Garetz {
+            for _ in 0..50 {
+                let ibu = total(&additions);
+                additions = breakdown(ibu);
+                if (total(&additions).0 - ibu.0).abs() < 0.001 {
+                    break;
+                }
+            }
         }
-
-        Ibu(ibu)
+        additions
     }
 
     /// Beer color in SRM units (Morey)
@@ -1070,6 +2199,11 @@ impl Process {
             let lovabond: Lovabond = dose.sugar.ebc().into();
             mcu += pounds.0 * lovabond.0;
         }
+        for dose in &self.extract_doses() {
+            let pounds: Pounds = dose.weight.into();
+            let lovabond: Lovabond = dose.extract.ebc().into();
+            mcu += pounds.0 * lovabond.0;
+        }
 
         let gallons: Gallons = self.batch_size.into();
         mcu /= gallons.0;
@@ -1078,28 +2212,236 @@ impl Process {
         Srm(1.4922 * mcu.powf(0.6859))
     }
 
+    /// The dose of color malt to add to bring the color up into the
+    /// style range, if it is too pale.
+    #[must_use]
+    pub fn color_correction(&self) -> Option<MaltDose> {
+        let range = self.recipe.style.color_range()?;
+        if self.color() >= range.start {
+            return None;
+        }
+
+        // Aim a bit above the minimum, as the added extract slightly
+        // reduces the rest of the grain bill
+        let target = Srm(range.start.0 + (range.end.0 - range.start.0) * 0.25);
+
+        // Invert Morey to get the MCU needed
+        let mcu_needed = (target.0 / 1.4922).powf(1.0 / 0.6859);
+        let mcu_now = (self.color().0 / 1.4922).powf(1.0 / 0.6859);
+
+        let malt = Malt::GladfieldCrystalDark;
+        let lovabond: Lovabond = malt.ebc().into();
+        let gallons: Gallons = self.batch_size.into();
+        let pounds = Pounds((mcu_needed - mcu_now) * gallons.0 / lovabond.0);
+
+        Some(MaltDose {
+            malt,
+            weight: pounds.into(),
+        })
+    }
+
+    /// The original gravity target to set to bring the beer into the
+    /// style range, if it is outside of it.
+    ///
+    /// The grain bill is scaled to hit the target, so this is what has to
+    /// change, not the grain.
+    #[must_use]
+    pub fn og_correction(&self) -> Option<SpecificGravity> {
+        let range = self.recipe.style.original_gravity_range()?;
+        let og = self.recipe.original_gravity;
+        let margin = (range.end.0 - range.start.0) * 0.1;
+        if og < range.start {
+            Some(SpecificGravity(range.start.0 + margin))
+        } else if og > range.end {
+            Some(SpecificGravity(range.end.0 - margin))
+        } else {
+            None
+        }
+    }
+
+    /// The IBU target to set to bring the beer into the style range, if
+    /// it is outside of it.
+    ///
+    /// Hops are dosed to hit the target, so this is what has to change,
+    /// not the hops.
+    #[must_use]
+    pub fn ibu_correction(&self) -> Option<Ibu> {
+        let range = self.recipe.style.bitterness_range()?;
+        let ibu = self.bitterness();
+        let margin = (range.end.0 - range.start.0) * 0.1;
+        if ibu < range.start {
+            Some(Ibu(range.start.0 + margin))
+        } else if ibu > range.end {
+            Some(Ibu(range.end.0 - margin))
+        } else {
+            None
+        }
+    }
+
+    /// Concrete suggestions to bring out-of-style attributes into range
+    #[must_use]
+    pub fn style_autocorrect(&self) -> Vec<String> {
+        let mut output: Vec<String> = Vec::new();
+        let style = self.recipe.style;
+
+        if let Some(og) = self.og_correction() {
+            output.push(format!(
+                "Set the original gravity target to {og} (from {}). The grain \
+                 bill is scaled to the target.",
+                self.recipe.original_gravity
+            ));
+        }
+
+        if let Some(ibu) = self.ibu_correction() {
+            output.push(format!(
+                "Set the IBU target to {:.0} (from {:.0}). The hops are dosed \
+                 to the target.",
+                ibu.0, self.recipe.ibu.0
+            ));
+        }
+
+        // Color
+        let srm = self.color();
+        if let Some(srm_range) = style.color_range() {
+            if let Some(dose) = self.color_correction() {
+                output.push(format!(
+                    "Add about {} of {} to raise the color to {}.",
+                    dose.weight, dose.malt, srm_range.start
+                ));
+            } else if srm > srm_range.end {
+                output.push(format!(
+                    "Reduce the darkest malts to lower the color to {}.",
+                    srm_range.end
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// A qualitative note on expected head retention.
+    ///
+    /// Wheat and high-protein malts improve head retention. Adjuncts and
+    /// sugars carry no foam-positive protein, so adjunct-heavy grists
+    /// reduce it.
+    #[must_use]
+    pub fn head_retention_note(&self) -> &'static str {
+        let total_weight = self.fermentables_weight().0;
+
+        let mut wheat_weight: f32 = 0.0;
+        let mut protein: f32 = 0.0;
+        for dose in &self.malt_doses() {
+            if matches!(dose.malt, Malt::GladfieldWheat | Malt::WeyermannWheatPale) {
+                wheat_weight += dose.weight.0;
+            }
+
+            // Where the spec is missing, presume a typical base malt
+            // and no protein otherwise.
+            let percent_protein = dose.malt.percent_protein().unwrap_or(
+                if dose.malt.category() == MaltCategory::Base {
+                    10.5
+                } else {
+                    0.0
+                },
+            );
+            protein += percent_protein * dose.weight.0;
+        }
+
+        let wheat_fraction = wheat_weight / total_weight;
+        let percent_protein = protein / total_weight;
+
+        if wheat_fraction >= 0.3 || percent_protein >= 11.0 {
+            "Good head retention expected (wheat/high protein)"
+        } else if percent_protein < 6.0 {
+            "Poor head retention expected (low protein, adjunct heavy)"
+        } else {
+            "Average head retention expected"
+        }
+    }
+
+    /// A qualitative note on the body to expect from the mash temperature,
+    /// or None if there is no mash
+    #[must_use]
+    pub fn mash_body_note(&self) -> Option<&'static str> {
+        if !self.has_mash() {
+            return None;
+        }
+
+        let fermentability = self.mash_fermentability();
+        Some(if fermentability >= 1.04 {
+            "This cool mash makes a highly fermentable wort, for a dry, light bodied beer."
+        } else if fermentability <= 0.94 {
+            "This hot mash leaves more unfermentable dextrins, for a fuller bodied, \
+             sweeter beer."
+        } else {
+            "This mash temperature makes a moderately fermentable wort, for a medium \
+             bodied beer."
+        })
+    }
+
+    /// A qualitative note on the expected melanoidin and Maillard malt
+    /// depth, from boil length, decoction, and Munich/melanoidin malts.
+    #[must_use]
+    pub fn malt_depth_note(&self) -> &'static str {
+        let mut score: u32 = 0;
+
+        if self.recipe.boil_length >= Minutes(90) {
+            score += 2;
+        } else if self.recipe.boil_length >= Minutes(75) {
+            score += 1;
+        }
+
+        if self.recipe.decoction {
+            score += 2;
+        }
+
+        // Melanoidin malt is far more intense than Munich malt
+        let total_weight = self.fermentables_weight().0;
+        let mut weighted: f32 = 0.0;
+        for dose in &self.malt_doses() {
+            weighted += dose.weight.0
+                * match dose.malt {
+                    Malt::GladfieldMunich | Malt::WeyermannMunich1 | Malt::WeyermannMunich2 => 1.0,
+                    Malt::WeyermannMelanoidin => 3.0,
+                    _ => 0.0,
+                };
+        }
+        let fraction = weighted / total_weight;
+        if fraction >= 0.5 {
+            score += 2;
+        } else if fraction >= 0.2 {
+            score += 1;
+        }
+
+        match score {
+            0..=1 => "Minimal melanoidin/Maillard malt complexity",
+            2..=3 => "Moderate melanoidin/Maillard malt complexity",
+            _ => "Strong melanoidin/Maillard malt complexity",
+        }
+    }
+
     /// Get warnings
     #[must_use]
     #[allow(clippy::too_many_lines)]
     pub fn get_warnings(&self) -> Vec<Warning> {
         let mut warnings: Vec<Warning> = Vec::new();
 
-        // Check fermenter volume
-        {
-            let needed = self.batch_size + self.fermentation_head_space();
+        // Check there is something to ferment
+        if self.grain_bill_multiplier() == 0.0 {
+            warnings.push(Warning::NoFermentables);
+        }
 
-            // Choose the smallest fermenter that handles this
-            let chosen = self
-                .brewery
-                .fermenters
-                .iter()
-                .copied()
-                .filter(|&f| f >= needed)
-                .min();
+        // Check an acid is available to compute a dose of
+        if matches!(self.recipe.ph_method, PhMethod::ComputeAcid(_))
+            && self.brewery.acids_available.is_empty()
+        {
+            warnings.push(Warning::NoAcidAvailable);
+        }
 
-            if chosen.is_none() {
-                warnings.push(Warning::FermentersTooSmall { needed });
-            }
+        // Check fermenter volume
+        if self.recommended_fermenter().is_none() {
+            let needed = self.batch_size + self.fermentation_head_space();
+            warnings.push(Warning::FermentersTooSmall { needed });
         }
 
         // Check partial boil dilution amount
@@ -1114,34 +2456,17 @@ impl Process {
             }
         }
 
-        // Verify diastatic power of the mash
-        {
-            // TODO: use degrees Lintner
-            let mut diastatic_weight: Kilograms = Kilograms(0.0);
-            for malt_dose in &self.malt_doses() {
-                if malt_dose.malt.category() == MaltCategory::Base {
-                    diastatic_weight = diastatic_weight + malt_dose.weight;
-                }
+        // Verify the base malt fraction and diastatic power of the mash,
+        // and that malts are not in excess of recommendations
+        if self.has_mash() {
+            let malt_bill = crate::MaltBill::from(self.recipe.malts.clone());
+            if let Err(malt_warnings) = malt_bill.validate() {
+                warnings.extend(malt_warnings);
             }
 
-            let fraction_base_malts = diastatic_weight.0 / self.grain_weight().0;
-
-            if fraction_base_malts < 0.7 {
-                warnings.push(Warning::LowDiastaticPower {
-                    fraction_base_malts,
-                });
-            }
-        }
-
-        // Verify malts are not in excess of recommendations
-        for malt_dose in &self.malt_doses() {
-            let percent = 100.0 * malt_dose.weight.0 / self.grain_weight().0;
-            if percent > malt_dose.malt.recommended_max_percent() {
-                warnings.push(Warning::ExcessMalt {
-                    malt: malt_dose.malt,
-                    percent,
-                    max_recommended_percent: malt_dose.malt.recommended_max_percent(),
-                });
+            let diastatic_power = self.total_diastatic_power();
+            if diastatic_power < Lintner(30.0) {
+                warnings.push(Warning::LowConversionPower { diastatic_power });
             }
         }
 
@@ -1172,7 +2497,7 @@ impl Process {
             ));
         }
 
-        if self.brewery.infusion_temperature > Celsius(100.0) {
+        if self.brewery.infusion_temperature > self.boiling_point() {
             warnings.push(Warning::ImpossibleInfusionTemperature(
                 self.brewery.infusion_temperature,
             ));
@@ -1208,6 +2533,47 @@ impl Process {
             });
         }
 
+        if let Some(style_max) = self.style_max_ferment_temperature()
+            && self.recipe.ferment_temperature > style_max
+        {
+            warnings.push(Warning::EstersOutOfStyle {
+                ferment_temp: self.recipe.ferment_temperature,
+                style_max,
+            });
+        }
+
+        if self.recipe.yeast.is_sta1_positive() == Some(true) {
+            warnings.push(Warning::DiastaticusRisk(self.recipe.yeast));
+        }
+
+        for dose in &self.salt_doses(None) {
+            if dose.mg.0 > 0.0 && dose.mg.0 < SaltDose::MIN_WEIGHABLE.0 {
+                warnings.push(Warning::UnweighableSaltDose {
+                    salt: dose.salt,
+                    mg: dose.mg,
+                });
+            }
+        }
+
+        // Enzymes in the hops break down dextrins, which restarts
+        // fermentation
+        let dry_hop_rate = self.recipe.dry_hop_rate();
+        if dry_hop_rate >= 4.0 {
+            warnings.push(Warning::HopCreep {
+                grams_per_liter: dry_hop_rate,
+            });
+        }
+
+        // A large dose of a nutrient with zinc can overshoot
+        let zinc = self.nutrient_zinc();
+        if zinc > ZINC_MAX {
+            warnings.push(Warning::ExcessZinc {
+                nutrient: self.brewery.yeast_nutrient,
+                zinc,
+                maximum: ZINC_MAX,
+            });
+        }
+
         if self.abv() > Abv(self.recipe.yeast.alcohol_tolerance() * 100.0) {
             warnings.push(Warning::TooMuchAlcohol {
                 abv: self.abv(),
@@ -1235,60 +2601,2002 @@ impl Process {
         }
 
         // Verify the style OG
-        if !self
-            .recipe
-            .style
-            .original_gravity_range()
-            .contains(&self.recipe.original_gravity)
+        if let Some(range) = self.recipe.style.original_gravity_range()
+            && !range.contains(&self.recipe.original_gravity)
         {
             warnings.push(Warning::OriginalGravityOutOfRange {
                 gravity: self.recipe.original_gravity,
-                range: self.recipe.style.original_gravity_range(),
+                range,
             });
         }
 
         // Verify the style FG
-        if !self
-            .recipe
-            .style
-            .final_gravity_range()
-            .contains(&self.final_gravity())
+        if let Some(range) = self.recipe.style.final_gravity_range()
+            && !range.contains(&self.final_gravity())
         {
             warnings.push(Warning::FinalGravityOutOfRange {
                 gravity: self.final_gravity(),
-                range: self.recipe.style.final_gravity_range(),
+                range,
             });
         }
 
         // Verify the style ABV
-        if !self.recipe.style.abv_range().contains(&self.abv()) {
+        if let Some(range) = self.recipe.style.abv_range()
+            && !range.contains(&self.abv())
+        {
             warnings.push(Warning::AbvOutOfRange {
                 abv: self.abv(),
-                range: self.recipe.style.abv_range(),
+                range,
             });
         }
 
         // Verify the style IBU
-        if !self
-            .recipe
-            .style
-            .bitterness_range()
-            .contains(&self.bitterness())
+        if let Some(range) = self.recipe.style.bitterness_range()
+            && !range.contains(&self.bitterness())
         {
             warnings.push(Warning::IbuOutOfRange {
                 ibu: self.bitterness(),
-                range: self.recipe.style.bitterness_range(),
+                range,
             });
         }
 
         // Verify the style SRM
-        if !self.recipe.style.color_range().contains(&self.color()) {
+        if let Some(range) = self.recipe.style.color_range()
+            && !range.contains(&self.color())
+        {
             warnings.push(Warning::SrmOutOfRange {
                 srm: self.color(),
-                range: self.recipe.style.color_range(),
+                range,
             });
         }
 
+        // Check the adjusted water against palate thresholds
+        {
+            let profile = self.adjusted_water_profile();
+            for (ion, ppm, threshold) in [
+                (Ion::Sodium, profile.na, Ppm(150.0)),
+                (Ion::Sulfate, profile.so4, Ppm(350.0)),
+            ] {
+                if ppm > threshold {
+                    warnings.push(Warning::HarshWater {
+                        ion,
+                        ppm,
+                        threshold,
+                    });
+                }
+            }
+        }
+
         warnings
     }
+
+    /// Get only the warnings that are errors, i.e. the ones that mean
+    /// the process cannot work
+    #[must_use]
+    pub fn get_errors(&self) -> Vec<Warning> {
+        self.get_warnings()
+            .into_iter()
+            .filter(Warning::is_error)
+            .collect()
+    }
+
+    /// Whether the process can be brewed, i.e. there are no errors
+    #[must_use]
+    pub fn is_brewable(&self) -> bool {
+        self.get_errors().is_empty()
+    }
+
+    /// Check the process.
+    ///
+    /// # Errors
+    ///
+    /// If the process cannot be brewed as specified, returns the warnings
+    /// that are errors. Otherwise returns the remaining (non-blocking)
+    /// warnings.
+    pub fn validate(&self) -> Result<Vec<Warning>, Vec<Warning>> {
+        let (errors, warnings): (Vec<Warning>, Vec<Warning>) =
+            self.get_warnings().into_iter().partition(Warning::is_error);
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Buffering capacity of a malt in mEq/(kg·pH)
+fn malt_buffering(malt: Malt) -> f32 {
+    40.0 + 0.2 * malt.acidity()
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::{MashRest, Style};
+    use float_cmp::approx_eq;
+
+    pub(crate) fn test_brewery() -> Brewery {
+        Brewery {
+            water_profile: WaterProfile {
+                ca: Ppm(38.6),
+                mg: Ppm(7.0),
+                na: Ppm(15.6),
+                so4: Ppm(12.5),
+                cl: Ppm(21.9),
+                alkalinity_caco3: CaCO3(120.),
+                ph: Ph(8.0),
+            },
+            ro_fraction: None,
+            salts_available: vec![
+                Salt::CalciumChloride,
+                Salt::TableSalt,
+                Salt::Epsom,
+                Salt::Gypsum,
+                Salt::BakingSoda,
+            ],
+            acids_available: vec![Acid::LacticAcid],
+            yeast_nutrient: YeastNutrient::FermaidO,
+            hulls_contain_gluten: false,
+            mash_tun_volume: Liters(30.0),
+            mash_tun_losses: Liters(0.5),
+            mash_tun_thermal_mass: Liters(0.0),
+            preheat_mash_tun: false,
+            max_kettle_volume: Liters(36.0),
+            kettle_losses: Liters(1.0),
+            boil_evaporation_per_hour: Liters(4.0),
+            grain_absorption_per_kg: Liters(1.0),
+            hops_absorption_per_kg: Liters(5.0),
+            mash_efficiency: 0.75,
+            infusion_temperature: Celsius(98.5),
+            room_temperature: Celsius(20.0),
+            serving_temperature: Celsius(4.0),
+            elevation: Meters(0.0),
+            ice_bath: false,
+            fermenters: vec![Liters(30.0)],
+            lagerers: vec![Liters(24.0)],
+            packaging: Packaging::Bottle(Liters(0.500), Sugar::Dextrose),
+        }
+    }
+
+    pub(crate) fn test_recipe() -> Recipe {
+        Recipe {
+            name: "Test Märzen".to_owned(),
+            style: Style::Marzen,
+            sulfate_chloride_target: 0.33,
+            malts: vec![
+                MaltProportion {
+                    malt: Malt::WeyermannMunich2,
+                    proportion: 60.,
+                },
+                MaltProportion {
+                    malt: Malt::GladfieldGermanPilsner,
+                    proportion: 27.,
+                },
+                MaltProportion {
+                    malt: Malt::WeyermannMelanoidin,
+                    proportion: 4.,
+                },
+                MaltProportion {
+                    malt: Malt::WeyermannAcidulated,
+                    proportion: 2.,
+                },
+            ],
+            mash_rests: vec![
+                MashRest {
+                    target_temperature: Celsius(61.0),
+                    duration: Minutes(30),
+                },
+                MashRest {
+                    target_temperature: Celsius(69.0),
+                    duration: Minutes(30),
+                },
+            ],
+            decoction: false,
+            mash_method: MashMethod::Infusion,
+            mash_thickness: 3.3,
+            mash_ph_target: Ph(5.3),
+            ph_method: PhMethod::ComputeAcid(Acid::LacticAcid),
+            sugars: vec![],
+            extracts: vec![],
+            original_gravity: SpecificGravity(1.056),
+            ibu: Ibu(21.0),
+            ibu_model: IbuModel::Tinseth,
+            abv_model: AbvModel::Hall,
+            hops: vec![HopsProportion {
+                hops: Hops::HallertauMittelfruh,
+                proportion: 11.0,
+                timing: Minutes(60),
+                first_wort: false,
+                whirlpool: None,
+                age: None,
+                measured_alpha_acid: None,
+            }],
+            dry_hops: vec![],
+            boil_length: Minutes(80),
+            fining_desired: true,
+            fining_agent: FiningAgent::Whirlfloc,
+            yeast: Yeast::WLP835,
+            max_partial_boil_dilution: 1.0,
+            ferment_temperature: Yeast::WLP835.temp(),
+            target_abv: None,
+            max_post_ferment_dilution: 1.0,
+            custom_steps: None,
+        }
+    }
+
+    pub(crate) fn test_process() -> Process {
+        Process::new(test_brewery(), test_recipe(), Liters(20.0))
+    }
+
+    #[test]
+    fn test_hops_by_aau() {
+        let mut process = test_process();
+        process.recipe.hops = vec![
+            HopsProportion::from_aau(Hops::Magnum, 5.0, Minutes(60)),
+            HopsProportion::from_aau(Hops::Saaz, 2.0, Minutes(10)),
+        ];
+
+        let doses = process.hops_doses();
+
+        // Relative weights follow from AAU / alpha acid
+        let expected_ratio = (5.0 / Hops::Magnum.alpha_acid()) / (2.0 / Hops::Saaz.alpha_acid());
+        let ratio = doses[0].weight.0 / doses[1].weight.0;
+        assert!(approx_eq!(f32, ratio, expected_ratio, epsilon = 0.001));
+
+        // And together they hit the recipe IBU target
+        assert!(approx_eq!(
+            f32,
+            process.bitterness().0,
+            process.recipe.ibu.0,
+            epsilon = 0.01
+        ));
+    }
+
+    #[test]
+    fn test_head_retention_note() {
+        let mut process = test_process();
+        process.recipe.style = Style::Weissbier;
+        process.recipe.malts = vec![
+            MaltProportion {
+                malt: Malt::WeyermannWheatPale,
+                proportion: 60.,
+            },
+            MaltProportion {
+                malt: Malt::WeyermannPilsner,
+                proportion: 40.,
+            },
+        ];
+        assert!(process.head_retention_note().starts_with("Good"));
+
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::FlakedRice,
+            proportion: 100.,
+        }];
+        assert!(process.head_retention_note().starts_with("Poor"));
+    }
+
+    #[test]
+    fn test_partial_boil_bitterness() {
+        let full = test_process();
+        assert!(approx_eq!(
+            f32,
+            full.partial_boil_dilution().0,
+            0.0,
+            epsilon = 0.001
+        ));
+
+        let mut partial = test_process();
+        partial.brewery.max_kettle_volume = Liters(18.0);
+        assert!(partial.partial_boil_dilution().0 > 0.0);
+        assert!(partial.post_boil_gravity().0 > full.post_boil_gravity().0);
+
+        let hops = Hops::HallertauMittelfruh;
+        let ibu_full = full.hops_ibu(hops.alpha_acid(), Grams(30.0), Minutes(60));
+        let ibu_partial = partial.hops_ibu(hops.alpha_acid(), Grams(30.0), Minutes(60));
+        assert!(ibu_partial.0 < ibu_full.0);
+
+        // The doses compensate, so both reach the target IBU
+        let hops_weight = |p: &Process| p.hops_doses().iter().map(|d| d.weight.0).sum::<f32>();
+        assert!(hops_weight(&partial) > hops_weight(&full));
+        assert!(approx_eq!(
+            f32,
+            partial.bitterness().0,
+            full.bitterness().0,
+            epsilon = 0.1
+        ));
+    }
+
+    #[test]
+    fn test_effective_fermentation() {
+        let mut process = test_process();
+        process.recipe.style = Style::CreamAle;
+        process.recipe.yeast = Yeast::SafLagerW3470;
+        assert_eq!(process.effective_fermentation(), Fermentation::Lager);
+
+        let output = crate::print_process(&process, None, Some(1000), crate::UnitSystem::Metric);
+        assert!(output.contains("drop haze proteins"));
+
+        process.recipe.yeast = Yeast::SafAleUS05;
+        assert_eq!(process.effective_fermentation(), Fermentation::Ale);
+    }
+
+    #[test]
+    fn test_calcium_lowers_mash_ph() {
+        let mut process = test_process();
+        process.recipe.ph_method = PhMethod::None;
+        let without = process.mash_ph();
+
+        process.brewery.water_profile.add_salt(SaltConcentration {
+            salt: Salt::Gypsum,
+            ppm: Ppm(200.0),
+        });
+        let with = process.mash_ph();
+
+        for (w, wo) in with.iter().zip(without.iter()) {
+            assert!(w.0 < wo.0);
+        }
+    }
+
+    #[test]
+    fn test_recommended_vessels() {
+        let mut process = test_process();
+        process.brewery.fermenters = vec![
+            Gallons(1.0).into(),
+            Liters(5.0),
+            Liters(8.0),
+            Liters(24.0),
+            Liters(30.0),
+        ];
+        process.brewery.lagerers =
+            vec![Gallons(1.0).into(), Liters(5.0), Liters(8.0), Liters(24.0)];
+
+        process.batch_size = Liters(6.0);
+        assert_eq!(process.recommended_fermenter(), Some(Liters(8.0)));
+        assert_eq!(process.recommended_lagerer(), Some(Liters(8.0)));
+
+        process.batch_size = Liters(30.0);
+        assert_eq!(process.recommended_fermenter(), None);
+        assert_eq!(process.recommended_lagerer(), None);
+    }
+
+    #[test]
+    fn test_post_ferment_dilution_consistency() {
+        let mut process = test_process();
+        process.recipe.max_post_ferment_dilution = 1.5;
+        let natural = process.abv();
+        process.recipe.target_abv = Some(Abv(natural.0 * 0.85));
+
+        let fraction = process.post_fermentation_dilution_fraction();
+        assert!(fraction > 1.0);
+
+        // Volume reflects the dilution
+        assert!(approx_eq!(
+            f32,
+            process.product_volume().0,
+            process.post_ferment_volume().0 * fraction,
+            epsilon = 0.001
+        ));
+
+        // Gravity points are diluted by the same fraction
+        assert!(approx_eq!(
+            f32,
+            process.final_gravity().points(),
+            process.post_ferment_gravity().points() / fraction,
+            epsilon = 0.01
+        ));
+
+        // ABV hits the target
+        assert!(approx_eq!(
+            f32,
+            process.abv().0,
+            process.recipe.target_abv.unwrap().0,
+            epsilon = 0.0001
+        ));
+
+        // ABV agrees with diluting the original gravity alongside the
+        // final gravity (the ABV formula is nonlinear so this is approximate)
+        let diluted_og =
+            SpecificGravity(1.0 + (process.recipe.original_gravity.0 - 1.0) / fraction);
+        let implied = Abv::from_gravity(diluted_og, process.final_gravity(), 1.0);
+        assert!(approx_eq!(f32, process.abv().0, implied.0, epsilon = 0.001));
+    }
+
+    #[test]
+    fn test_warm_lager_warning() {
+        let mut process = test_process();
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::EstersOutOfStyle { .. }))
+        );
+        assert!(
+            process.recommended_ferment_temperature()
+                <= process.style_max_ferment_temperature().unwrap()
+        );
+
+        process.recipe.ferment_temperature = process.recipe.yeast.temp_range().end + Celsius(5.0);
+        let warnings = process.get_warnings();
+        assert!(
+            warnings
+                .iter()
+                .any(|w| matches!(w, Warning::EstersOutOfStyle { .. }))
+        );
+        assert!(warnings.iter().any(|w| matches!(w, Warning::TooHot { .. })));
+    }
+
+    #[test]
+    fn test_boil_extract_conservation() {
+        let process = test_process();
+        assert!(approx_eq!(
+            f32,
+            process.partial_boil_dilution().0,
+            0.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            process.post_boil_gravity().0,
+            process.recipe.original_gravity.0,
+            epsilon = 0.0001
+        ));
+
+        let pre_extract = process.pre_boil_gravity().points() * process.pre_boil_volume().0;
+        let post_extract =
+            process.post_boil_gravity().points() * process.post_boil_pre_loss_volume().0;
+        assert!(approx_eq!(f32, pre_extract, post_extract, epsilon = 0.5));
+    }
+
+    #[test]
+    fn test_get_errors() {
+        let mut process = test_process();
+        assert!(process.get_errors().is_empty());
+        assert!(process.is_brewable());
+
+        process.batch_size = Liters(60.0);
+        assert!(!process.get_errors().is_empty());
+        assert!(!process.is_brewable());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut process = test_process();
+        match process.validate() {
+            Ok(warnings) => assert!(warnings.iter().all(|w| !w.is_error())),
+            Err(errors) => panic!("{errors:?}"),
+        }
+
+        process.brewery.fermenters = vec![Liters(10.0)];
+        match process.validate() {
+            Ok(warnings) => panic!("{warnings:?}"),
+            Err(errors) => {
+                assert!(errors.iter().all(Warning::is_error));
+                assert!(
+                    errors
+                        .iter()
+                        .any(|e| matches!(e, Warning::FermentersTooSmall { .. }))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_runnings_gravity() {
+        let process = test_process();
+        let first = process.first_runnings_gravity().unwrap();
+        let sparge = process.sparge_runnings_gravity().unwrap();
+        assert!(first.0 > sparge.0);
+
+        let v1 = process.pre_sparge_volume().0;
+        let v2 = process.sparge_volume().0;
+        let mixed = (first.points() * v1 + sparge.points() * v2) / (v1 + v2);
+        assert!(approx_eq!(
+            f32,
+            mixed,
+            process.pre_boil_gravity().points(),
+            epsilon = 0.01
+        ));
+    }
+
+    #[test]
+    fn test_harsh_water() {
+        let mut process = test_process();
+        process.recipe.ph_method = PhMethod::AdjustWater;
+        process.recipe.sulfate_chloride_target = 1.0;
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::HarshWater { .. }))
+        );
+
+        // Very alkaline water needs a lot of calcium, and chasing a high
+        // sulfate/chloride ratio puts it all in as gypsum
+        process.brewery.water_profile.alkalinity_caco3 = CaCO3(300.0);
+        process.recipe.sulfate_chloride_target = 10.0;
+        assert!(process.get_warnings().iter().any(|w| matches!(
+            w,
+            Warning::HarshWater {
+                ion: Ion::Sulfate,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_malt_depth_note() {
+        let mut process = test_process();
+        process.recipe.decoction = true;
+        assert!(process.malt_depth_note().starts_with("Strong"));
+
+        process.recipe.decoction = false;
+        process.recipe.boil_length = Minutes(60);
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::GladfieldAle,
+            proportion: 100.,
+        }];
+        assert!(process.malt_depth_note().starts_with("Minimal"));
+    }
+
+    #[test]
+    fn test_ro_blend() {
+        let mut process = test_process();
+        process.recipe.ph_method = PhMethod::None;
+        let tap = process.adjusted_water_profile_preacid();
+
+        process.brewery.ro_fraction = Some(0.5);
+        let blended = process.adjusted_water_profile_preacid();
+
+        assert!(approx_eq!(
+            f32,
+            blended.ca.0,
+            tap.ca.0 / 2.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            blended.mg.0,
+            tap.mg.0 / 2.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            blended.na.0,
+            tap.na.0 / 2.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            blended.so4.0,
+            tap.so4.0 / 2.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            blended.cl.0,
+            tap.cl.0 / 2.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            blended.alkalinity_caco3.0,
+            tap.alkalinity_caco3.0 / 2.0,
+            epsilon = 0.001
+        ));
+    }
+
+    #[test]
+    fn test_volume_flow_dot() {
+        let process = test_process();
+        let dot = process.volume_flow_dot();
+
+        assert!(dot.starts_with("digraph volume_flow {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+        assert!(dot.contains("    strike_volume [label="));
+        assert!(dot.contains("    product_volume [label="));
+        assert!(dot.contains("strike_volume -> mash_volume"));
+        assert_eq!(dot.matches(" -> ").count(), 9);
+    }
+
+    #[test]
+    fn test_dry_yeast_grams_per_strain() {
+        let mut process = test_process();
+
+        process.recipe.yeast = Yeast::SafAleUS05;
+        let ale_grams = process.yeast_grams(None).unwrap();
+
+        process.recipe.yeast = Yeast::SafLagerW3470;
+        let lager_grams = process.yeast_grams(None).unwrap();
+
+        assert!(lager_grams.0 > ale_grams.0);
+    }
+
+    #[test]
+    fn test_priming_table() {
+        let process = test_process();
+        let table = process.priming_table();
+        assert_eq!(table.len(), 6);
+        for pair in table.windows(2) {
+            assert!(pair[1].0 > pair[0].0);
+            assert!(pair[1].1 > pair[0].1);
+        }
+    }
+
+    #[test]
+    fn test_style_autocorrect_og_ibu() {
+        let mut process = test_process();
+        process.recipe.original_gravity = SpecificGravity(1.030);
+        process.recipe.ibu = Ibu(60.0);
+
+        let suggestions = process.style_autocorrect();
+        assert!(
+            suggestions
+                .iter()
+                .any(|s| s.contains("original gravity target"))
+        );
+        assert!(suggestions.iter().any(|s| s.contains("IBU target")));
+
+        // Applying the suggested targets brings the beer into range
+        process.recipe.original_gravity = process.og_correction().unwrap();
+        process.recipe.ibu = process.ibu_correction().unwrap();
+        let og_range = process.recipe.style.original_gravity_range().unwrap();
+        let ibu_range = process.recipe.style.bitterness_range().unwrap();
+        assert!(og_range.contains(&process.achievable_og(process.grain_weight())));
+        assert!(ibu_range.contains(&process.bitterness()));
+        assert!(process.og_correction().is_none());
+        assert!(process.ibu_correction().is_none());
+        assert!(process.style_autocorrect().is_empty());
+    }
+
+    #[test]
+    fn test_style_autocorrect_color() {
+        let mut process = test_process();
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::GladfieldPilsner,
+            proportion: 100.,
+        }];
+        let range = process.recipe.style.color_range().unwrap();
+        assert!(process.color() < range.start);
+
+        let suggestions = process.style_autocorrect();
+        assert!(suggestions.iter().any(|s| s.contains("to raise the color")));
+
+        // Applying the suggested dose brings the color into range
+        let dose = process.color_correction().unwrap();
+        let multiplier = process.grain_bill_multiplier();
+        process.recipe.malts.push(MaltProportion {
+            malt: dose.malt,
+            proportion: dose.weight.0 / multiplier,
+        });
+        assert!(range.contains(&process.color()));
+        assert!(process.color_correction().is_none());
+    }
+
+    #[test]
+    fn test_water_acids() {
+        let mut process = test_process();
+        let acids = process.water_acids();
+        assert_eq!(acids.len(), 1);
+        assert_eq!(acids[0].acid, Acid::LacticAcid);
+        assert!(acids[0].ppm.0 > 0.0);
+        assert!(process.water_doses().contains("Lactic Acid"));
+
+        // The dose hits the target in the final rest
+        let ph = process.mash_ph().pop().unwrap();
+        assert!(approx_eq!(
+            f32,
+            ph.0,
+            process.recipe.mash_ph_target.0,
+            epsilon = 0.001
+        ));
+
+        // Falls back to what the brewery has
+        process.brewery.acids_available = vec![Acid::PhosphoricAcid];
+        assert_eq!(process.water_acids()[0].acid, Acid::PhosphoricAcid);
+
+        // Nothing needed when already acidic enough
+        process.recipe.mash_ph_target = Ph(6.5);
+        assert!(process.water_acids().is_empty());
+
+        // Warns when no acid is available
+        process.brewery.acids_available = vec![];
+        assert!(process.water_acids().is_empty());
+        assert!(
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::NoAcidAvailable))
+        );
+    }
+
+    #[test]
+    fn test_yeast_age_scales_pitch() {
+        let mut process = test_process();
+
+        process.recipe.yeast = Yeast::WLP001;
+        let fresh = process.yeast_cells(Some(Days(0)));
+        assert_eq!(fresh, process.yeast_cells(None));
+        assert!(process.yeast_cells(Some(Days(90))) > fresh * 2);
+
+        process.recipe.yeast = Yeast::SafAleUS05;
+        let fresh = process.yeast_grams(None).unwrap();
+        let aged = process.yeast_grams(Some(Days(90))).unwrap();
+        assert!(aged.0 > fresh.0);
+        assert!(aged.0 < fresh.0 * 1.1);
+    }
+
+    #[test]
+    fn test_boiling_point_at_elevation() {
+        let mut process = test_process();
+        assert!(approx_eq!(
+            f32,
+            process.boiling_point().0,
+            100.0,
+            epsilon = 0.01
+        ));
+        let sea_level_boil = process.recommended_boil_length();
+
+        // Denver
+        process.brewery.elevation = Meters(1600.0);
+        assert!(approx_eq!(
+            f32,
+            process.boiling_point().0,
+            94.6,
+            epsilon = 0.1
+        ));
+
+        process.recipe.malts[0].malt = Malt::WeyermannPilsner;
+        assert!(process.recommended_boil_length() > sea_level_boil);
+    }
+
+    #[test]
+    fn test_biab_has_no_sparge() {
+        let mut process = test_process();
+        let infusion_grain = process.grain_weight();
+
+        process.recipe.mash_method = MashMethod::Biab;
+        assert!(approx_eq!(f32, process.sparge_volume().0, 0.0));
+        assert!(approx_eq!(
+            f32,
+            process.mash_volume().0,
+            process.pre_boil_volume().0
+                + process.brewery.mash_tun_losses.0
+                + process.water_absorption().0,
+            epsilon = 0.001
+        ));
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::TooMuchMash { .. }))
+        );
+
+        // Lower efficiency needs more grain
+        assert!(process.grain_weight() > infusion_grain);
+    }
+
+    #[test]
+    fn test_decoction_mash_steps() {
+        let mut process = test_process();
+        assert!(matches!(process.mash_steps()[0], MashStep::Infusion { .. }));
+
+        process.recipe.decoction = true;
+        let steps = process.mash_steps();
+        assert_eq!(steps.len(), 1);
+        match steps[0] {
+            MashStep::Decoction {
+                target_temperature,
+                fraction_pulled,
+                volume,
+            } => {
+                assert_eq!(target_temperature, Celsius(69.0));
+                // 61 -> 69 of 100: about a fifth of the mash
+                assert!(approx_eq!(
+                    f32,
+                    fraction_pulled,
+                    8.0 / 39.0,
+                    epsilon = 0.001
+                ));
+                assert!(volume.0 > 0.0);
+            }
+            MashStep::Infusion { .. } => panic!("expected a decoction"),
+        }
+        assert!(process.mash_infusions().is_empty());
+        assert!(approx_eq!(
+            f32,
+            process.strike_volume().0,
+            process.mash_volume().0
+        ));
+    }
+
+    #[test]
+    fn test_ibu_models() {
+        let mut process = test_process();
+        let doses = process.hops_doses();
+        let mut ibus: Vec<f32> = Vec::new();
+        for model in [IbuModel::Tinseth, IbuModel::Rager, IbuModel::Garetz] {
+            process.recipe.ibu_model = model;
+
+            // The same hops give different bitterness under each model
+            let ibu: f32 = doses
+                .iter()
+                .map(|d| process.hops_ibu(d.alpha_acid(), d.weight, d.timing).0)
+                .sum();
+            assert!(ibu > 10.0 && ibu < 35.0, "{model}: {ibu}");
+            ibus.push(ibu);
+
+            // But each model doses to hit the target
+            assert!(approx_eq!(
+                f32,
+                process.bitterness().0,
+                process.recipe.ibu.0,
+                epsilon = 0.01
+            ));
+        }
+        assert!((ibus[0] - ibus[1]).abs() > 1.0);
+        assert!((ibus[0] - ibus[2]).abs() > 1.0);
+    }
+
+    #[test]
+    fn test_aged_hops_doses() {
+        let fresh = test_process();
+        let mut aged = test_process();
+        aged.recipe.hops[0].age = Some((Days(365), HopStorage::RoomTempOpen));
+
+        // Older hops need a larger dose to reach the same bitterness
+        let fresh_dose = fresh.hops_doses()[0];
+        let aged_dose = aged.hops_doses()[0];
+        assert!(aged_dose.weight.0 > fresh_dose.weight.0 * 3.0);
+        assert!(approx_eq!(
+            f32,
+            aged.bitterness().0,
+            aged.recipe.ibu.0,
+            epsilon = 0.01
+        ));
+    }
+
+    #[test]
+    fn test_first_wort_hops() {
+        let process = test_process();
+        let hops = Hops::HallertauMittelfruh;
+        let weight = Grams(30.0);
+        let ibu = process.recipe.ibu;
+
+        let fwh = process.addition_ibu(hops.alpha_acid(), weight, Minutes(0), true, None, ibu);
+        let twenty = process.hops_ibu(hops.alpha_acid(), weight, Minutes(20));
+        let sixty = process.hops_ibu(hops.alpha_acid(), weight, Minutes(60));
+        assert!(fwh.0 > twenty.0);
+        assert!(fwh.0 < sixty.0);
+        assert!(approx_eq!(f32, fwh.0, twenty.0 * 1.1, epsilon = 0.001));
+
+        // First-wort hops are not boil additions
+        let mut process = test_process();
+        process.recipe.hops[0].first_wort = true;
+        assert!(process.hops_additions_string().is_empty());
+        assert!(approx_eq!(
+            f32,
+            process.bitterness().0,
+            ibu.0,
+            epsilon = 0.01
+        ));
+    }
+
+    #[test]
+    fn test_whirlpool_hops() {
+        let process = test_process();
+        let hops = Hops::HallertauMittelfruh;
+        let weight = Grams(30.0);
+        let ibu = process.recipe.ibu;
+        let whirlpool = Some((Celsius(80.0), Minutes(20)));
+
+        let flameout =
+            process.addition_ibu(hops.alpha_acid(), weight, Minutes(0), false, None, ibu);
+        let stand =
+            process.addition_ibu(hops.alpha_acid(), weight, Minutes(0), false, whirlpool, ibu);
+        let boiled = process.hops_ibu(hops.alpha_acid(), weight, Minutes(20));
+        assert!(stand.0 > flameout.0);
+        assert!(stand.0 < boiled.0);
+
+        // A stand at boiling temperature continues the boil, rather than
+        // counting the boil minutes again
+        let at_boil = Some((Celsius(100.0), Minutes(20)));
+        let held =
+            process.addition_ibu(hops.alpha_acid(), weight, Minutes(60), false, at_boil, ibu);
+        let longer = process.hops_ibu(hops.alpha_acid(), weight, Minutes(80));
+        assert!(approx_eq!(f32, held.0, longer.0, epsilon = 0.01));
+    }
+
+    #[test]
+    fn test_all_sugar_recipe() {
+        let mut process = test_process();
+        process.recipe.malts = vec![];
+        process.recipe.sugars = vec![SugarProportion {
+            sugar: Sugar::Dextrose,
+            proportion: 1.0,
+        }];
+
+        let multiplier = process.grain_bill_multiplier();
+        assert!(multiplier.is_finite() && multiplier > 0.0);
+
+        // The sugar alone reaches the gravity
+        let sg = SpecificGravity::from_recipe(
+            &[],
+            &process.sugar_doses(),
+            &[],
+            process.post_boil_pre_loss_volume(),
+            process.mash_efficiency(),
+        );
+        assert!(approx_eq!(
+            f32,
+            sg.0,
+            process.post_boil_gravity().0,
+            epsilon = 0.0001
+        ));
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::NoFermentables))
+        );
+    }
+
+    #[test]
+    fn test_no_fermentables() {
+        let mut process = test_process();
+        process.recipe.malts = vec![];
+        process.recipe.sugars = vec![];
+
+        assert!(approx_eq!(f32, process.grain_bill_multiplier(), 0.0));
+        assert!(process.malt_doses().is_empty());
+        assert!(
+            process
+                .get_errors()
+                .iter()
+                .any(|w| matches!(w, Warning::NoFermentables))
+        );
+    }
+
+    #[test]
+    fn test_low_conversion_power() {
+        let mut process = test_process();
+        assert!(process.total_diastatic_power() > Lintner(30.0));
+
+        // Malted oats count as base malt, but have little enzyme
+        process.recipe.malts = vec![
+            MaltProportion {
+                malt: Malt::GladfieldPilsner,
+                proportion: 25.0,
+            },
+            MaltProportion {
+                malt: Malt::GladfieldBigOMaltedOats,
+                proportion: 50.0,
+            },
+            MaltProportion {
+                malt: Malt::FlakedRice,
+                proportion: 25.0,
+            },
+        ];
+        let warnings = process.get_warnings();
+        assert!(
+            !warnings
+                .iter()
+                .any(|w| matches!(w, Warning::LowDiastaticPower { .. }))
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| matches!(w, Warning::LowConversionPower { .. }))
+        );
+    }
+
+    #[test]
+    fn test_partial_mash_with_extract() {
+        let mut process = test_process();
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::GladfieldPilsner,
+            proportion: 50.0,
+        }];
+        process.recipe.extracts = vec![ExtractProportion {
+            extract: MaltExtract::LightDme,
+            proportion: 50.0,
+        }];
+
+        let sg = SpecificGravity::from_recipe(
+            &process.malt_doses(),
+            &[],
+            &process.extract_doses(),
+            process.post_boil_pre_loss_volume(),
+            process.mash_efficiency(),
+        );
+        assert!(approx_eq!(
+            f32,
+            sg.0,
+            process.post_boil_gravity().0,
+            epsilon = 0.0001
+        ));
+
+        // Extract does not go through the mash, so it yields more than the
+        // same weight of malt
+        let malt = &process.malt_doses()[0];
+        let extract = &process.extract_doses()[0];
+        assert!(approx_eq!(
+            f32,
+            malt.weight.0,
+            extract.weight.0,
+            epsilon = 0.0001
+        ));
+        let malt_points = malt.malt.pkl() * malt.weight.0 * process.mash_efficiency();
+        let extract_points = extract.extract.pkl() * extract.weight.0;
+        assert!(extract_points > malt_points);
+
+        assert!(process.fan_from_malt().0 > 0.0);
+        assert!(process.color().0 > 0.0);
+    }
+
+    #[test]
+    fn test_diastaticus_risk() {
+        let has_risk = |process: &Process| {
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::DiastaticusRisk(_)))
+        };
+
+        let mut process = test_process();
+        process.recipe.yeast = Yeast::WLP001;
+        assert!(!has_risk(&process));
+        let clean_fg = process.final_gravity();
+
+        process.recipe.yeast = Yeast::WLP565;
+        assert!(has_risk(&process));
+
+        // STA1 keeps going past the listed attenuation
+        let og = process.recipe.original_gravity.0;
+        let listed = SpecificGravity(og - (og - 1.0) * Yeast::WLP565.attenuation());
+        assert!(process.post_ferment_gravity() < listed);
+        assert!(process.final_gravity().0 < clean_fg.0);
+    }
+
+    #[test]
+    fn test_fermentability() {
+        let mash_at = |temperature: f32| {
+            let mut process = test_process();
+            process.recipe.mash_rests = vec![MashRest {
+                target_temperature: Celsius(temperature),
+                duration: Minutes(60),
+            }];
+            process
+        };
+
+        let low = mash_at(63.0);
+        let high = mash_at(70.0);
+        assert!(low.mash_fermentability() > 1.0);
+        assert!(high.mash_fermentability() < 1.0);
+        assert!(high.post_ferment_gravity() > low.post_ferment_gravity());
+        assert!(high.abv() < low.abv());
+
+        // A protein rest does not count
+        let mut process = mash_at(66.0);
+        process.recipe.mash_rests.insert(
+            0,
+            MashRest {
+                target_temperature: Celsius(50.0),
+                duration: Minutes(20),
+            },
+        );
+        assert!(approx_eq!(
+            f32,
+            process.mash_fermentability(),
+            1.0,
+            epsilon = 0.0001
+        ));
+
+        // Sugar ferments out, roast barely ferments
+        let base = process.effective_attenuation();
+        process.recipe.sugars = vec![SugarProportion {
+            sugar: Sugar::Sucrose,
+            proportion: 15.0,
+        }];
+        assert!(process.effective_attenuation() > base);
+        process.recipe.sugars = vec![];
+        process.recipe.malts.push(MaltProportion {
+            malt: Malt::WeyermannCarafaSpecial3,
+            proportion: 10.0,
+        });
+        assert!(process.effective_attenuation() < base);
+    }
+
+    #[test]
+    fn test_mash_body() {
+        let mut process = test_process();
+        process.recipe.mash_rests = vec![MashRest {
+            target_temperature: Celsius(63.0),
+            duration: Minutes(60),
+        }];
+        let cool_fg = process.final_gravity();
+        assert!(process.mash_body_note().unwrap().contains("dry"));
+
+        process.recipe.mash_rests[0].target_temperature = Celsius(65.0);
+        let medium_fg = process.final_gravity();
+        assert!(process.mash_body_note().unwrap().contains("medium"));
+
+        process.recipe.mash_rests[0].target_temperature = Celsius(70.0);
+        let hot_fg = process.final_gravity();
+        assert!(process.mash_body_note().unwrap().contains("fuller"));
+
+        assert!(cool_fg < medium_fg);
+        assert!(medium_fg < hot_fg);
+    }
+
+    #[test]
+    fn test_style_warnings() {
+        let mut process = test_process();
+        let warnings = process.get_warnings();
+        assert!(
+            !warnings
+                .iter()
+                .any(|w| matches!(w, Warning::OriginalGravityOutOfRange { .. }))
+        );
+        assert!(!warnings.iter().any(|w| matches!(w, Warning::TooHot { .. })));
+
+        process.recipe.original_gravity = SpecificGravity(1.100);
+        assert!(
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::OriginalGravityOutOfRange { .. }))
+        );
+
+        let mut process = test_process();
+        process.recipe.ferment_temperature = process.recipe.yeast.temp_range().start - Celsius(1.0);
+        let warnings = process.get_warnings();
+        assert!(
+            warnings
+                .iter()
+                .any(|w| matches!(w, Warning::TooCold { .. }))
+        );
+        assert!(!warnings.iter().any(|w| matches!(w, Warning::TooHot { .. })));
+    }
+
+    #[test]
+    fn test_yeast_nutrient_dose() {
+        // With no malt, all of the yeast FAN requirement comes from nutrient
+        let mut process = test_process();
+        process.recipe.malts = vec![];
+        process.recipe.sugars = vec![SugarProportion {
+            sugar: Sugar::Dextrose,
+            proportion: 1.0,
+        }];
+
+        process.brewery.yeast_nutrient = YeastNutrient::FermaidO;
+        let organic = process.yeast_nutrient_amount();
+        let organic_zinc = process.zinc_needed();
+        assert!(approx_eq!(
+            f32,
+            process.wort_fan().0,
+            process.recipe.fan_requirement_of_yeast().0,
+            epsilon = 0.01
+        ));
+
+        process.brewery.yeast_nutrient = YeastNutrient::DapPlusZinc;
+        let dap = process.yeast_nutrient_amount();
+        assert!(approx_eq!(
+            f32,
+            process.wort_fan().0,
+            process.recipe.fan_requirement_of_yeast().0,
+            epsilon = 0.01
+        ));
+
+        // Organic nutrient needs a much bigger dose
+        assert!(dap > Grams(0.0));
+        assert!(approx_eq!(
+            f32,
+            organic.0 / dap.0,
+            150.0 / 40.0,
+            epsilon = 0.001
+        ));
+
+        // The zinc in the DAP blend reduces the supplemental zinc, but a
+        // dose this big brings in too much
+        assert!(process.zinc_needed() < organic_zinc);
+        assert!(process.nutrient_zinc() > ZINC_MAX);
+        assert!(
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::ExcessZinc { .. }))
+        );
+
+        // Servomyces is dosed for zinc, 0.2 mg/L at 20 mg/g
+        process.brewery.yeast_nutrient = YeastNutrient::Servomyces;
+        let servomyces = process.yeast_nutrient_amount();
+        assert!(approx_eq!(
+            f32,
+            servomyces.0,
+            0.2 * process.batch_size.0 / 20.0,
+            epsilon = 0.0001
+        ));
+        assert!(servomyces < Grams(1.0));
+        assert!(approx_eq!(
+            f32,
+            process.nutrient_zinc().0,
+            ZINC_TARGET.0,
+            epsilon = 0.0001
+        ));
+        assert!(process.zinc_needed() < Milligrams(0.001));
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::ExcessZinc { .. }))
+        );
+    }
+
+    #[test]
+    fn test_diacetyl_rest_temperature() {
+        let mut process = test_process();
+        process.recipe.yeast = Yeast::SafLagerW3470;
+        process.recipe.ferment_temperature = Celsius(12.0);
+        let rest = process.recipe.diacetyl_rest_temperature();
+        assert!(approx_eq!(f32, rest.0, 18.0, epsilon = 0.5));
+        assert!(rest <= Yeast::SafLagerW3470.temp_range().end);
+
+        // Bounded by the yeast range
+        process.recipe.ferment_temperature = Celsius(15.0);
+        let rest = process.recipe.diacetyl_rest_temperature();
+        assert!(approx_eq!(f32, rest.0, 18.0, epsilon = 0.001));
+
+        // Ales rest at their ferment temperature
+        process.recipe.yeast = Yeast::SafAleUS05;
+        process.recipe.ferment_temperature = Celsius(19.0);
+        assert_eq!(process.recipe.diacetyl_rest_temperature(), Celsius(19.0));
+    }
+
+    #[test]
+    fn test_recipe_scaled_to() {
+        let recipe = test_recipe();
+        let single = recipe.scaled_to(Liters(20.0));
+        let double = recipe.scaled_to(Liters(40.0));
+
+        // Proportions become weights, which double with the batch
+        let (malt_kg, _) = recipe.weight_scales(Liters(20.0));
+        for (original, frozen) in recipe.malts.iter().zip(&single.malts) {
+            assert!(approx_eq!(
+                f32,
+                frozen.proportion,
+                original.proportion * malt_kg,
+                epsilon = 0.0001
+            ));
+        }
+        for (s, d) in single.malts.iter().zip(&double.malts) {
+            assert!(approx_eq!(
+                f32,
+                d.proportion / s.proportion,
+                2.0,
+                epsilon = 0.001
+            ));
+        }
+        for (s, d) in single.hops.iter().zip(&double.hops) {
+            assert!(approx_eq!(
+                f32,
+                d.proportion / s.proportion,
+                2.0,
+                epsilon = 0.001
+            ));
+            assert_eq!(s.timing, d.timing);
+        }
+        assert!(approx_eq!(
+            f32,
+            double.original_gravity.0,
+            recipe.original_gravity.0
+        ));
+        assert!(approx_eq!(f32, double.ibu.0, recipe.ibu.0));
+        assert!(approx_eq!(
+            f32,
+            double.mash_thickness,
+            recipe.mash_thickness
+        ));
+
+        // A process brews the same beer from the frozen recipe
+        let process = test_process();
+        let frozen = Process {
+            recipe: double,
+            ..process.clone()
+        };
+        for (a, b) in process.malt_doses().iter().zip(&frozen.malt_doses()) {
+            assert!(approx_eq!(f32, a.weight.0, b.weight.0, epsilon = 0.001));
+        }
+        for (a, b) in process.hops_doses().iter().zip(&frozen.hops_doses()) {
+            assert!(approx_eq!(f32, a.weight.0, b.weight.0, epsilon = 0.001));
+        }
+    }
+
+    #[test]
+    fn test_rescale() {
+        let mut process = test_process();
+        // Room for a double batch, so there is no partial boil
+        process.brewery.mash_tun_volume = Liters(60.0);
+        process.brewery.max_kettle_volume = Liters(72.0);
+        process.brewery.fermenters = vec![Liters(60.0)];
+        let doubled = process.rescale(process.batch_size * 2.0);
+        assert!(approx_eq!(f32, doubled.batch_size.0, 40.0, epsilon = 0.001));
+
+        for (single, double) in process.malt_doses().iter().zip(&doubled.malt_doses()) {
+            assert!(approx_eq!(
+                f32,
+                double.weight.0 / single.weight.0,
+                2.0,
+                epsilon = 0.1
+            ));
+        }
+
+        for (single, double) in process.hops_doses().iter().zip(&doubled.hops_doses()) {
+            assert_eq!(single.timing, double.timing);
+        }
+
+        let og = |p: &Process| {
+            SpecificGravity::from_recipe(
+                &p.malt_doses(),
+                &p.sugar_doses(),
+                &p.extract_doses(),
+                p.post_boil_pre_loss_volume(),
+                p.mash_efficiency(),
+            )
+        };
+        assert!(approx_eq!(
+            f32,
+            og(&process).0,
+            og(&doubled).0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            process.bitterness().0,
+            doubled.bitterness().0,
+            epsilon = 0.5
+        ));
+        assert!(approx_eq!(
+            f32,
+            process.recipe.mash_thickness,
+            doubled.recipe.mash_thickness
+        ));
+    }
+
+    #[test]
+    fn test_grist_mash_ph() {
+        let mut process = test_process();
+        process.brewery.water_profile = WaterProfile::PILSEN;
+        process.recipe.ph_method = PhMethod::None;
+
+        // Pale base malt in soft water lands near its distilled water mash
+        // pH, which Kaiser measured at 5.76 for Pilsner malt
+        process.recipe.malts = vec![MaltProportion {
+            malt: Malt::WeyermannPilsner,
+            proportion: 1.0,
+        }];
+        let pale = process.mash_ph();
+        for ph in &pale {
+            assert!(ph.0 > 5.6 && ph.0 < 5.9, "{ph}");
+        }
+
+        // Roasted malt is acidic and pulls the pH down
+        process.recipe.malts = vec![
+            MaltProportion {
+                malt: Malt::WeyermannPilsner,
+                proportion: 0.9,
+            },
+            MaltProportion {
+                malt: Malt::WeyermannCarafaSpecial3,
+                proportion: 0.1,
+            },
+        ];
+        let roasty = process.mash_ph();
+        for (r, p) in roasty.iter().zip(&pale) {
+            assert!(*p - *r > 0.05);
+        }
+
+        // Alkaline water pushes it up
+        process.brewery.water_profile = WaterProfile::DUBLIN;
+        for (d, r) in process.mash_ph().iter().zip(&roasty) {
+            assert!(d > r);
+        }
+    }
+
+    #[test]
+    fn test_mash_thickness_ph() {
+        let mut process = test_process();
+        process.brewery.water_profile = WaterProfile::PILSEN;
+        process.recipe.ph_method = PhMethod::None;
+
+        process.recipe.mash_thickness = 2.0;
+        let thick = process.mash_ph().pop().unwrap();
+
+        process.recipe.mash_thickness = 4.0;
+        let thin = process.mash_ph().pop().unwrap();
+
+        let difference = thin - thick;
+        assert!(difference > 0.0);
+        assert!(difference < 0.1);
+    }
+
+    #[test]
+    fn test_sparge_acid() {
+        let mut process = test_process();
+        process.brewery.water_profile = WaterProfile::DUBLIN;
+        assert!(process.sparge_volume() > Liters(0.0));
+
+        let doses = process.sparge_acid_doses();
+        assert_eq!(doses.len(), 1);
+        assert!(doses[0].mg.0 > 0.0);
+
+        // Soft water needs less
+        process.brewery.water_profile = WaterProfile::PILSEN;
+        assert!(process.sparge_acid_doses()[0].mg.0 < doses[0].mg.0);
+
+        // Nothing to sparge
+        process.recipe.mash_method = MashMethod::Biab;
+        assert!(process.sparge_acid_doses().is_empty());
+    }
+
+    #[test]
+    fn test_mash_tun_thermal_mass() {
+        let mut process = test_process();
+        let light = process.strike_temperature().unwrap();
+
+        // A cold metal tun (5 kg of steel) needs hotter strike water
+        process.brewery.mash_tun_thermal_mass = Liters(0.6);
+        let heavy = process.strike_temperature().unwrap();
+        assert!(heavy.0 > light.0 + 1.0);
+        assert!(heavy.0 < light.0 + 3.0);
+
+        // Unless it is preheated. It still takes some of the heat of the
+        // step infusions, so the strike volume differs a little.
+        process.brewery.preheat_mash_tun = true;
+        assert!(approx_eq!(
+            f32,
+            process.strike_temperature().unwrap().0,
+            light.0,
+            epsilon = 0.5
+        ));
+    }
+
+    #[test]
+    fn test_dry_hops() {
+        let mut process = test_process();
+        let losses = process.ferment_losses();
+        assert!(
+            process
+                .get_warnings()
+                .iter()
+                .all(|w| !matches!(w, Warning::HopCreep { .. }))
+        );
+
+        process.recipe.dry_hops = vec![DryHopAddition {
+            hops: Hops::Cascade,
+            grams_per_liter: 5.0,
+            day: Days(5),
+            duration: Days(3),
+        }];
+        assert!(process.ferment_losses() > losses);
+        assert!(approx_eq!(
+            f32,
+            (process.ferment_losses() - losses).0,
+            process.dry_hops_absorption().0,
+            epsilon = 0.001
+        ));
+        assert!(
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::HopCreep { .. }))
+        );
+
+        let output = crate::print_process(&process, None, None, crate::UnitSystem::Metric);
+        assert!(output.contains("Dry hop: On day 5"));
+        assert!(output.contains("Hop creep"));
+    }
+
+    #[test]
+    fn test_hop_mass() {
+        let mut process = test_process();
+
+        // A Märzen is malty
+        assert!(process.bu_gu_ratio() < 0.5);
+
+        let split = process.hop_mass_split();
+        let boil: f32 = process.hops_doses().iter().map(|d| d.weight.0).sum();
+        assert!(approx_eq!(
+            f32,
+            process.total_hop_mass().0,
+            boil,
+            epsilon = 0.001
+        ));
+
+        process.recipe.dry_hops = vec![
+            DryHopAddition {
+                hops: Hops::Cascade,
+                grams_per_liter: 1.0,
+                day: Days(3),
+                duration: Days(4),
+            },
+            DryHopAddition {
+                hops: Hops::Citra,
+                grams_per_liter: 3.0,
+                day: Days(7),
+                duration: Days(3),
+            },
+        ];
+        process.recipe.set_dry_hop_rate(8.0);
+        assert!(approx_eq!(
+            f32,
+            process.recipe.dry_hop_rate(),
+            8.0,
+            epsilon = 0.001
+        ));
+        assert!(approx_eq!(
+            f32,
+            process.recipe.dry_hops[1].grams_per_liter,
+            6.0,
+            epsilon = 0.001
+        ));
+
+        let dry_split = process.hop_mass_split();
+        assert!(approx_eq!(
+            f32,
+            dry_split.aroma.0 - split.aroma.0,
+            8.0 * process.batch_size.0,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f32,
+            dry_split.bittering.0,
+            split.bittering.0,
+            epsilon = 0.001
+        ));
+    }
+
+    #[test]
+    fn test_hops_absorption() {
+        let mut light = test_process();
+        light.recipe.ibu = Ibu(15.0);
+        let mut heavy = light.clone();
+        heavy.recipe.ibu = Ibu(90.0);
+
+        // Hops soak up wort in proportion to their weight
+        assert!(heavy.hops_absorption() > light.hops_absorption());
+        let grams = heavy.total_hop_mass().0;
+        assert!(approx_eq!(
+            f32,
+            heavy.hops_absorption().0,
+            heavy.brewery.hops_absorption_per_kg.0 * grams / 1000.0,
+            epsilon = 0.001
+        ));
+
+        // With a full boil, more water is boiled to make up for it
+        assert!(approx_eq!(
+            f32,
+            heavy.pre_boil_volume().0 - light.pre_boil_volume().0,
+            heavy.hops_absorption().0 - light.hops_absorption().0,
+            epsilon = 0.001
+        ));
+        assert!(heavy.volume_history_string().contains("Hops Absorbed:"));
+
+        // With a small kettle, less wort is left after the boil
+        light.brewery.max_kettle_volume = Liters(20.0);
+        heavy.brewery.max_kettle_volume = Liters(20.0);
+        assert!(heavy.post_boil_volume().0 < light.post_boil_volume().0 - 0.1);
+    }
+
+    #[test]
+    fn test_crash_vacuum_volume() {
+        let mut process = test_process();
+        process.recipe.yeast = Yeast::WLP001;
+        process.recipe.ferment_temperature = Celsius(20.0);
+
+        let headspace = process.fermenter_volume() - process.post_ferment_volume();
+        assert!(headspace > Liters(5.0));
+
+        // 273.15 / 293.15 of the gas is left, so 20°C to 0°C shrinks it by about 7%
+        let contraction = process.crash_vacuum_volume();
+        assert!(approx_eq!(
+            f32,
+            contraction.0,
+            headspace.0 * 20.0 / 293.15,
+            epsilon = 0.001
+        ));
+        assert!(contraction > Liters(0.3) && contraction < Liters(1.0));
+
+        // No contraction if not cooled
+        assert_eq!(process.crash_vacuum_volume_to(Celsius(20.0)), Liters(0.0));
+    }
+
+    #[test]
+    fn test_candi_syrup() {
+        let plain = test_process();
+        let mut candi = test_process();
+        candi.recipe.sugars = vec![SugarProportion {
+            sugar: Sugar::CandiSyrupD180,
+            proportion: 15.0,
+        }];
+
+        assert!(candi.color().0 > plain.color().0 + 5.0);
+        assert!(candi.post_ferment_gravity().0 < plain.post_ferment_gravity().0);
+
+        // The same weight of clear candi sugar adds no color but more points
+        assert!(Sugar::CandiSugarClear.ebc().0 < Sugar::CandiSyrupD45.ebc().0);
+        assert!(Sugar::CandiSugarClear.ppg() > Sugar::CandiSyrupD180.ppg());
+    }
+
+    #[test]
+    fn test_lactose() {
+        let plain = test_process();
+        let mut sweet = test_process();
+
+        // About 500 g of lactose in the 20 L batch
+        sweet.recipe.sugars = vec![SugarProportion {
+            sugar: Sugar::Lactose,
+            proportion: 0.5 / plain.grain_bill_multiplier(),
+        }];
+        let lactose = sweet.sugar_doses()[0].weight;
+        assert!(lactose.0 > 0.4 && lactose.0 <= 0.5);
+
+        // Lactose adds about 41 ppg, none of which ferments
+        let points = sweet.unfermentable_sugar_points();
+        assert!(points > 7.0 && points < 10.0);
+        assert!(plain.unfermentable_sugar_points() <= 0.0);
+
+        // So the final gravity is measurably higher
+        let raised = sweet.post_ferment_gravity().points() - plain.post_ferment_gravity().points();
+        assert!(raised > 5.0);
+
+        let output = crate::print_process(&sweet, None, None, crate::UnitSystem::Metric);
+        assert!(output.contains("Residual sweetness"));
+        assert!(!sweet.dietary_report().vegan);
+    }
+
+    #[test]
+    fn test_target_dissolved_oxygen() {
+        let mut strong = test_process();
+        strong.recipe.style = Style::BelgianDarkStrongAle;
+        strong.recipe.yeast = Yeast::WLP001;
+        strong.recipe.original_gravity = SpecificGravity(1.092);
+
+        let mut mild = test_process();
+        mild.recipe.style = Style::DarkMild;
+        mild.recipe.yeast = Yeast::WLP001;
+        mild.recipe.original_gravity = SpecificGravity(1.036);
+
+        assert!(strong.target_dissolved_oxygen().0 > mild.target_dissolved_oxygen().0 + 2.0);
+        assert!(approx_eq!(
+            f32,
+            mild.target_dissolved_oxygen().0,
+            8.0,
+            epsilon = 0.001
+        ));
+        assert!(strong.pure_oxygen_seconds() > mild.pure_oxygen_seconds());
+
+        // Dry yeast needs none
+        mild.recipe.yeast = Yeast::SafAleS04;
+        assert!(mild.target_dissolved_oxygen().0 <= 0.0);
+    }
+
+    #[test]
+    fn test_unweighable_salt_dose() {
+        // A tiny calcium chloride dose is made up as a stock solution
+        let dose = SaltDose {
+            salt: Salt::CalciumChloride,
+            mg: Milligrams(50.0),
+        };
+        let ml = dose.as_stock_solution(SaltDose::STOCK_G_PER_L);
+        assert!(approx_eq!(f32, ml.0, 5.0, epsilon = 0.001));
+        let warning = Warning::UnweighableSaltDose {
+            salt: dose.salt,
+            mg: dose.mg,
+        };
+        assert!(!warning.is_error());
+        assert!(warning.to_string().contains("add 5 mL"));
+
+        // A small batch needs a small dose of gypsum
+        let mut process = test_process();
+        process.recipe.ph_method = PhMethod::AdjustWater;
+        process.batch_size = Liters(5.0);
+        let small: Vec<SaltDose> = process
+            .salt_doses(None)
+            .into_iter()
+            .filter(|d| d.mg.0 < SaltDose::MIN_WEIGHABLE.0)
+            .collect();
+        assert!(!small.is_empty());
+        let warnings = process.get_warnings();
+        for dose in &small {
+            assert!(warnings.iter().any(|w| matches!(
+                w,
+                Warning::UnweighableSaltDose { salt, .. } if *salt == dose.salt
+            )));
+        }
+
+        // But not at the full batch size
+        process.batch_size = Liters(20.0);
+        assert!(
+            !process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::UnweighableSaltDose { .. }))
+        );
+    }
+
+    #[test]
+    fn test_gravity_points_unchanged() {
+        let process = test_process();
+        let og = process.recipe.original_gravity.0;
+
+        let attenuation = process.effective_attenuation();
+        assert!(approx_eq!(
+            f32,
+            process.post_ferment_gravity().0,
+            og - (og - 1.0) * attenuation,
+            epsilon = 0.000_01
+        ));
+
+        let ratio = process.post_ferment_volume().0 / process.product_volume().0;
+        assert!(approx_eq!(
+            f32,
+            process.final_gravity().0,
+            1.0 + (process.post_ferment_gravity().0 - 1.0) * ratio,
+            epsilon = 0.000_01
+        ));
+
+        assert!(approx_eq!(
+            f32,
+            process.post_boil_gravity().0,
+            1.0 + (og - 1.0) * process.batch_size.0 / process.post_boil_volume().0,
+            epsilon = 0.000_01
+        ));
+    }
+
+    #[test]
+    fn test_estimated_conversion_time() {
+        let mut hot = test_process();
+        hot.recipe.mash_rests = vec![MashRest {
+            target_temperature: Celsius(67.0),
+            duration: Minutes(60),
+        }];
+        let mut cool = hot.clone();
+        cool.recipe.mash_rests[0].target_temperature = Celsius(62.0);
+
+        assert!(hot.estimated_conversion_time() < cool.estimated_conversion_time());
+
+        // Unmalted adjuncts slow it down
+        let mut adjunct = hot.clone();
+        adjunct.recipe.malts.push(MaltProportion {
+            malt: Malt::FlakedRice,
+            proportion: 40.0,
+        });
+        assert!(adjunct.estimated_conversion_time() > hot.estimated_conversion_time());
+    }
+
+    #[test]
+    fn test_ibu_breakdown() {
+        let mut process = test_process();
+        process.recipe.hops.push(HopsProportion {
+            hops: Hops::Saaz,
+            proportion: 4.0,
+            timing: Minutes(15),
+            first_wort: false,
+            whirlpool: None,
+            age: None,
+            measured_alpha_acid: None,
+        });
+        process.recipe.hops.push(HopsProportion {
+            hops: Hops::HallertauMittelfruh,
+            proportion: 3.0,
+            timing: Minutes(0),
+            first_wort: false,
+            whirlpool: Some((Celsius(80.0), Minutes(20))),
+            age: None,
+            measured_alpha_acid: None,
+        });
+        for model in [IbuModel::Tinseth, IbuModel::Rager, IbuModel::Garetz] {
+            process.recipe.ibu_model = model;
+            let breakdown = process.ibu_breakdown();
+            assert_eq!(breakdown.len(), process.hops_doses().len());
+            assert!(breakdown.iter().all(|(_, ibu)| ibu.0 > 0.0));
+            let sum: f32 = breakdown.iter().map(|(_, ibu)| ibu.0).sum();
+            assert!(approx_eq!(
+                f32,
+                sum,
+                process.bitterness().0,
+                epsilon = 0.001
+            ));
+        }
+    }
+
+    #[test]
+    fn test_achievable_og() {
+        let process = test_process();
+
+        // The recipe's own grain weight reaches its original gravity
+        let og = process.achievable_og(process.grain_weight());
+        assert!(approx_eq!(
+            f32,
+            og.0,
+            process.recipe.original_gravity.0,
+            epsilon = 0.0001
+        ));
+
+        // grain -> OG -> grain
+        let og = process.achievable_og(Kilograms(5.0));
+        assert!(og < process.recipe.original_gravity);
+        assert!(approx_eq!(
+            f32,
+            process.grain_needed_for_og(og).0,
+            5.0,
+            epsilon = 0.001
+        ));
+    }
+
+    #[test]
+    fn test_baking_soda_raises_mash_ph() {
+        let mut process = test_process();
+        process.brewery.water_profile = WaterProfile::PILSEN;
+        process.brewery.ro_fraction = None;
+        process.recipe.malts = vec![
+            MaltProportion {
+                malt: Malt::GladfieldPilsner,
+                proportion: 50.0,
+            },
+            MaltProportion {
+                malt: Malt::GladfieldBrown,
+                proportion: 25.0,
+            },
+            MaltProportion {
+                malt: Malt::GladfieldCrystalDark,
+                proportion: 25.0,
+            },
+        ];
+        process.recipe.mash_ph_target = Ph(5.4);
+
+        let target = process.recipe.mash_ph_target;
+        assert!(process.mash_ph_with(&WaterProfile::PILSEN).pop().unwrap() < target);
+
+        let salts = process.water_salts();
+        assert_eq!(salts.len(), 1);
+        assert_eq!(salts[0].salt, Salt::BakingSoda);
+        assert!(process.water_acids().is_empty());
+        assert!(approx_eq!(
+            f32,
+            process.mash_ph().pop().unwrap().0,
+            target.0,
+            epsilon = 0.01
+        ));
+        let cancelling = |process: &Process| {
+            process
+                .get_warnings()
+                .iter()
+                .any(|w| matches!(w, Warning::AcidityNeededCancelling))
+        };
+        assert!(!cancelling(&process));
+
+        // It is dosed into the mash water, not the total water
+        let doses = process.salt_doses(None);
+        assert_eq!(doses.len(), 1);
+        assert!(approx_eq!(
+            f32,
+            doses[0].mg.0,
+            salts[0].ppm.0 * process.mash_volume().0,
+            epsilon = 0.01
+        ));
+        assert!(process.water_doses().contains("to the mash water"));
+
+        // With acidulated malt, that acidity had to be cancelled
+        process.recipe.malts.push(MaltProportion {
+            malt: Malt::WeyermannAcidulated,
+            proportion: 3.0,
+        });
+        assert!(cancelling(&process));
+    }
+
+    #[test]
+    fn test_measured_alpha_acid() {
+        let mut process = test_process();
+        let default = process.recipe.hops[0].hops.alpha_acid();
+        let before = process.hops_doses()[0];
+
+        process.recipe.hops[0].measured_alpha_acid = Some(default * 2.0);
+        let after = process.hops_doses()[0];
+        assert!(approx_eq!(f32, after.alpha_acid(), default * 2.0));
+
+        // The same weight of the stronger crop is twice as bitter
+        let ibu = |dose: &HopsDose, weight: Grams| {
+            process
+                .addition_ibu(
+                    dose.alpha_acid(),
+                    weight,
+                    dose.timing,
+                    dose.first_wort,
+                    dose.whirlpool,
+                    process.recipe.ibu,
+                )
+                .0
+        };
+        assert!(approx_eq!(
+            f32,
+            ibu(&after, before.weight),
+            2.0 * ibu(&before, before.weight),
+            epsilon = 0.01
+        ));
+
+        // So half as much is dosed to hit the target
+        assert!(approx_eq!(
+            f32,
+            after.weight.0,
+            before.weight.0 / 2.0,
+            epsilon = 0.01
+        ));
+        assert!(approx_eq!(
+            f32,
+            process.bitterness().0,
+            process.recipe.ibu.0,
+            epsilon = 0.01
+        ));
+    }
+
+    #[test]
+    fn test_extract_only() {
+        let mut process = test_process();
+        process.recipe.malts = vec![];
+        process.recipe.extracts = vec![ExtractProportion {
+            extract: MaltExtract::LightDme,
+            proportion: 100.0,
+        }];
+        process.recipe.mash_rests = vec![];
+
+        assert!(!process.has_mash());
+        assert!(process.grain_weight().0.is_sign_positive());
+        assert!(process.mash_ph().is_empty());
+        assert!(process.mash_thicknesses().is_empty());
+        assert!(process.mash_steps().is_empty());
+        assert_eq!(process.strike_volume(), Liters(0.0));
+        assert!(process.strike_temperature().is_none());
+        assert!(process.first_runnings_gravity().is_none());
+        assert!(process.sparge_runnings_gravity().is_none());
+        assert!(process.summary().strike_temperature.is_none());
+        assert!(process.water_salts().is_empty());
+        assert!(process.water_acids().is_empty());
+        assert!(process.sparge_acid_doses().is_empty());
+        assert!(process.mash_body_note().is_none());
+        assert!(approx_eq!(f32, process.mash_fermentability(), 1.0));
+
+        // All of the water goes into the kettle
+        assert!(approx_eq!(
+            f32,
+            process.total_water().0,
+            process.pre_boil_volume().0,
+            epsilon = 0.001
+        ));
+
+        assert!(!process.get_warnings().iter().any(|w| matches!(
+            w,
+            Warning::LowDiastaticPower { .. }
+                | Warning::LowConversionPower { .. }
+                | Warning::MashPhOutOfRange(..)
+        )));
+
+        let printed = crate::print_process(&process, None, None, crate::UnitSystem::Metric);
+        assert!(
+            !printed
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word == "NaN" || word == "inf")
+        );
+        assert!(!printed.contains("-0.000"));
+        assert!(!printed.contains("---MASH"));
+        assert!(printed.contains("Fill the boil kettle with"));
+        assert!(!printed.contains("mash temperature"));
+        assert!(
+            process
+                .brew_day_timeline(Minutes(0))
+                .iter()
+                .all(|e| !e.action.starts_with("Mash"))
+        );
+    }
 }
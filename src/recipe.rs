@@ -14,10 +14,25 @@ pub enum PhMethod {
     AdjustWater,
 
     /// Compute Acid Addition.
-    /// In this case, water salts will not be computed at all.
+    /// In this case, water salts will not be computed, except for baking
+    /// soda when the grist takes the mash pH below the target.
     ComputeAcid(Acid),
 }
 
+/// How the wort is separated from the grain
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MashMethod {
+    /// Mash at the recipe's mash thickness, then batch sparge
+    #[default]
+    Infusion,
+
+    /// Mash with the full water volume and drain without sparging
+    NoSparge,
+
+    /// Brew in a bag: mash with the full water volume, then lift the bag
+    Biab,
+}
+
 /// Recipe for beer
 // Recipe
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +60,18 @@ pub struct Recipe {
     /// For single infusion, just list that one.
     pub mash_rests: Vec<MashRest>,
 
+    /// Whether to raise the mash between rests by decoction rather than by
+    /// infusing hot water. This also adds malt depth.
+    #[serde(default)]
+    pub decoction: bool,
+
+    /// How the wort is separated from the grain.
+    ///
+    /// With `NoSparge` and `Biab` the whole water volume goes into the mash
+    /// and `mash_thickness` is ignored.
+    #[serde(default)]
+    pub mash_method: MashMethod,
+
     /// Final mash thickness in liters of liquor per kilogram of grist.
     /// Typical range is 2.4 - 3.1.
     pub mash_thickness: f32,
@@ -59,21 +86,42 @@ pub struct Recipe {
     /// by weight. The actual weights are calculated.
     pub sugars: Vec<SugarProportion>,
 
+    /// The malt extracts added to the kettle, in proportion to all malts
+    /// and sugars by weight. The actual weights are calculated.
+    #[serde(default)]
+    pub extracts: Vec<ExtractProportion>,
+
     /// Original gravity target
     pub original_gravity: SpecificGravity,
 
     /// The IBU target
     pub ibu: Ibu,
 
+    /// The model used to compute IBU
+    #[serde(default)]
+    pub ibu_model: IbuModel,
+
+    /// The formula used to compute ABV
+    #[serde(default)]
+    pub abv_model: AbvModel,
+
     /// The proportional hops additions added during the boil
     pub hops: Vec<HopsProportion>,
 
+    /// Hops added to the fermenter
+    #[serde(default)]
+    pub dry_hops: Vec<DryHopAddition>,
+
     /// Boil length
     pub boil_length: Minutes,
 
     /// Whether or not to use a fining agent
     pub fining_desired: bool,
 
+    /// Which fining agent to use, if `fining_desired`
+    #[serde(default)]
+    pub fining_agent: FiningAgent,
+
     /// The yeast to ferment with
     pub yeast: Yeast,
 
@@ -121,7 +169,7 @@ impl Recipe {
     pub(crate) fn ferment_loss_fraction(&self) -> f32 {
         // Start out based on gravity. More sugar leads to
         // more yeast
-        let mut fraction: f32 = (self.original_gravity.0 - 1.0) * 2.2;
+        let mut fraction: f32 = self.original_gravity.points() * 0.0022;
 
         // Next, reduce this under low flocculation conditions
         if !self.fining_desired {
@@ -176,12 +224,75 @@ impl Recipe {
     /// Estimated FAN requirement of yeast
     #[must_use]
     pub fn fan_requirement_of_yeast(&self) -> Ppm {
-        self.yeast.fan_requirement() * ((self.original_gravity.0 - 1.0) / 0.050)
+        self.yeast.fan_requirement() * (self.original_gravity.points() / 50.0)
     }
 
     /// Diacetyl rest temperature
+    ///
+    /// Lagers are warmed up about 6°C, but not beyond the top of the
+    /// yeast's range. Ales already ferment warm enough that the rest is
+    /// just a few days on the yeast cake at the ferment temperature.
     #[must_use]
     pub fn diacetyl_rest_temperature(&self) -> Celsius {
-        Celsius(self.ferment_temperature.0 * (5.0 / 6.0) + (20.0 / 3.0))
+        if !self.yeast.is_lager() {
+            return self.ferment_temperature;
+        }
+
+        let mut temp = self.ferment_temperature + Celsius(6.0);
+        if temp > self.yeast.temp_range().end {
+            temp = self.yeast.temp_range().end;
+        }
+        if temp < self.ferment_temperature {
+            temp = self.ferment_temperature;
+        }
+        temp
+    }
+
+    /// Total dry hop rate, in grams per liter
+    #[must_use]
+    pub fn dry_hop_rate(&self) -> f32 {
+        self.dry_hops.iter().map(|a| a.grams_per_liter).sum()
+    }
+
+    /// Scale the dry hop additions to a total rate in grams per liter,
+    /// keeping their relative sizes.
+    ///
+    /// Aroma is usually thought of in grams per liter (roughly 2 g/L for
+    /// a noticeable aroma up to 10+ g/L for a hazy IPA). If there are no
+    /// dry hop additions this does nothing.
+    pub fn set_dry_hop_rate(&mut self, grams_per_liter: f32) {
+        let current = self.dry_hop_rate();
+        if current <= 0.0 {
+            return;
+        }
+        for addition in &mut self.dry_hops {
+            addition.grams_per_liter *= grams_per_liter / current;
+        }
+    }
+
+    /// This recipe with its proportions frozen as the weights for a batch
+    /// of `batch_size`: kilograms for the malts, sugars and extracts, and
+    /// grams for the hops, at the 75% efficiency that exports use.
+    ///
+    /// Proportions are relative, so a process brews the same beer from
+    /// either recipe. The gravity and IBU targets, hop timings, dry hop
+    /// rates and mash thickness are unchanged.
+    #[must_use]
+    pub fn scaled_to(&self, batch_size: Liters) -> Recipe {
+        let (malt_kg, hops_g) = self.weight_scales(batch_size);
+        let mut recipe = self.clone();
+        for mp in &mut recipe.malts {
+            mp.proportion *= malt_kg;
+        }
+        for sp in &mut recipe.sugars {
+            sp.proportion *= malt_kg;
+        }
+        for ep in &mut recipe.extracts {
+            ep.proportion *= malt_kg;
+        }
+        for hp in &mut recipe.hops {
+            hp.proportion *= hops_g;
+        }
+        recipe
     }
 }
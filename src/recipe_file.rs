@@ -0,0 +1,197 @@
+use crate::beerxml::{fuzzy_match, trim_brackets};
+use crate::prelude::*;
+use crate::recipe::Recipe;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// The version of the saved recipe format written by `Recipe::save()`
+///
+/// Bump this when a change to `Recipe` means older files need converting.
+pub const RECIPE_FORMAT_VERSION: u32 = 1;
+
+/// An error loading a saved recipe
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecipeFileError {
+    /// The document is not valid JSON
+    Json(String),
+
+    /// The document has no `serialized_version`
+    MissingVersion,
+
+    /// The document was saved by a newer version of this library
+    UnsupportedVersion(u32),
+
+    /// Ingredients or style that this version does not know about
+    UnknownIngredients(Vec<String>),
+
+    /// The recipe does not have the expected structure
+    Invalid(String),
+
+    /// The recipe could not be written as JSON
+    Serialize(String),
+}
+
+impl fmt::Display for RecipeFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "Invalid JSON: {e}"),
+            Self::MissingVersion => write!(f, "Not a saved recipe: missing serialized_version"),
+            Self::UnsupportedVersion(v) => write!(
+                f,
+                "Recipe format version {v} is newer than this library supports \
+                 (version {RECIPE_FORMAT_VERSION}). Upgrade beermaker to load it."
+            ),
+            Self::UnknownIngredients(names) => write!(
+                f,
+                "Unknown ingredients: {}. They may have been renamed or removed; \
+                 edit the recipe to use a known variant.",
+                names.join("; ")
+            ),
+            Self::Invalid(e) => write!(f, "Invalid recipe: {e}"),
+            Self::Serialize(e) => write!(f, "Could not save recipe: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RecipeFileError {}
+
+impl Recipe {
+    /// Save as JSON, wrapped with the format version so that it can be
+    /// checked by `Recipe::load()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RecipeFileError::Serialize` if the recipe cannot be
+    /// written as JSON.
+    pub fn save(&self) -> Result<String, RecipeFileError> {
+        let recipe =
+            serde_json::to_value(self).map_err(|e| RecipeFileError::Serialize(e.to_string()))?;
+        let doc = json!({
+            "serialized_version": RECIPE_FORMAT_VERSION,
+            "recipe": recipe,
+        });
+        serde_json::to_string_pretty(&doc).map_err(|e| RecipeFileError::Serialize(e.to_string()))
+    }
+
+    /// Load a recipe saved by `Recipe::save()`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, was saved by a newer
+    /// version, names ingredients we do not know about, or is otherwise
+    /// not a recipe.
+    pub fn load(json: &str) -> Result<Recipe, RecipeFileError> {
+        let doc: Value =
+            serde_json::from_str(json).map_err(|e| RecipeFileError::Json(e.to_string()))?;
+
+        let version = doc["serialized_version"]
+            .as_u64()
+            .ok_or(RecipeFileError::MissingVersion)?;
+        let version = u32::try_from(version).unwrap_or(u32::MAX);
+        if version > RECIPE_FORMAT_VERSION {
+            return Err(RecipeFileError::UnsupportedVersion(version));
+        }
+
+        let recipe = &doc["recipe"];
+
+        let mut unknown: Vec<String> = Vec::new();
+        check_variant::<Style>(&recipe["style"], "style", &mut unknown);
+        check_variant::<Yeast>(&recipe["yeast"], "yeast", &mut unknown);
+        check_list::<Malt>(recipe, "malts", "malt", &mut unknown);
+        check_list::<Sugar>(recipe, "sugars", "sugar", &mut unknown);
+        check_list::<MaltExtract>(recipe, "extracts", "extract", &mut unknown);
+        check_list::<Hops>(recipe, "hops", "hops", &mut unknown);
+        check_list::<Hops>(recipe, "dry_hops", "hops", &mut unknown);
+        if !unknown.is_empty() {
+            return Err(RecipeFileError::UnknownIngredients(unknown));
+        }
+
+        Recipe::deserialize(recipe).map_err(|e| RecipeFileError::Invalid(e.to_string()))
+    }
+}
+
+/// Check each entry of a list of ingredients
+fn check_list<T>(recipe: &Value, list: &str, field: &str, unknown: &mut Vec<String>)
+where
+    T: DeserializeOwned + IntoEnumIterator + Copy + fmt::Display + fmt::Debug,
+{
+    if let Some(entries) = recipe[list].as_array() {
+        for (i, entry) in entries.iter().enumerate() {
+            check_variant::<T>(&entry[field], &format!("{list}[{i}].{field}"), unknown);
+        }
+    }
+}
+
+/// Check that a value names a known variant, and if not, describe it
+/// along with the closest known variant.
+fn check_variant<T>(value: &Value, path: &str, unknown: &mut Vec<String>)
+where
+    T: DeserializeOwned + IntoEnumIterator + Copy + fmt::Display + fmt::Debug,
+{
+    let Some(name) = value.as_str() else {
+        return;
+    };
+    if T::deserialize(value).is_ok() {
+        return;
+    }
+    let suggestion = fuzzy_match(name, |t: T| vec![t.to_string(), format!("{t:?}")]);
+    unknown.push(match suggestion {
+        Some(t) => format!(
+            "{path} `{name}` (did you mean `{t:?}`, {}?)",
+            trim_brackets(&t.to_string())
+        ),
+        None => format!("{path} `{name}`"),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_recipe;
+
+    #[test]
+    fn test_recipe_round_trip() {
+        let recipe = test_recipe();
+        let loaded = Recipe::load(&recipe.save().unwrap()).unwrap();
+        assert_eq!(loaded.save(), recipe.save());
+    }
+
+    #[test]
+    fn test_recipe_unknown_malt() {
+        let saved = test_recipe()
+            .save()
+            .unwrap()
+            .replace("\"WeyermannMunich2\"", "\"WeyermannMunich9\"");
+        let err = Recipe::load(&saved).unwrap_err();
+        let RecipeFileError::UnknownIngredients(ref names) = err else {
+            panic!("unexpected error {err}");
+        };
+        assert_eq!(names.len(), 1);
+        assert!(names[0].starts_with("malts[0].malt `WeyermannMunich9`"));
+        assert!(err.to_string().contains("WeyermannMunich9"));
+
+        let newer = test_recipe().save().unwrap().replace(
+            &format!("\"serialized_version\": {RECIPE_FORMAT_VERSION}"),
+            "\"serialized_version\": 99",
+        );
+        assert_eq!(
+            Recipe::load(&newer).unwrap_err(),
+            RecipeFileError::UnsupportedVersion(99)
+        );
+    }
+
+    #[test]
+    fn test_recipe_without_new_fields() {
+        // A recipe saved before decoction and the mash method were added
+        let mut doc: Value = serde_json::from_str(&test_recipe().save().unwrap()).unwrap();
+        let recipe = doc["recipe"].as_object_mut().unwrap();
+        let _ = recipe.remove("decoction");
+        let _ = recipe.remove("mash_method");
+        let loaded = Recipe::load(&doc.to_string()).unwrap();
+        assert!(!loaded.decoction);
+        assert_eq!(loaded.mash_method, MashMethod::Infusion);
+    }
+}
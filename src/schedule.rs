@@ -0,0 +1,186 @@
+use crate::prelude::*;
+use crate::{Conditioning, Process, StyleOrigin};
+use serde::{Deserialize, Serialize};
+
+/// A step in a fermentation schedule: from `day` (counted from pitching),
+/// hold the beer at `temperature`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FermentStep {
+    /// Day the step starts, counting from when the yeast is pitched
+    pub day: Days,
+
+    /// Temperature to set the controller to
+    pub temperature: Celsius,
+
+    /// What is happening
+    pub note: String,
+}
+
+impl FermentStep {
+    fn new(day: Days, temperature: Celsius, note: &str) -> FermentStep {
+        FermentStep {
+            day,
+            temperature,
+            note: note.to_owned(),
+        }
+    }
+}
+
+impl Process {
+    /// The fermentation schedule, for programming a temperature
+    /// controller.
+    ///
+    /// This covers pitching, primary fermentation, the diacetyl rest, and
+    /// then either a cold crash (ales) or lagering. American lagers are
+    /// crashed straight down; other lagers are lowered 1°C per day. The
+    /// last step is when the beer is ready to package. Dry hop additions
+    /// and removals are steps at the temperature in effect on their day.
+    #[must_use]
+    pub fn fermentation_schedule(&self) -> Vec<FermentStep> {
+        let ferment_temp = self.recipe.ferment_temperature;
+        let rest_temp = self.recipe.diacetyl_rest_temperature();
+        let mut steps = vec![FermentStep::new(
+            Days(0),
+            ferment_temp,
+            "Pitch the yeast and hold for primary fermentation",
+        )];
+
+        let mut day = self.recipe.fermentation_time();
+        if rest_temp > ferment_temp {
+            steps.push(FermentStep::new(day, rest_temp, "Diacetyl rest"));
+            day = day + Days(2);
+        } else {
+            steps.push(FermentStep::new(
+                day,
+                ferment_temp,
+                "Rest on the yeast cake to clean up diacetyl",
+            ));
+            day = day + Days(3);
+        }
+
+        if self.recipe.style.conditioning() == Conditioning::Lagered {
+            let lagering_time = self.recipe.style.recommended_conditioning_time();
+            let lagering_temp = if self.recipe.style.origin() == StyleOrigin::American {
+                Celsius(1.0)
+            } else {
+                // Lower 1°C per day
+                let mut temp = rest_temp;
+                while temp > Celsius(5.0) {
+                    temp = temp - Celsius(1.0);
+                    steps.push(FermentStep::new(day, temp, "Lower the temperature"));
+                    day = day + Days(1);
+                }
+                Celsius(4.0)
+            };
+            steps.push(FermentStep::new(day, lagering_temp, "Lager"));
+            day = day + lagering_time;
+        } else {
+            steps.push(FermentStep::new(
+                day,
+                Celsius(2.0),
+                "Cold crash to drop the yeast",
+            ));
+            day = day + Days(2);
+        }
+
+        steps.push(FermentStep::new(
+            day,
+            self.brewery.room_temperature,
+            "Ready to package",
+        ));
+
+        for addition in &self.recipe.dry_hops {
+            let add = FermentStep {
+                day: addition.day,
+                temperature: temperature_on(&steps, addition.day),
+                note: format!("Dry hop with {}", addition.hops),
+            };
+            let remove_day = addition.day + addition.duration;
+            let remove = FermentStep {
+                day: remove_day,
+                temperature: temperature_on(&steps, remove_day),
+                note: format!("Remove the {} dry hops", addition.hops),
+            };
+            steps.push(add);
+            steps.push(remove);
+        }
+        steps.sort_by_key(|step| step.day);
+
+        steps
+    }
+}
+
+/// The temperature the schedule holds on `day`
+fn temperature_on(steps: &[FermentStep], day: Days) -> Celsius {
+    steps
+        .iter()
+        .take_while(|step| step.day <= day)
+        .last()
+        .map_or(steps[0].temperature, |step| step.temperature)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Style;
+    use crate::process::test::test_process;
+
+    #[test]
+    fn test_fermentation_schedule() {
+        let process = test_process();
+        let schedule = process.fermentation_schedule();
+        assert_eq!(schedule[0].day, Days(0));
+        assert!(schedule.windows(2).all(|w| w[0].day <= w[1].day));
+
+        // Ends with weeks of cold lagering
+        let lager = &schedule[schedule.len() - 2];
+        let ready = &schedule[schedule.len() - 1];
+        assert!(lager.temperature <= Celsius(7.0));
+        assert!(ready.day - lager.day >= Days(21));
+
+        // Lowered by no more than 1°C per day
+        for w in schedule.windows(2) {
+            if w[1].note == "Lower the temperature" {
+                assert!(w[0].temperature - w[1].temperature <= Celsius(1.0));
+            }
+        }
+
+        let mut ale = test_process();
+        ale.recipe.style = Style::AmericanAmberAle;
+        ale.recipe.yeast = Yeast::SafAleUS05;
+        ale.recipe.ferment_temperature = Celsius(19.0);
+        let schedule = ale.fermentation_schedule();
+        let cold_days: usize = schedule
+            .windows(2)
+            .filter(|w| w[0].temperature <= Celsius(7.0))
+            .map(|w| (w[1].day - w[0].day).0)
+            .sum();
+        assert!(cold_days < 7);
+    }
+
+    #[test]
+    fn test_fermentation_schedule_dry_hops() {
+        let mut process = test_process();
+        process.recipe.dry_hops = vec![DryHopAddition {
+            hops: Hops::Citra,
+            grams_per_liter: 2.0,
+            day: Days(5),
+            duration: Days(3),
+        }];
+        let schedule = process.fermentation_schedule();
+        assert!(schedule.windows(2).all(|w| w[0].day <= w[1].day));
+
+        let add = schedule
+            .iter()
+            .find(|s| s.note.starts_with("Dry hop"))
+            .unwrap();
+        assert_eq!(add.day, Days(5));
+        assert_eq!(add.temperature, process.recipe.ferment_temperature);
+
+        let remove = schedule
+            .iter()
+            .find(|s| s.note.starts_with("Remove"))
+            .unwrap();
+        assert_eq!(remove.day, Days(8));
+    }
+}
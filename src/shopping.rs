@@ -0,0 +1,207 @@
+use crate::Process;
+use crate::beerxml::trim_brackets;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// An item on a shopping list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShoppingItem {
+    /// What kind of ingredient, e.g. "malt" or "hops"
+    pub category: String,
+
+    /// The ingredient
+    pub item: String,
+
+    /// How much to buy
+    pub amount: f32,
+
+    /// The unit of the amount
+    pub unit: String,
+}
+
+impl ShoppingItem {
+    fn new(category: &str, item: String, amount: f32, unit: &str) -> ShoppingItem {
+        ShoppingItem {
+            category: category.to_owned(),
+            item,
+            amount,
+            unit: unit.to_owned(),
+        }
+    }
+}
+
+impl Process {
+    /// The ingredients to buy, as structured data (e.g. for serializing
+    /// as JSON).
+    ///
+    /// Grain, sugar and extract are in kilograms, hops, salts, acids and
+    /// yeast nutrient in grams, yeast in packages, and the fining agent
+    /// in its own unit.
+    #[must_use]
+    pub fn shopping_list_json(&self) -> Vec<ShoppingItem> {
+        let mut items: Vec<ShoppingItem> = Vec::new();
+
+        for dose in &self.malt_doses() {
+            let item = trim_brackets(&dose.malt.to_string()).to_owned();
+            items.push(ShoppingItem::new("malt", item, dose.weight.0, "kg"));
+        }
+
+        for dose in &self.sugar_doses() {
+            let item = trim_brackets(&dose.sugar.to_string()).to_owned();
+            items.push(ShoppingItem::new("sugar", item, dose.weight.0, "kg"));
+        }
+
+        for dose in &self.extract_doses() {
+            let item = trim_brackets(&dose.extract.to_string()).to_owned();
+            items.push(ShoppingItem::new("extract", item, dose.weight.0, "kg"));
+        }
+
+        for dose in &self.hops_doses() {
+            let hops = trim_brackets(&dose.hops.to_string()).to_owned();
+            let item = if dose.first_wort {
+                format!("{hops} (first wort)")
+            } else if let Some((temperature, minutes)) = dose.whirlpool {
+                format!(
+                    "{hops} ({}, whirlpool {minutes} at {temperature})",
+                    dose.timing
+                )
+            } else {
+                format!("{hops} ({})", dose.timing)
+            };
+            items.push(ShoppingItem::new("hops", item, dose.weight.0, "g"));
+        }
+
+        for addition in &self.recipe.dry_hops {
+            let hops = trim_brackets(&addition.hops.to_string()).to_owned();
+            let item = format!("{hops} (dry hop)");
+            let weight = self.dry_hop_weight(addition);
+            items.push(ShoppingItem::new("hops", item, weight.0, "g"));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        items.push(ShoppingItem::new(
+            "yeast",
+            trim_brackets(&self.recipe.yeast.to_string()).to_owned(),
+            self.yeast_packages() as f32,
+            "packages",
+        ));
+
+        let nutrient = self.yeast_nutrient_amount();
+        if nutrient > Grams(0.0) {
+            items.push(ShoppingItem::new(
+                "nutrient",
+                trim_brackets(&self.brewery.yeast_nutrient.to_string()).to_owned(),
+                nutrient.0,
+                "g",
+            ));
+        }
+
+        if self.recipe.fining_desired {
+            let agent = self.recipe.fining_agent;
+            items.push(ShoppingItem::new(
+                "fining",
+                trim_brackets(&agent.to_string()).to_owned(),
+                self.fining_amount(),
+                agent.unit(),
+            ));
+        }
+
+        for dose in &self.salt_doses(None) {
+            let grams: Grams = dose.mg.into();
+            let item = trim_brackets(&dose.salt.to_string()).to_owned();
+            items.push(ShoppingItem::new("salt", item, grams.0, "g"));
+        }
+
+        let mut acid_doses: Vec<AcidDose> = Vec::new();
+        for dose in self
+            .mash_acid_doses()
+            .into_iter()
+            .chain(self.sparge_acid_doses())
+        {
+            match acid_doses.iter_mut().find(|d| d.acid == dose.acid) {
+                Some(existing) => existing.mg = existing.mg + dose.mg,
+                None => acid_doses.push(dose),
+            }
+        }
+        for dose in &acid_doses {
+            let grams: Grams = dose.mg.into();
+            let item = trim_brackets(&dose.acid.to_string()).to_owned();
+            items.push(ShoppingItem::new("acid", item, grams.0, "g"));
+        }
+
+        items
+    }
+
+    /// The ingredients to buy, as CSV with a header row of
+    /// `category,item,amount,unit`.
+    ///
+    /// See `shopping_list_json()` for the units used.
+    #[must_use]
+    pub fn shopping_list_csv(&self) -> String {
+        let mut csv = String::from("category,item,amount,unit\n");
+        for item in &self.shopping_list_json() {
+            let amount = if item.unit == "packages" {
+                format!("{:.0}", item.amount)
+            } else if item.unit == "kg" {
+                format!("{:.3}", item.amount)
+            } else {
+                format!("{:.1}", item.amount)
+            };
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(&item.category),
+                csv_field(&item.item),
+                amount,
+                csv_field(&item.unit)
+            );
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it needs it
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_process;
+
+    #[test]
+    fn test_shopping_list() {
+        let process = test_process();
+        let csv = process.shopping_list_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "category,item,amount,unit");
+
+        for dose in &process.malt_doses() {
+            let malt = trim_brackets(&dose.malt.to_string()).to_owned();
+            assert!(
+                rows.iter()
+                    .any(|row| row.starts_with(&format!("malt,{malt},")) && row.ends_with(",kg")),
+                "{malt}"
+            );
+        }
+        assert!(
+            rows.iter()
+                .any(|row| row.starts_with("hops,Hallertau Mittelfruh (60 min),"))
+        );
+        assert!(rows.iter().any(|row| row.starts_with("yeast,")));
+
+        let items = process.shopping_list_json();
+        assert_eq!(items.len(), rows.len() - 1);
+        let json = serde_json::to_string(&items).unwrap();
+        let back: Vec<ShoppingItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, items);
+
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+    }
+}
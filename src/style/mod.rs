@@ -1,7 +1,10 @@
+use crate::ingredients::Yeast;
+use crate::mash::MashRest;
 use crate::units::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::Range;
+use strum::{EnumIter, IntoEnumIterator};
 
 mod conditioning;
 pub use conditioning::Conditioning;
@@ -22,7 +25,7 @@ pub use origin::StyleOrigin;
 ///
 /// Brewer's Association
 /// <https://www.brewersassociation.org/edu/brewers-association-beer-style-guidelines/>
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, EnumIter)]
 pub enum Style {
     /// 1A. American Light Lager
     AmericanLightLager,
@@ -328,6 +331,7 @@ impl Style {
 
     /// BJCP style guideline overall impression
     #[must_use]
+    #[allow(clippy::too_many_lines)]
     pub fn overall_impression_bjcp(&self) -> &str {
         match *self {
             Self::AmericanLightLager => {
@@ -490,9 +494,9 @@ impl Style {
         }
     }
 
-    /// Range of original gravity for the style
+    /// Range of original gravity for the style, or `None` if none is published
     #[must_use]
-    pub fn original_gravity_range(&self) -> Range<SpecificGravity> {
+    pub fn original_gravity_range(&self) -> Option<Range<SpecificGravity>> {
         crate::union_ranges(self.original_gravity_ranges())
     }
 
@@ -544,12 +548,23 @@ impl Style {
         }
     }
 
-    /// Range of final gravity for the style
+    /// Range of final gravity for the style, or `None` if none is published
     #[must_use]
-    pub fn final_gravity_range(&self) -> Range<SpecificGravity> {
+    pub fn final_gravity_range(&self) -> Option<Range<SpecificGravity>> {
         crate::union_ranges(self.final_gravity_ranges())
     }
 
+    /// Range of apparent attenuation implied by the gravity ranges, or
+    /// `None` if they are not published
+    #[must_use]
+    pub fn attenuation_range(&self) -> Option<Range<f32>> {
+        let og = self.original_gravity_range()?;
+        let fg = self.final_gravity_range()?;
+        let least = 1.0 - (fg.end.0 - 1.0) / (og.start.0 - 1.0);
+        let most = 1.0 - (fg.start.0 - 1.0) / (og.end.0 - 1.0);
+        Some(least..most)
+    }
+
     /// Ranges of ABV for the style, BJCP then BA
     #[must_use]
     pub fn abv_ranges(&self) -> Vec<Range<Abv>> {
@@ -581,9 +596,9 @@ impl Style {
             .collect()
     }
 
-    /// Range of ABV for the style
+    /// Range of ABV for the style, or `None` if none is published
     #[must_use]
-    pub fn abv_range(&self) -> Range<Abv> {
+    pub fn abv_range(&self) -> Option<Range<Abv>> {
         crate::union_ranges(&self.abv_ranges())
     }
 
@@ -613,9 +628,9 @@ impl Style {
         }
     }
 
-    /// Range of IBU for the style
+    /// Range of IBU for the style, or `None` if none is published
     #[must_use]
-    pub fn bitterness_range(&self) -> Range<Ibu> {
+    pub fn bitterness_range(&self) -> Option<Range<Ibu>> {
         crate::union_ranges(self.bitterness_ranges())
     }
 
@@ -645,15 +660,15 @@ impl Style {
         }
     }
 
-    /// Range of SRM for the style
+    /// Range of SRM for the style, or `None` if none is published
     #[must_use]
-    pub fn color_range(&self) -> Range<Srm> {
+    pub fn color_range(&self) -> Option<Range<Srm>> {
         crate::union_ranges(self.color_ranges())
     }
 
     /// Carbonation volume
     #[must_use]
-    pub fn carbonation_volume(&self) -> f32 {
+    pub fn carbonation_volume(&self) -> CarbonationVolume {
         // 1.5 - 2.0:  British cask ale, barleywine
         // 2.0 - 2.5:  American and British ales
         // 2.5 - 3.0:  Lagers, wheat beers
@@ -671,25 +686,25 @@ impl Style {
         // belgian ale 3.0  1.9-2.4
 
         match *self {
-            Self::AmericanLightLager => 4.0,
-            Self::AmericanLager => 4.0,
-            Self::CreamAle => 3.2,
-            Self::AmericanWheatBeer => 2.8,
-            Self::CzechPremiumPaleLager => 2.4,
-            Self::MunichHelles => 2.6,
-            Self::OrdinaryBitter => 1.8,
-            Self::BestBitter => 1.8,
-            Self::StrongBitter => 1.8,
-            Self::BritishGoldenAle => 1.8,
-            Self::DarkMild => 1.8,
-            Self::BritishBrownAle => 2.1,
-            Self::DunklesWeissbier => 3.5,
-            Self::Marzen => 2.7,
-            Self::Weissbier => 3.3,
-            Self::LeichtesWeizen => 4.0,
-            Self::IrishRedAle => 1.9,
-            Self::AmericanAmberAle => 2.5,
-            Self::BelgianDarkStrongAle => 3.0,
+            Self::AmericanLightLager => CarbonationVolume(4.0),
+            Self::AmericanLager => CarbonationVolume(4.0),
+            Self::CreamAle => CarbonationVolume(3.2),
+            Self::AmericanWheatBeer => CarbonationVolume(2.8),
+            Self::CzechPremiumPaleLager => CarbonationVolume(2.4),
+            Self::MunichHelles => CarbonationVolume(2.6),
+            Self::OrdinaryBitter => CarbonationVolume(1.8),
+            Self::BestBitter => CarbonationVolume(1.8),
+            Self::StrongBitter => CarbonationVolume(1.8),
+            Self::BritishGoldenAle => CarbonationVolume(1.8),
+            Self::DarkMild => CarbonationVolume(1.8),
+            Self::BritishBrownAle => CarbonationVolume(2.1),
+            Self::DunklesWeissbier => CarbonationVolume(3.5),
+            Self::Marzen => CarbonationVolume(2.7),
+            Self::Weissbier => CarbonationVolume(3.3),
+            Self::LeichtesWeizen => CarbonationVolume(4.0),
+            Self::IrishRedAle => CarbonationVolume(1.9),
+            Self::AmericanAmberAle => CarbonationVolume(2.5),
+            Self::BelgianDarkStrongAle => CarbonationVolume(3.0),
         }
     }
 
@@ -730,6 +745,110 @@ impl Style {
         }
     }
 
+    /// A suggested mash schedule, as a starting point for a recipe
+    ///
+    /// German wheat beers get a ferulic acid rest, which gives the yeast
+    /// more to make clove phenols from. German lagers get a step mash
+    /// with a beta amylase rest and then an alpha amylase rest. Other
+    /// styles get a single rest: lower for dry styles, higher for those
+    /// that want body.
+    #[must_use]
+    pub fn suggested_mash_rests(&self) -> Vec<MashRest> {
+        let rest = |temperature: f32, minutes: usize| MashRest {
+            target_temperature: Celsius(temperature),
+            duration: Minutes(minutes),
+        };
+
+        match *self {
+            Self::Weissbier | Self::DunklesWeissbier | Self::LeichtesWeizen => {
+                vec![rest(44.0, 15), rest(63.0, 35), rest(72.0, 20)]
+            }
+            Self::CzechPremiumPaleLager | Self::MunichHelles | Self::Marzen => {
+                vec![rest(63.0, 40), rest(72.0, 20)]
+            }
+            Self::AmericanLightLager
+            | Self::AmericanLager
+            | Self::CreamAle
+            | Self::BritishGoldenAle
+            | Self::BelgianDarkStrongAle => vec![rest(63.0, 75)],
+            Self::DarkMild | Self::BritishBrownAle => vec![rest(68.0, 60)],
+            Self::AmericanWheatBeer
+            | Self::OrdinaryBitter
+            | Self::BestBitter
+            | Self::StrongBitter
+            | Self::IrishRedAle
+            | Self::AmericanAmberAle => vec![rest(66.0, 60)],
+        }
+    }
+
+    /// BJCP 2021 category number and style letter, if this is a BJCP style
+    #[must_use]
+    pub fn bjcp_code(&self) -> Option<(u32, &'static str)> {
+        match *self {
+            Self::AmericanLightLager => Some((1, "A")),
+            Self::AmericanLager => Some((1, "B")),
+            Self::CreamAle => Some((1, "C")),
+            Self::AmericanWheatBeer => Some((1, "D")),
+            Self::CzechPremiumPaleLager => Some((3, "B")),
+            Self::MunichHelles => Some((4, "A")),
+            Self::Marzen => Some((6, "A")),
+            Self::Weissbier => Some((10, "A")),
+            Self::DunklesWeissbier => Some((10, "B")),
+            Self::OrdinaryBitter => Some((11, "A")),
+            Self::BestBitter => Some((11, "B")),
+            Self::StrongBitter => Some((11, "C")),
+            Self::BritishGoldenAle => Some((12, "A")),
+            Self::DarkMild => Some((13, "A")),
+            Self::BritishBrownAle => Some((13, "B")),
+            Self::IrishRedAle => Some((15, "A")),
+            Self::AmericanAmberAle => Some((19, "A")),
+            Self::BelgianDarkStrongAle => Some((26, "D")),
+            Self::LeichtesWeizen => None,
+        }
+    }
+
+    /// BJCP 2021 category name, or None if the style is not in the BJCP
+    /// guidelines
+    #[must_use]
+    pub fn bjcp_category(&self) -> Option<&'static str> {
+        match self.bjcp_code()? {
+            (1, _) => Some("Standard American Beer"),
+            (3, _) => Some("Czech Lager"),
+            (4, _) => Some("Pale Malty European Lager"),
+            (6, _) => Some("Amber Malty European Lager"),
+            (10, _) => Some("German Wheat Beer"),
+            (11, _) => Some("British Bitter"),
+            (12, _) => Some("Pale Commonwealth Beer"),
+            (13, _) => Some("Brown British Beer"),
+            (15, _) => Some("Irish Beer"),
+            (19, _) => Some("Amber and Brown American Beer"),
+            (26, _) => Some("Monastic Ale"),
+            _ => None,
+        }
+    }
+
+    /// Is a lager style (bottom fermented)
+    #[must_use]
+    pub fn lager_style(&self) -> bool {
+        self.fermentation() == Fermentation::Lager
+    }
+
+    /// Typical yeast pitching rate for the style (cells per mL per Plato)
+    #[must_use]
+    pub fn yeast_pitching_rate(&self) -> u64 {
+        if self.lager_style() {
+            1_500_000
+        } else {
+            750_000
+        }
+    }
+
+    /// Yeasts that suit the style
+    #[must_use]
+    pub fn suggested_yeasts(&self) -> Vec<Yeast> {
+        Yeast::iter().filter(|y| y.suits_style(*self)).collect()
+    }
+
     /// Recommended conditioning time
     #[must_use]
     pub fn recommended_conditioning_time(&self) -> Days {
@@ -740,4 +859,123 @@ impl Style {
             Days(14) // 2 weeks
         }
     }
+
+    /// Whether measured numbers fit this style.
+    ///
+    /// Each must land within the (inclusive) published range. Metrics for
+    /// which no range is published are not checked.
+    #[must_use]
+    pub fn matches(
+        &self,
+        og: SpecificGravity,
+        fg: SpecificGravity,
+        abv: Abv,
+        ibu: Ibu,
+        srm: Srm,
+    ) -> bool {
+        in_range(og, self.original_gravity_range())
+            && in_range(fg, self.final_gravity_range())
+            && in_range(abv, self.abv_range())
+            && in_range(ibu, self.bitterness_range())
+            && in_range(srm, self.color_range())
+    }
+}
+
+fn in_range<T: PartialOrd + Copy>(value: T, range: Option<Range<T>>) -> bool {
+    match range {
+        Some(range) => range.start <= value && value <= range.end,
+        None => true,
+    }
+}
+
+/// All of the styles that measured numbers fit
+#[must_use]
+pub fn classify(
+    og: SpecificGravity,
+    fg: SpecificGravity,
+    abv: Abv,
+    ibu: Ibu,
+    srm: Srm,
+) -> Vec<Style> {
+    Style::iter()
+        .filter(|style| style.matches(og, fg, abv, ibu, srm))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_style_methods_are_total() {
+        for style in Style::iter() {
+            let _ = style.origin();
+            let _ = style.conditioning();
+            let _ = style.overall_impression_bjcp();
+            assert!(!style.original_gravity_ranges().is_empty());
+            assert!(!style.final_gravity_ranges().is_empty());
+            assert!(!style.abv_ranges().is_empty());
+            assert!(!style.bitterness_ranges().is_empty());
+            assert!(!style.color_ranges().is_empty());
+            assert!(style.carbonation_volume() > CarbonationVolume(0.0));
+            assert!(style.yeast_pitching_rate() > 0);
+            assert_eq!(style.bjcp_category().is_some(), style.bjcp_code().is_some());
+            assert_eq!(
+                style.lager_style(),
+                style.fermentation() == Fermentation::Lager
+            );
+        }
+        assert!(Style::Marzen.lager_style());
+        assert!(!Style::Weissbier.lager_style());
+        assert_eq!(Style::Weissbier.bjcp_category(), Some("German Wheat Beer"));
+        assert_eq!(Style::LeichtesWeizen.bjcp_category(), None);
+    }
+
+    #[test]
+    fn test_suggested_mash_rests() {
+        for style in Style::iter() {
+            assert!(!style.suggested_mash_rests().is_empty());
+        }
+
+        let weissbier = Style::Weissbier.suggested_mash_rests();
+        assert!(weissbier[0].target_temperature < Celsius(46.0));
+        assert!(weissbier.len() > 1);
+
+        let irish_red = Style::IrishRedAle.suggested_mash_rests();
+        assert_eq!(irish_red.len(), 1);
+        assert!(irish_red[0].target_temperature > Celsius(64.0));
+        assert!(irish_red[0].target_temperature < Celsius(68.0));
+    }
+
+    #[test]
+    fn test_suggested_yeasts() {
+        let weissbier = Style::Weissbier.suggested_yeasts();
+        assert!(weissbier.contains(&Yeast::WLP300));
+        assert!(weissbier.contains(&Yeast::SafAleWB06));
+        assert!(!weissbier.contains(&Yeast::WLP001));
+        assert!(!weissbier.contains(&Yeast::SafAleUS05));
+
+        let marzen = Style::Marzen.suggested_yeasts();
+        assert!(marzen.contains(&Yeast::WLP820));
+        assert!(marzen.contains(&Yeast::SafLagerW3470));
+        assert!(!marzen.contains(&Yeast::WLP300));
+
+        assert!(Yeast::WLP001.suits_style(Style::AmericanAmberAle));
+        assert!(!Yeast::WLP300.suits_style(Style::AmericanAmberAle));
+    }
+
+    #[test]
+    fn test_classify() {
+        let og = SpecificGravity(1.057);
+        let fg = SpecificGravity(1.013);
+        let abv: Abv = Percent(5.8).into();
+        let ibu = Ibu(21.0);
+        let srm = Srm(11.0);
+
+        assert!(Style::Marzen.matches(og, fg, abv, ibu, srm));
+
+        let styles = classify(og, fg, abv, ibu, srm);
+        assert!(styles.contains(&Style::Marzen));
+        assert!(!styles.contains(&Style::AmericanLightLager));
+    }
 }
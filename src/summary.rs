@@ -0,0 +1,112 @@
+use crate::Process;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The key computed numbers of a process, as structured data (e.g. for
+/// serializing as JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessSummary {
+    /// Original gravity
+    pub original_gravity: SpecificGravity,
+
+    /// Final gravity
+    pub final_gravity: SpecificGravity,
+
+    /// Alcohol by volume
+    pub abv: Abv,
+
+    /// Bitterness
+    pub ibu: Ibu,
+
+    /// Color
+    pub color: Srm,
+
+    /// All of the water used
+    pub total_water: Liters,
+
+    /// Strike water volume
+    pub strike_volume: Liters,
+
+    /// Strike water temperature, if there is a mash
+    pub strike_temperature: Option<Celsius>,
+
+    /// Sparge water volume
+    pub sparge_volume: Liters,
+
+    /// Weight of the grains in the mash
+    pub grain_weight: Kilograms,
+
+    /// Yeast to pitch, in grams, if it is dry yeast
+    pub yeast_grams: Option<Grams>,
+
+    /// Yeast cells to pitch
+    pub yeast_cells: u64,
+
+    /// Estimated length of the fermentation
+    pub fermentation_time: Days,
+
+    /// Warnings (and errors), as text
+    pub warnings: Vec<String>,
+}
+
+impl Process {
+    /// A summary of the computed numbers
+    #[must_use]
+    pub fn summary(&self) -> ProcessSummary {
+        ProcessSummary {
+            original_gravity: self.recipe.original_gravity,
+            final_gravity: self.final_gravity(),
+            abv: self.abv(),
+            ibu: self.bitterness(),
+            color: self.color(),
+            total_water: self.total_water(),
+            strike_volume: self.strike_volume(),
+            strike_temperature: self.strike_temperature(),
+            sparge_volume: self.sparge_volume(),
+            grain_weight: self.grain_weight(),
+            yeast_grams: self.yeast_grams(None),
+            yeast_cells: self.yeast_cells(None),
+            fermentation_time: self.recipe.fermentation_time(),
+            warnings: self
+                .get_warnings()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_process;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_summary() {
+        let process = test_process();
+        let summary = process.summary();
+
+        let json = serde_json::to_string(&summary).unwrap();
+        let parsed: ProcessSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, summary);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let abv = value["abv"].as_f64().unwrap();
+        assert!(approx_eq!(
+            f64,
+            abv,
+            f64::from(process.abv().0),
+            epsilon = 0.0001
+        ));
+        let ibu = value["ibu"].as_f64().unwrap();
+        assert!(approx_eq!(
+            f64,
+            ibu,
+            f64::from(process.bitterness().0),
+            epsilon = 0.001
+        ));
+        assert_eq!(summary.strike_volume, process.strike_volume());
+        assert_eq!(summary.warnings.len(), process.get_warnings().len());
+    }
+}
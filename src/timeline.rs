@@ -0,0 +1,201 @@
+use crate::prelude::*;
+use crate::{MashMethod, Process};
+use serde::{Deserialize, Serialize};
+
+/// Time to heat the strike water
+const STRIKE_HEATING: Minutes = Minutes(30);
+
+/// Time to raise the mash to the next rest by infusion
+const INFUSION_STEP: Minutes = Minutes(5);
+
+/// Time to pull, boil and return a decoction
+const DECOCTION_STEP: Minutes = Minutes(30);
+
+/// Time to raise the mash to mash-out temperature and hold it
+const MASH_OUT: Minutes = Minutes(10);
+
+/// Time to lauter, including the sparge
+const LAUTER: Minutes = Minutes(20);
+
+/// Time to bring the wort from lautering to a boil
+const HEAT_TO_BOIL: Minutes = Minutes(30);
+
+/// Time to chill in an ice bath
+const ICE_BATH_CHILL: Minutes = Minutes(40);
+
+/// Time to chill with a chiller
+const CHILLER_CHILL: Minutes = Minutes(20);
+
+/// An event on brew day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    /// When the event happens, counted from the start of the day (e.g.
+    /// midnight) if that is what the timeline was started from
+    pub offset: Minutes,
+
+    /// What to do
+    pub action: String,
+}
+
+impl TimelineEvent {
+    fn new(offset: Minutes, action: String) -> TimelineEvent {
+        TimelineEvent { offset, action }
+    }
+
+    /// The offset as a time of day, `HH:MM`, if the timeline was started
+    /// from a number of minutes after midnight
+    #[must_use]
+    pub fn time_of_day(&self) -> String {
+        let minutes = self.offset.0 % (24 * 60);
+        format!("{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl Process {
+    /// The brew day timeline, from heating the strike water (or filling
+    /// the kettle, if there is no mash) to pitching the yeast, with each
+    /// event at an offset from `start`.
+    ///
+    /// Heating, lautering and chilling times are typical figures for a
+    /// homebrew setup; the mash rests, boil and hop stands come from the
+    /// recipe.
+    #[must_use]
+    pub fn brew_day_timeline(&self, start: Minutes) -> Vec<TimelineEvent> {
+        let mut events: Vec<TimelineEvent> = Vec::new();
+        let mut t = start;
+
+        if let Some(strike_temperature) = self.strike_temperature() {
+            events.push(TimelineEvent::new(
+                t,
+                format!("Heat the strike water to {strike_temperature}"),
+            ));
+            t = t + STRIKE_HEATING;
+
+            events.push(TimelineEvent::new(t, "Mash in".to_string()));
+            for (i, rest) in self.recipe.mash_rests.iter().enumerate() {
+                if i > 0 {
+                    t = t + if self.recipe.decoction {
+                        DECOCTION_STEP
+                    } else {
+                        INFUSION_STEP
+                    };
+                }
+                events.push(TimelineEvent::new(
+                    t,
+                    format!("Rest at {} for {}", rest.target_temperature, rest.duration),
+                ));
+                t = t + rest.duration;
+            }
+
+            events.push(TimelineEvent::new(t, "Mash out".to_string()));
+            t = t + MASH_OUT;
+
+            events.push(TimelineEvent::new(
+                t,
+                match self.recipe.mash_method {
+                    MashMethod::Infusion => "Lauter and sparge",
+                    MashMethod::NoSparge => "Lauter",
+                    MashMethod::Biab => "Lift the bag and drain it",
+                }
+                .to_string(),
+            ));
+            for dose in self.hops_doses().iter().filter(|d| d.first_wort) {
+                events.push(TimelineEvent::new(
+                    t,
+                    format!("Add {} of {} (first-wort hops)", dose.weight, dose.hops),
+                ));
+            }
+            t = t + LAUTER;
+        } else {
+            events.push(TimelineEvent::new(t, "Fill the kettle".to_string()));
+            for dose in self.hops_doses().iter().filter(|d| d.first_wort) {
+                events.push(TimelineEvent::new(
+                    t,
+                    format!("Add {} of {} (first-wort hops)", dose.weight, dose.hops),
+                ));
+            }
+        }
+
+        events.push(TimelineEvent::new(t, "Heat to a boil".to_string()));
+        t = t + HEAT_TO_BOIL;
+
+        let boil_start = t;
+        events.push(TimelineEvent::new(t, "Start the boil".to_string()));
+        t = t + self.recipe.boil_length;
+        events.push(TimelineEvent::new(t, "Turn off the burner".to_string()));
+
+        for dose in self.hops_doses().iter().filter(|d| !d.first_wort) {
+            let timing = dose.timing.min(self.recipe.boil_length);
+            events.push(TimelineEvent::new(
+                boil_start + self.recipe.boil_length - timing,
+                format!("Add {} of {}", dose.weight, dose.hops),
+            ));
+        }
+
+        let hop_stand = self
+            .hops_doses()
+            .iter()
+            .filter_map(|d| d.whirlpool.map(|(_, minutes)| minutes))
+            .max();
+        if let Some(minutes) = hop_stand {
+            events.push(TimelineEvent::new(t, format!("Hop stand for {minutes}")));
+            t = t + minutes;
+        }
+
+        events.push(TimelineEvent::new(t, "Chill the wort".to_string()));
+        t = t + if self.brewery.ice_bath {
+            ICE_BATH_CHILL
+        } else {
+            CHILLER_CHILL
+        };
+
+        events.push(TimelineEvent::new(
+            t,
+            format!("Pitch the {}", self.recipe.yeast),
+        ));
+
+        // Stable, so events at the same time stay in the order above
+        events.sort_by_key(|e| e.offset);
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::process::test::test_process;
+
+    #[test]
+    fn test_brew_day_timeline() {
+        let mut process = test_process();
+        process.recipe.boil_length = Minutes(60);
+        let start = Minutes(8 * 60);
+        let timeline = process.brew_day_timeline(start);
+
+        let rests: Minutes = process.recipe.mash_rests.iter().map(|r| r.duration).sum();
+        let steps = INFUSION_STEP * (process.recipe.mash_rests.len() - 1);
+        let expected = STRIKE_HEATING
+            + rests
+            + steps
+            + MASH_OUT
+            + LAUTER
+            + HEAT_TO_BOIL
+            + Minutes(60)
+            + CHILLER_CHILL;
+        let end = timeline.last().unwrap();
+        assert!(end.action.starts_with("Pitch"));
+        assert_eq!(end.offset, start + expected);
+        assert_eq!(timeline[0].time_of_day(), "08:00");
+
+        // The 60 minute hop addition goes in as the boil starts
+        let boil_start = timeline
+            .iter()
+            .find(|e| e.action == "Start the boil")
+            .unwrap();
+        let hops = timeline
+            .iter()
+            .find(|e| e.action.starts_with("Add "))
+            .unwrap();
+        assert_eq!(hops.offset, boil_start.offset);
+    }
+}
@@ -0,0 +1,56 @@
+use derive_more::{Add, Sub};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Carbonation in volumes of CO2: the volume the dissolved CO2 would
+/// occupy at standard temperature and pressure, per volume of beer
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct CarbonationVolume(pub f32);
+
+impl fmt::Display for CarbonationVolume {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} vols CO2", self.0)
+    }
+}
+
+/// Dissolved CO2 in grams per liter
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct GramsPerLiter(pub f32);
+
+impl fmt::Display for GramsPerLiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} g/L", self.0)
+    }
+}
+
+/// Grams of CO2 per liter in one volume of CO2
+const GRAMS_PER_LITER_PER_VOLUME: f32 = 1.96;
+
+impl From<CarbonationVolume> for GramsPerLiter {
+    fn from(v: CarbonationVolume) -> GramsPerLiter {
+        GramsPerLiter(v.0 * GRAMS_PER_LITER_PER_VOLUME)
+    }
+}
+
+impl From<GramsPerLiter> for CarbonationVolume {
+    fn from(g: GramsPerLiter) -> CarbonationVolume {
+        CarbonationVolume(g.0 / GRAMS_PER_LITER_PER_VOLUME)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_carbonation_volume() {
+        let gpl: GramsPerLiter = CarbonationVolume(2.5).into();
+        assert!(approx_eq!(f32, gpl.0, 4.9, epsilon = 0.01));
+
+        let vols: CarbonationVolume = gpl.into();
+        assert!(approx_eq!(f32, vols.0, 2.5, epsilon = 0.0001));
+
+        assert_eq!(format!("{}", CarbonationVolume(2.7)), "2.7 vols CO2");
+    }
+}
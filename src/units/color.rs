@@ -32,6 +32,66 @@ impl fmt::Display for Srm {
     }
 }
 
+/// Approximate beer colors for SRM 1 through 40
+const SRM_RGB: [(u8, u8, u8); 40] = [
+    (0xFF, 0xE6, 0x99),
+    (0xFF, 0xD8, 0x78),
+    (0xFF, 0xCA, 0x5A),
+    (0xFF, 0xBF, 0x42),
+    (0xFB, 0xB1, 0x23),
+    (0xF8, 0xA6, 0x00),
+    (0xF3, 0x9C, 0x00),
+    (0xEA, 0x8F, 0x00),
+    (0xE5, 0x85, 0x00),
+    (0xDE, 0x7C, 0x00),
+    (0xD7, 0x72, 0x00),
+    (0xCF, 0x69, 0x00),
+    (0xCB, 0x62, 0x00),
+    (0xC3, 0x59, 0x00),
+    (0xBB, 0x51, 0x00),
+    (0xB5, 0x4C, 0x00),
+    (0xB0, 0x45, 0x00),
+    (0xA6, 0x3E, 0x00),
+    (0xA1, 0x37, 0x00),
+    (0x9B, 0x32, 0x00),
+    (0x95, 0x2D, 0x00),
+    (0x8E, 0x29, 0x00),
+    (0x88, 0x23, 0x00),
+    (0x82, 0x1E, 0x00),
+    (0x7B, 0x1A, 0x00),
+    (0x77, 0x19, 0x00),
+    (0x70, 0x14, 0x00),
+    (0x6A, 0x0E, 0x00),
+    (0x66, 0x0D, 0x00),
+    (0x5E, 0x0B, 0x00),
+    (0x5A, 0x0A, 0x02),
+    (0x60, 0x09, 0x03),
+    (0x52, 0x09, 0x07),
+    (0x4C, 0x05, 0x05),
+    (0x47, 0x06, 0x06),
+    (0x44, 0x06, 0x07),
+    (0x3F, 0x07, 0x08),
+    (0x3B, 0x06, 0x07),
+    (0x3A, 0x07, 0x0B),
+    (0x36, 0x08, 0x0A),
+];
+
+impl Srm {
+    /// Approximate color of the beer as RGB, for display
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let srm = self.0.clamp(1.0, 40.0);
+        let low = (srm.floor() as usize).min(39) - 1;
+        let frac = srm - srm.floor().min(39.0);
+        let (a, b) = (SRM_RGB[low], SRM_RGB[(low + 1).min(39)]);
+        let lerp =
+            |x: u8, y: u8| (f32::from(x) + (f32::from(y) - f32::from(x)) * frac).round() as u8;
+        (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+    }
+}
+
 impl From<Srm> for Lovabond {
     fn from(v: Srm) -> Lovabond {
         Lovabond((v.0 + 0.76) / 1.3546)
@@ -46,13 +106,13 @@ impl From<Lovabond> for Srm {
 
 impl From<Srm> for Ebc {
     fn from(v: Srm) -> Ebc {
-        Ebc(v.0 * 1.97)
+        Ebc(v.0 / 0.508)
     }
 }
 
 impl From<Ebc> for Srm {
     fn from(v: Ebc) -> Srm {
-        Srm(v.0 / 1.97)
+        Srm(v.0 * 0.508)
     }
 }
 
@@ -93,4 +153,30 @@ mod test {
         let b = Into::<Srm>::into(Into::<Lovabond>::into(a));
         assert!(approx_eq!(f32, a.0, b.0, ulps = 10));
     }
+
+    #[test]
+    fn test_malt_ebc_round_trip() {
+        use crate::ingredients::Malt;
+
+        for malt in [
+            Malt::GladfieldGermanPilsner,
+            Malt::WeyermannMunich2,
+            Malt::DingemansSpecialB,
+        ] {
+            let ebc = malt.ebc();
+            let srm: Srm = ebc.into();
+            assert!(approx_eq!(f32, srm.0, ebc.0 * 0.508, epsilon = 0.001));
+            let back: Ebc = srm.into();
+            assert!(approx_eq!(f32, back.0, ebc.0, epsilon = 0.001));
+        }
+    }
+
+    #[test]
+    fn test_srm_to_rgb() {
+        assert_eq!(Srm(0.0).to_rgb(), (0xFF, 0xE6, 0x99));
+        assert_eq!(Srm(10.0).to_rgb(), (0xDE, 0x7C, 0x00));
+        assert_eq!(Srm(10.5).to_rgb(), (0xDB, 0x77, 0x00));
+        assert_eq!(Srm(80.0).to_rgb(), (0x36, 0x08, 0x0A));
+        assert_eq!(format!("{}", Srm(12.0)), "12.0 SRM");
+    }
 }
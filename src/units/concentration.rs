@@ -1,5 +1,5 @@
 use super::prelude::*;
-use crate::ingredients::{MaltDose, SugarDose};
+use crate::ingredients::{ExtractDose, MaltDose, SugarDose};
 use derive_more::{Add, Div, Mul, Sub, Sum};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -24,12 +24,59 @@ impl fmt::Display for Ppm {
 pub struct SpecificGravity(pub f32);
 
 impl fmt::Display for SpecificGravity {
+    /// The alternate form (`{:#}`) displays gravity points
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.3} s.g.", self.0)
+        if f.alternate() {
+            write!(f, "{:.0}", self.points())
+        } else {
+            write!(f, "{:.3} s.g.", self.0)
+        }
+    }
+}
+
+/// Gravity points, the part of a specific gravity above water in
+/// thousandths, e.g. 48 for 1.048
+///
+/// Points scale with the amount of extract and inversely with the volume
+/// it is dissolved in, which the specific gravity itself does not.
+#[derive(
+    Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sum, Sub, Mul, Div,
+)]
+pub struct GravityPoints(pub f32);
+
+impl fmt::Display for GravityPoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0} points", self.0)
+    }
+}
+
+impl GravityPoints {
+    /// Points from an amount of extract, in point-liters (e.g. the
+    /// points per kilogram per liter of a malt times its kilograms),
+    /// dissolved in the given volume
+    #[must_use]
+    pub fn per_volume(extract: f32, volume: Liters) -> GravityPoints {
+        GravityPoints(extract / volume.0)
+    }
+}
+
+impl From<SpecificGravity> for GravityPoints {
+    fn from(sg: SpecificGravity) -> GravityPoints {
+        GravityPoints((sg.0 - 1.0) * 1000.0)
+    }
+}
+
+impl From<GravityPoints> for SpecificGravity {
+    fn from(points: GravityPoints) -> SpecificGravity {
+        SpecificGravity(1.0 + points.0 / 1000.0)
     }
 }
 
 /// Plato (approx same as Brix).  Percentage of dissolved sugar.
+///
+/// Conversions to and from `SpecificGravity` use the standard cubic
+/// polynomial and its inverse, and are good to within about 0.001 s.g.
+/// over the range of wort, roughly 1.000 to 1.130 (0 to 30 °P).
 #[derive(
     Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sum, Sub, Mul, Div,
 )]
@@ -56,6 +103,10 @@ impl From<Plato> for SpecificGravity {
 }
 
 /// Brix
+///
+/// Brix is measured on sucrose solutions rather than wort, but for
+/// brewing purposes it is the same as Plato. Conversions to and from
+/// `SpecificGravity` are valid over roughly 1.000 to 1.130.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sum, Sub, Div)]
 pub struct Brix(pub f32);
 
@@ -77,33 +128,63 @@ impl From<Brix> for SpecificGravity {
     }
 }
 
+impl From<Plato> for Brix {
+    fn from(plato: Plato) -> Brix {
+        Brix(plato.0)
+    }
+}
+
+impl From<Brix> for Plato {
+    fn from(brix: Brix) -> Plato {
+        Plato(brix.0)
+    }
+}
+
 impl SpecificGravity {
-    /// Compute the specific gravity of the given malts and sugars
+    /// Gravity points, e.g. 56 for 1.056
+    #[must_use]
+    pub fn points(&self) -> f32 {
+        GravityPoints::from(*self).0
+    }
+
+    /// Specific gravity from degrees Plato (valid from 0 to about 30 °P)
+    #[must_use]
+    pub fn from_plato(plato: f32) -> Self {
+        Plato(plato).into()
+    }
+
+    /// Specific gravity from degrees Brix (valid from 0 to about 30 °Bx)
+    #[must_use]
+    pub fn from_brix(brix: f32) -> Self {
+        Brix(brix).into()
+    }
+
+    /// Compute the specific gravity of the given malts, sugars and extracts
     /// in the given volume of waters, at the given mash efficiency.
     #[must_use]
     pub fn from_recipe(
         malts: &[MaltDose],
         sugars: &[SugarDose],
-        volume: Gallons,
+        extracts: &[ExtractDose],
+        volume: Liters,
         mash_efficiency: f32,
     ) -> Self {
-        let mut points: f32 = 0.0;
+        let mut extract: f32 = 0.0;
 
         for malt_dose in malts {
-            let pounds: Pounds = malt_dose.weight.into();
-            let pts = malt_dose.malt.ppg() * pounds.0 * mash_efficiency;
-            let points_added = pts / volume.0;
-            points += points_added;
+            extract += malt_dose.malt.pkl() * malt_dose.weight.0 * mash_efficiency;
         }
 
         for sugar_dose in sugars {
-            let pounds: Pounds = sugar_dose.weight.into();
-            let pts = sugar_dose.sugar.ppg() * pounds.0;
-            let points_added = pts / volume.0;
-            points += points_added;
+            extract += sugar_dose.sugar.pkl() * sugar_dose.weight.0;
+        }
+
+        // Extract was already mashed, so mash efficiency does not apply
+        for extract_dose in extracts {
+            extract += extract_dose.extract.pkl() * extract_dose.weight.0;
         }
 
-        SpecificGravity(1.0 + points / 1000.0)
+        GravityPoints::per_volume(extract, volume).into()
     }
 }
 
@@ -118,8 +199,31 @@ impl fmt::Display for Abv {
     }
 }
 
+/// A formula for computing ABV from gravity
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AbvModel {
+    /// The simple linear estimate, (OG - FG) * 131.25. Good for normal
+    /// strength beers, but it understates strong beers.
+    Simple,
+
+    /// Michael Hall's formula (Zymurgy, 1995), which accounts for the
+    /// density of the alcohol and so stays accurate for strong beers.
+    #[default]
+    Hall,
+}
+
+impl fmt::Display for AbvModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AbvModel::Simple => write!(f, "Simple"),
+            AbvModel::Hall => write!(f, "Hall"),
+        }
+    }
+}
+
 impl Abv {
-    /// Compute Abv from original and final gravity.
+    /// Compute Abv from original and final gravity, with the `Hall`
+    /// formula.
     ///
     /// `dilution_fraction` must be 1.0 or greater.
     #[must_use]
@@ -127,10 +231,31 @@ impl Abv {
         original_gravity: SpecificGravity,
         final_gravity: SpecificGravity,
         dilution_fraction: f32,
+    ) -> Abv {
+        Abv::from_gravity_model(
+            original_gravity,
+            final_gravity,
+            dilution_fraction,
+            AbvModel::Hall,
+        )
+    }
+
+    /// Compute Abv from original and final gravity with the given model.
+    ///
+    /// `dilution_fraction` must be 1.0 or greater.
+    #[must_use]
+    pub fn from_gravity_model(
+        original_gravity: SpecificGravity,
+        final_gravity: SpecificGravity,
+        dilution_fraction: f32,
+        model: AbvModel,
     ) -> Abv {
         let og = original_gravity.0;
         let fg = final_gravity.0;
-        let abv = (76.08 * (og - fg) / (1.775 - og)) * (fg / 0.794) / 100.0;
+        let abv = match model {
+            AbvModel::Simple => (og - fg) * 131.25 / 100.0,
+            AbvModel::Hall => (76.08 * (og - fg) / (1.775 - og)) * (fg / 0.794) / 100.0,
+        };
         Abv(abv / dilution_fraction)
     }
 }
@@ -159,9 +284,14 @@ impl From<Abv> for Percent {
     }
 }
 
-/// 1 PPG = 8.3454 PKL
-/// via the conversion of pounds to kilograms 2.204623
-/// and the conversion of gallons to liters 3.7854
+/// Points per kilogram per liter, for each point per pound per gallon
+///
+/// 1 PPG = 8.3454 PKL, via the conversion of pounds to kilograms
+/// (2.204623) and of gallons to liters (3.7854).
+pub const PKL_PER_PPG: f32 = 8.3454;
+
+/// Points per pound per gallon of sucrose, which is 100% extract
+pub const SUCROSE_PPG: f32 = 46.214;
 
 #[cfg(test)]
 mod test {
@@ -185,4 +315,83 @@ mod test {
         println!("  a={a} b={b}");
         assert!(approx_eq!(f32, a.0, b.0, epsilon = 0.0005));
     }
+
+    #[test]
+    fn test_gravity_round_trip() {
+        let sg = SpecificGravity(1.048);
+        let plato: Plato = sg.into();
+        assert!(approx_eq!(f32, plato.0, 12.0, epsilon = 0.1));
+        let back = SpecificGravity::from_plato(plato.0);
+        assert!(approx_eq!(f32, back.0, sg.0, epsilon = 0.001));
+
+        let brix: Brix = plato.into();
+        let back = SpecificGravity::from_brix(brix.0);
+        assert!(approx_eq!(f32, back.0, sg.0, epsilon = 0.001));
+        let plato_again: Plato = brix.into();
+        assert!(approx_eq!(f32, plato_again.0, plato.0, epsilon = 0.0001));
+
+        // Across the whole range
+        for points in (0..=130).step_by(10) {
+            #[allow(clippy::cast_precision_loss)]
+            let sg = SpecificGravity(1.0 + points as f32 / 1000.0);
+            let plato: Plato = sg.into();
+            let brix: Brix = sg.into();
+            assert!(approx_eq!(
+                f32,
+                SpecificGravity::from(plato).0,
+                sg.0,
+                epsilon = 0.001
+            ));
+            assert!(approx_eq!(
+                f32,
+                SpecificGravity::from(brix).0,
+                sg.0,
+                epsilon = 0.001
+            ));
+        }
+    }
+
+    #[test]
+    fn test_abv_models() {
+        let abv = |og: f32, fg: f32, model: AbvModel| {
+            Abv::from_gravity_model(SpecificGravity(og), SpecificGravity(fg), 1.0, model).0
+        };
+
+        // A normal strength beer, about 5%
+        let simple = abv(1.050, 1.012, AbvModel::Simple);
+        let hall = abv(1.050, 1.012, AbvModel::Hall);
+        assert!(approx_eq!(f32, simple, 0.05, epsilon = 0.001));
+        assert!((hall - simple).abs() < 0.001);
+
+        // A strong beer, about 13%
+        let simple = abv(1.120, 1.020, AbvModel::Simple);
+        let hall = abv(1.120, 1.020, AbvModel::Hall);
+        assert!(approx_eq!(f32, simple, 0.13, epsilon = 0.005));
+        assert!(hall - simple > 0.01);
+
+        // The default is Hall
+        let default = Abv::from_gravity(SpecificGravity(1.120), SpecificGravity(1.020), 1.0);
+        assert!(approx_eq!(f32, default.0, hall, ulps = 2));
+        assert_eq!(AbvModel::default(), AbvModel::Hall);
+    }
+
+    #[test]
+    fn test_gravity_points() {
+        let sg = SpecificGravity(1.056);
+        assert!(approx_eq!(f32, sg.points(), 56.0, epsilon = 0.01));
+        assert_eq!(format!("{sg:#}"), "56");
+        assert_eq!(format!("{sg}"), "1.056 s.g.");
+
+        let points: GravityPoints = SpecificGravity(1.048).into();
+        assert!(approx_eq!(f32, points.0, 48.0, epsilon = 0.001));
+        let back: SpecificGravity = points.into();
+        assert!(approx_eq!(f32, back.0, 1.048, epsilon = 0.000_01));
+        assert_eq!(format!("{points}"), "48 points");
+
+        // The same extract in twice the volume has half the points
+        let small = GravityPoints::per_volume(480.0, Liters(10.0));
+        let large = GravityPoints::per_volume(480.0, Liters(20.0));
+        assert!(approx_eq!(f32, small.0, 48.0, epsilon = 0.001));
+        assert!(approx_eq!(f32, (small / 2.0).0, large.0, epsilon = 0.001));
+    }
 }
@@ -0,0 +1,37 @@
+use derive_more::{Add, Sub};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Length in meters (metric)
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct Meters(pub f32);
+
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0} m", self.0)
+    }
+}
+
+/// Length in feet (imperial)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct Feet(pub f32);
+
+impl fmt::Display for Feet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0} ft", self.0)
+    }
+}
+
+const FEET_PER_METER: f32 = 3.280_84;
+
+impl From<Meters> for Feet {
+    fn from(v: Meters) -> Feet {
+        Feet(v.0 * FEET_PER_METER)
+    }
+}
+
+impl From<Feet> for Meters {
+    fn from(v: Feet) -> Meters {
+        Meters(v.0 / FEET_PER_METER)
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, ops};
 
 /// Weight
 pub mod weight;
@@ -24,17 +24,29 @@ pub mod alkalinity;
 /// Time
 pub mod time;
 
+/// Length
+pub mod length;
+
+/// Pressure
+pub mod pressure;
+
+/// Carbonation (dissolved CO2)
+pub mod carbonation;
+
 /// Prelude, for importing all of the units
 pub mod prelude {
     pub use super::alkalinity::*;
+    pub use super::carbonation::*;
     pub use super::color::*;
     pub use super::concentration::*;
     pub use super::hardness::*;
+    pub use super::length::*;
+    pub use super::pressure::*;
     pub use super::temperature::*;
     pub use super::time::*;
     pub use super::volume::*;
     pub use super::weight::*;
-    pub use super::{Ibu, Ph};
+    pub use super::{Ibu, Lintner, Ph};
 }
 
 use serde::{Deserialize, Serialize};
@@ -49,6 +61,72 @@ impl fmt::Display for Ph {
     }
 }
 
+impl Ph {
+    /// Hydronium (H₃O⁺) concentration in mol/L
+    #[must_use]
+    pub fn to_hydronium(self) -> f32 {
+        10.0_f32.powf(-self.0)
+    }
+
+    /// pH from a hydronium (H₃O⁺) concentration in mol/L
+    #[must_use]
+    pub fn from_hydronium(hydronium: f32) -> Ph {
+        Ph(-hydronium.log10())
+    }
+
+    /// Blend with another solution.
+    ///
+    /// `fraction` is the fraction (by volume) of `other` in the blend.
+    ///
+    /// pH is logarithmic, so this mixes in hydronium concentration space
+    /// rather than averaging the pH values. The acidic side dominates.
+    /// Buffering is not considered.
+    #[must_use]
+    pub fn blend(self, other: Ph, fraction: f32) -> Ph {
+        let hydronium = self.to_hydronium() * (1.0 - fraction) + other.to_hydronium() * fraction;
+        Ph::from_hydronium(hydronium)
+    }
+}
+
+/// Shift a pH by some number of pH units
+impl ops::Add<f32> for Ph {
+    type Output = Ph;
+
+    fn add(self, shift: f32) -> Ph {
+        Ph(self.0 + shift)
+    }
+}
+
+/// Shift a pH by some number of pH units
+impl ops::Sub<f32> for Ph {
+    type Output = Ph;
+
+    fn sub(self, shift: f32) -> Ph {
+        Ph(self.0 - shift)
+    }
+}
+
+impl ops::AddAssign<f32> for Ph {
+    fn add_assign(&mut self, shift: f32) {
+        self.0 += shift;
+    }
+}
+
+impl ops::SubAssign<f32> for Ph {
+    fn sub_assign(&mut self, shift: f32) {
+        self.0 -= shift;
+    }
+}
+
+/// The difference between two pHs, in pH units
+impl ops::Sub<Ph> for Ph {
+    type Output = f32;
+
+    fn sub(self, other: Ph) -> f32 {
+        self.0 - other.0
+    }
+}
+
 /// Bitterness in IBU
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Ibu(pub f32);
@@ -58,3 +136,62 @@ impl fmt::Display for Ibu {
         write!(f, "{:.1} IBU", self.0)
     }
 }
+
+/// Diastatic power in degrees Lintner
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Lintner(pub f32);
+
+impl fmt::Display for Lintner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0} °Lintner", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_ph_hydronium() {
+        let a = Ph(5.4);
+        let b = Ph::from_hydronium(a.to_hydronium());
+        assert!(approx_eq!(f32, a.0, b.0, epsilon = 0.0001));
+
+        assert!(approx_eq!(f32, Ph(7.0).to_hydronium(), 1e-7, ulps = 10));
+    }
+
+    #[test]
+    fn test_ph_blend() {
+        // Equal parts are dominated by the acid, not the naive average of 6.0
+        let blend = Ph(5.0).blend(Ph(7.0), 0.5);
+        assert!(approx_eq!(f32, blend.0, 5.3, epsilon = 0.01));
+
+        // Blending is symmetric
+        let blend2 = Ph(7.0).blend(Ph(5.0), 0.5);
+        assert!(approx_eq!(f32, blend.0, blend2.0, epsilon = 0.0001));
+
+        // The endpoints are the inputs
+        assert!(approx_eq!(
+            f32,
+            Ph(5.0).blend(Ph(7.0), 0.0).0,
+            5.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            Ph(5.0).blend(Ph(7.0), 1.0).0,
+            7.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn test_ph_shift() {
+        let mut ph = Ph(5.4) + 0.2;
+        assert!(approx_eq!(f32, ph.0, 5.6, epsilon = 0.0001));
+        ph -= 0.3;
+        assert!(approx_eq!(f32, ph.0, 5.3, epsilon = 0.0001));
+        assert!(approx_eq!(f32, Ph(5.6) - Ph(5.2), 0.4, epsilon = 0.0001));
+    }
+}
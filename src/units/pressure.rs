@@ -0,0 +1,37 @@
+use derive_more::{Add, Sub};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Pressure in pounds per square inch, as read on a gauge (imperial)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct Psi(pub f32);
+
+impl fmt::Display for Psi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} psi", self.0)
+    }
+}
+
+/// Pressure in bar, as read on a gauge (metric)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize, Add, Sub)]
+pub struct Bar(pub f32);
+
+impl fmt::Display for Bar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} bar", self.0)
+    }
+}
+
+const PSI_PER_BAR: f32 = 14.503_774;
+
+impl From<Bar> for Psi {
+    fn from(v: Bar) -> Psi {
+        Psi(v.0 * PSI_PER_BAR)
+    }
+}
+
+impl From<Psi> for Bar {
+    fn from(v: Psi) -> Bar {
+        Bar(v.0 / PSI_PER_BAR)
+    }
+}
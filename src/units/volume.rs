@@ -27,7 +27,9 @@ impl PartialOrd for Milliliters {
 }
 
 /// Volume in Liters (L, metric)
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Add, Sum, Sub, Mul, Div)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Add, Sum, Sub, Mul, Div,
+)]
 pub struct Liters(pub f32);
 
 impl fmt::Display for Liters {
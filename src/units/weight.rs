@@ -80,7 +80,7 @@ pub struct Ounces(pub f32);
 
 impl fmt::Display for Ounces {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.0} oz", self.0)
+        write!(f, "{:.2} oz", self.0)
     }
 }
 
@@ -104,7 +104,7 @@ pub struct Pounds(pub f32);
 
 impl fmt::Display for Pounds {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.2} lbs", self.0)
+        write!(f, "{:.2} lb", self.0)
     }
 }
 
@@ -224,6 +224,41 @@ impl From<Pounds> for Kilograms {
     }
 }
 
+impl From<Grams> for Milligrams {
+    fn from(v: Grams) -> Self {
+        Milligrams(v.0 * MILLIGRAMS_PER_GRAM)
+    }
+}
+
+impl From<Kilograms> for Milligrams {
+    fn from(v: Kilograms) -> Self {
+        Milligrams(v.0 * GRAMS_PER_KILOGRAM * MILLIGRAMS_PER_GRAM)
+    }
+}
+
+impl From<Ounces> for Milligrams {
+    fn from(v: Ounces) -> Self {
+        Milligrams(v.0 * GRAMS_PER_OUNCE * MILLIGRAMS_PER_GRAM)
+    }
+}
+
+impl From<Pounds> for Milligrams {
+    fn from(v: Pounds) -> Self {
+        Milligrams(v.0 * OUNCES_PER_POUND * GRAMS_PER_OUNCE * MILLIGRAMS_PER_GRAM)
+    }
+}
+
+impl Kilograms {
+    /// Whole pounds and the remaining ounces, e.g. for shopping lists
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_pounds_ounces(&self) -> (u32, f32) {
+        let pounds: Pounds = (*self).into();
+        let whole = pounds.0.max(0.0).floor();
+        (whole as u32, (pounds.0.max(0.0) - whole) * OUNCES_PER_POUND)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -262,5 +297,37 @@ mod test {
         assert!(approx_eq!(f32, a.0, b.0, ulps = 10));
         let b = Into::<Kilograms>::into(Into::<Pounds>::into(a));
         assert!(approx_eq!(f32, a.0, b.0, ulps = 10));
+        let b = Into::<Kilograms>::into(Into::<Milligrams>::into(a));
+        assert!(approx_eq!(f32, a.0, b.0, ulps = 10));
+
+        let a = Milligrams(16.5);
+        let b = Into::<Milligrams>::into(Into::<Grams>::into(a));
+        assert!(approx_eq!(f32, a.0, b.0, ulps = 10));
+        let b = Into::<Milligrams>::into(Into::<Kilograms>::into(a));
+        assert!(approx_eq!(f32, a.0, b.0, epsilon = 0.0001));
+        let b = Into::<Milligrams>::into(Into::<Ounces>::into(a));
+        assert!(approx_eq!(f32, a.0, b.0, epsilon = 0.0001));
+        let b = Into::<Milligrams>::into(Into::<Pounds>::into(a));
+        assert!(approx_eq!(f32, a.0, b.0, epsilon = 0.0001));
+    }
+
+    #[test]
+    fn test_weight_display() {
+        assert_eq!(format!("{}", Into::<Grams>::into(Pounds(1.0))), "454 g");
+        assert_eq!(format!("{}", Pounds(1.0)), "1.00 lb");
+        assert_eq!(format!("{}", Ounces(0.5)), "0.50 oz");
+        assert_eq!(format!("{}", Kilograms(1.5)), "1.500 kg");
+        assert_eq!(format!("{}", Milligrams(250.0)), "250 mg");
+    }
+
+    #[test]
+    fn test_to_pounds_ounces() {
+        let (pounds, ounces) = Kilograms(1.0).to_pounds_ounces();
+        assert_eq!(pounds, 2);
+        assert!(approx_eq!(f32, ounces, 3.274, epsilon = 0.001));
+
+        let (pounds, ounces) = Into::<Kilograms>::into(Pounds(5.5)).to_pounds_ounces();
+        assert_eq!(pounds, 5);
+        assert!(approx_eq!(f32, ounces, 8.0, epsilon = 0.001));
     }
 }
@@ -40,6 +40,13 @@ pub enum Warning {
         fraction_base_malts: f32,
     },
 
+    /// Diastatic power of the whole grist is too low for the mash to
+    /// convert itself
+    LowConversionPower {
+        /// The blended diastatic power of the grist
+        diastatic_power: Lintner,
+    },
+
     /// ExcessMalt
     ExcessMalt {
         /// The malt in excess
@@ -100,6 +107,61 @@ pub enum Warning {
         yeast_min: Celsius,
     },
 
+    /// Fermentation temperature is too warm for the clean profile the
+    /// style calls for
+    EstersOutOfStyle {
+        /// Fermentation temp
+        ferment_temp: Celsius,
+
+        /// The max the style should be fermented at
+        style_max: Celsius,
+    },
+
+    /// Water has enough of an ion to taste bad or harsh
+    HarshWater {
+        /// The ion
+        ion: Ion,
+
+        /// Its concentration in the adjusted water
+        ppm: Ppm,
+
+        /// The concentration above which it tastes bad
+        threshold: Ppm,
+    },
+
+    /// The recipe computes an acid dose, but the brewery has no acids
+    NoAcidAvailable,
+
+    /// The yeast is STA1 positive (diastaticus)
+    DiastaticusRisk(Yeast),
+
+    /// A salt dose is too small to weigh on a home scale
+    UnweighableSaltDose {
+        /// The salt
+        salt: Salt,
+
+        /// The dose
+        mg: Milligrams,
+    },
+
+    /// Heavy dry hopping may restart fermentation
+    HopCreep {
+        /// The total dry hop rate, in grams per liter
+        grams_per_liter: f32,
+    },
+
+    /// The yeast nutrient dose adds too much zinc
+    ExcessZinc {
+        /// The yeast nutrient
+        nutrient: YeastNutrient,
+
+        /// The zinc it adds
+        zinc: Ppm,
+
+        /// The most zinc the wort should have
+        maximum: Ppm,
+    },
+
     /// Yeast cannot tolerate the alcohol
     TooMuchAlcohol {
         /// abv
@@ -159,6 +221,10 @@ pub enum Warning {
 
     /// Acidity Needed Cancelling
     AcidityNeededCancelling,
+
+    /// The recipe has no malts, sugars or extracts to reach the original
+    /// gravity with
+    NoFermentables,
 }
 
 impl fmt::Display for Warning {
@@ -200,6 +266,13 @@ impl fmt::Display for Warning {
             } => {
                 write!(f, "Not enough base malt: {fraction_base_malts} < 0.7")
             }
+            Self::LowConversionPower { diastatic_power } => {
+                write!(
+                    f,
+                    "The grist has a diastatic power of {diastatic_power}, \
+                     below the 30 °Lintner needed to convert itself"
+                )
+            }
             Self::ExcessMalt {
                 malt,
                 percent,
@@ -251,6 +324,70 @@ impl fmt::Display for Warning {
                     "The fermentation temp of {ferment_temp} is colder than the yesat minimum of {yeast_min}"
                 )
             }
+            Self::EstersOutOfStyle {
+                ferment_temp,
+                style_max,
+            } => {
+                write!(
+                    f,
+                    "The fermentation temp of {ferment_temp} will produce esters \
+                     inappropriate for the style, which should ferment at or below {style_max}"
+                )
+            }
+            Self::HarshWater {
+                ion,
+                ppm,
+                threshold,
+            } => {
+                write!(
+                    f,
+                    "The adjusted water has {ppm} of {ion}, above {threshold} it tastes harsh"
+                )
+            }
+            Self::NoAcidAvailable => {
+                write!(f, "No acid is available to lower the mash pH")
+            }
+            Self::UnweighableSaltDose { salt, mg } => {
+                let dose = SaltDose {
+                    salt: *salt,
+                    mg: *mg,
+                };
+                write!(
+                    f,
+                    "{mg} of {salt} is too little to weigh. Instead dissolve {} g of it in \
+                     1 L of distilled water, and add {} of that.",
+                    SaltDose::STOCK_G_PER_L,
+                    dose.as_stock_solution(SaltDose::STOCK_G_PER_L)
+                )
+            }
+            Self::HopCreep { grams_per_liter } => {
+                write!(
+                    f,
+                    "Dry hopping at {grams_per_liter:.1} g/L may restart fermentation (hop \
+                     creep), lowering the final gravity and producing diacetyl. Check the \
+                     gravity and do a forced diacetyl test after dry hopping."
+                )
+            }
+            Self::ExcessZinc {
+                nutrient,
+                zinc,
+                maximum,
+            } => {
+                write!(
+                    f,
+                    "The {nutrient} dose adds {zinc} of zinc, more than the {maximum} the \
+                     yeast can take without stress and a metallic taste. Use a nutrient \
+                     without zinc."
+                )
+            }
+            Self::DiastaticusRisk(yeast) => {
+                write!(
+                    f,
+                    "{yeast} is STA1 positive (diastaticus). It may attenuate well past \
+                     predictions, with the gravity dropping near 1.000. Keep it away from \
+                     other batches, and do not bottle until the gravity is stable."
+                )
+            }
             Self::TooMuchAlcohol { abv, yeast_max } => {
                 write!(
                     f,
@@ -302,6 +439,13 @@ impl fmt::Display for Warning {
                      in the first place. Please adjust the recipe."
                 )
             }
+            Self::NoFermentables => {
+                write!(
+                    f,
+                    "The recipe has no malts, sugars or extracts, so the original gravity \
+                     cannot be reached."
+                )
+            }
         }
     }
 }
@@ -319,6 +463,7 @@ impl Warning {
                 | Self::BoilKettleTooSmall { .. }
                 | Self::TooMuchMash { .. }
                 | Self::ImpossibleInfusionTemperature(_)
+                | Self::NoFermentables
         )
     }
 }